@@ -4,7 +4,9 @@ use std::io::BufRead;
 
 use criterion::{black_box, Bencher, Criterion};
 use criterion::{criterion_group, criterion_main};
-use radix_heap::RadixHeapMap;
+use radix_heap::{
+    BucketQueue, ByteRadixHeapMap, CompactRadixHeapMap, LazyRadixHeapMap, RadixHeapBy, RadixHeapMap,
+};
 
 type Pos = (u32, u32);
 
@@ -70,6 +72,16 @@ impl Bool2D {
 }
 
 fn astar<H: AStarHeap>(b: &mut Bencher) {
+    astar_with::<H>(b, false);
+}
+
+/// Like `astar`, but creates a new heap for every search instead of reusing
+/// one, so that the allocations of the heap are part of the measurement.
+fn astar_fresh<H: AStarHeap>(b: &mut Bencher) {
+    astar_with::<H>(b, true);
+}
+
+fn astar_with<H: AStarHeap>(b: &mut Bencher, fresh: bool) {
     let from = (40, 75);
     let to = (20, 10);
     let expected_distance = 85;
@@ -83,7 +95,11 @@ fn astar<H: AStarHeap>(b: &mut Bencher) {
     let mut heap = H::new();
 
     b.iter(|| {
-        heap.clear();
+        if fresh {
+            heap = H::new();
+        } else {
+            heap.clear();
+        }
         visited.clear();
 
         heap.push(AStarEntry {
@@ -187,6 +203,114 @@ impl AStarHeap for RadixHeapMap<Reverse<u32>, (Pos, u32)> {
     }
 }
 
+impl AStarHeap for ByteRadixHeapMap<Reverse<u32>, (Pos, u32)> {
+    #[inline]
+    fn new() -> Self {
+        ByteRadixHeapMap::new()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    #[inline]
+    fn push(&mut self, entry: AStarEntry) {
+        self.push(Reverse(entry.full_cost), (entry.pos, entry.cost))
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<AStarEntry> {
+        self.pop()
+            .map(|(Reverse(full_cost), (pos, cost))| AStarEntry {
+                pos,
+                cost,
+                full_cost,
+            })
+    }
+}
+
+impl AStarHeap for LazyRadixHeapMap<Reverse<u32>, (Pos, u32)> {
+    #[inline]
+    fn new() -> Self {
+        LazyRadixHeapMap::new()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    #[inline]
+    fn push(&mut self, entry: AStarEntry) {
+        self.push(Reverse(entry.full_cost), (entry.pos, entry.cost))
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<AStarEntry> {
+        self.pop()
+            .map(|(Reverse(full_cost), (pos, cost))| AStarEntry {
+                pos,
+                cost,
+                full_cost,
+            })
+    }
+}
+
+type AStarBy = RadixHeapBy<Reverse<u32>, AStarEntry, fn(&AStarEntry) -> Reverse<u32>>;
+
+/// Stores whole entries, so nothing is split into a key and a payload.
+impl AStarHeap for AStarBy {
+    #[inline]
+    fn new() -> Self {
+        RadixHeapBy::new(|entry| Reverse(entry.full_cost))
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    #[inline]
+    fn push(&mut self, entry: AStarEntry) {
+        self.push(entry)
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<AStarEntry> {
+        self.pop()
+    }
+}
+
+/// With unit edge costs and a consistent heuristic, the estimates of the
+/// neighbors of a node are at most 2 above its own.
+impl AStarHeap for BucketQueue<Reverse<u32>, (Pos, u32)> {
+    #[inline]
+    fn new() -> Self {
+        BucketQueue::new(2)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.clear()
+    }
+
+    #[inline]
+    fn push(&mut self, entry: AStarEntry) {
+        self.push(Reverse(entry.full_cost), (entry.pos, entry.cost))
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<AStarEntry> {
+        self.pop()
+            .map(|(Reverse(full_cost), (pos, cost))| AStarEntry {
+                pos,
+                cost,
+                full_cost,
+            })
+    }
+}
+
 impl AStarHeap for BinaryHeap<AStarEntry> {
     #[inline]
     fn new() -> Self {
@@ -227,6 +351,132 @@ fn pushpop_radix(b: &mut Bencher) {
     });
 }
 
+fn pushpop_lazy(b: &mut Bencher) {
+    let mut heap = LazyRadixHeapMap::<i32, ()>::new();
+
+    b.iter(|| {
+        heap.push(0, ());
+
+        for _ in 0..10000 {
+            let (n, _) = heap.pop().unwrap();
+
+            for i in 0..4 {
+                heap.push(n - i, ());
+            }
+        }
+
+        heap.clear();
+    });
+}
+
+fn pushpop_bytewise(b: &mut Bencher) {
+    let mut heap = ByteRadixHeapMap::<i32, ()>::new();
+
+    b.iter(|| {
+        heap.push(0, ());
+
+        for _ in 0..10000 {
+            let (n, _) = heap.pop().unwrap();
+
+            for i in 0..4 {
+                heap.push(n - i, ());
+            }
+        }
+
+        heap.clear();
+    });
+}
+
+/// Keys spread across the whole range of `u64`, such as hashes or timestamps
+/// in nanoseconds.
+fn spread_keys() -> Vec<u64> {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+
+    (0..10000)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            state
+        })
+        .collect()
+}
+
+fn spread_radix(b: &mut Bencher) {
+    let keys = spread_keys();
+    let mut heap = RadixHeapMap::<u64, ()>::new();
+
+    b.iter(|| {
+        heap.extend(keys.iter().map(|&k| (k, ())));
+        while let Some(entry) = heap.pop() {
+            black_box(entry);
+        }
+        heap.clear();
+    });
+}
+
+fn spread_bytewise(b: &mut Bencher) {
+    let keys = spread_keys();
+    let mut heap = ByteRadixHeapMap::<u64, ()>::new();
+
+    b.iter(|| {
+        heap.extend(keys.iter().map(|&k| (k, ())));
+        while let Some(entry) = heap.pop() {
+            black_box(entry);
+        }
+        heap.clear();
+    });
+}
+
+fn spread_compact(b: &mut Bencher) {
+    let keys = spread_keys();
+    let mut heap = CompactRadixHeapMap::<u64, ()>::new();
+
+    b.iter(|| {
+        heap.extend(keys.iter().map(|&k| (k, ())));
+        while let Some(entry) = heap.pop() {
+            black_box(entry);
+        }
+        heap.clear();
+    });
+}
+
+/// Timestamps in nanoseconds within a few seconds of each other, with a
+/// payload, where most entries end up within 32 bits of the top key.
+fn timestamps_keys() -> Vec<(u64, [u32; 2])> {
+    spread_keys()
+        .into_iter()
+        .enumerate()
+        .map(|(i, k)| (1_700_000_000_000_000_000 + (k >> 32), [i as u32; 2]))
+        .collect()
+}
+
+fn timestamps_radix(b: &mut Bencher) {
+    let entries = timestamps_keys();
+    let mut heap = RadixHeapMap::<u64, [u32; 2]>::new();
+
+    b.iter(|| {
+        heap.extend(entries.iter().copied());
+        while let Some(entry) = heap.pop() {
+            black_box(entry);
+        }
+        heap.clear();
+    });
+}
+
+fn timestamps_compact(b: &mut Bencher) {
+    let entries = timestamps_keys();
+    let mut heap = CompactRadixHeapMap::<u64, [u32; 2]>::new();
+
+    b.iter(|| {
+        heap.extend(entries.iter().copied());
+        while let Some(entry) = heap.pop() {
+            black_box(entry);
+        }
+        heap.clear();
+    });
+}
+
 fn pushpop_binary(b: &mut Bencher) {
     let mut heap = BinaryHeap::<i32>::new();
 
@@ -250,9 +500,31 @@ fn criterion_benchmark(c: &mut Criterion) {
         "astar_radix",
         astar::<RadixHeapMap<Reverse<u32>, (Pos, u32)>>,
     );
+    c.bench_function(
+        "astar_bytewise",
+        astar::<ByteRadixHeapMap<Reverse<u32>, (Pos, u32)>>,
+    );
+    c.bench_function(
+        "astar_lazy",
+        astar::<LazyRadixHeapMap<Reverse<u32>, (Pos, u32)>>,
+    );
+    c.bench_function("astar_radix_by", astar::<AStarBy>);
+    c.bench_function("astar_dial", astar::<BucketQueue<Reverse<u32>, (Pos, u32)>>);
     c.bench_function("astar_binary", astar::<BinaryHeap<AStarEntry>>);
+    c.bench_function(
+        "astar_fresh_radix",
+        astar_fresh::<RadixHeapMap<Reverse<u32>, (Pos, u32)>>,
+    );
+    c.bench_function("astar_fresh_binary", astar_fresh::<BinaryHeap<AStarEntry>>);
     c.bench_function("pushpop_radix", pushpop_radix);
+    c.bench_function("pushpop_lazy", pushpop_lazy);
+    c.bench_function("pushpop_bytewise", pushpop_bytewise);
     c.bench_function("pushpop_binary", pushpop_binary);
+    c.bench_function("spread_radix", spread_radix);
+    c.bench_function("spread_bytewise", spread_bytewise);
+    c.bench_function("spread_compact", spread_compact);
+    c.bench_function("timestamps_radix", timestamps_radix);
+    c.bench_function("timestamps_compact", timestamps_compact);
 }
 
 criterion_group!(benches, criterion_benchmark);
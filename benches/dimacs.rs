@@ -0,0 +1,84 @@
+//! Shortest paths on a DIMACS graph.
+//!
+//! Uses the small grid in `benches/grid.gr` by default. Set `DIMACS_GRAPH` to
+//! the path of a `.gr` file, such as one of the road networks of the 9th
+//! DIMACS Implementation Challenge, to benchmark on it instead.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use criterion::{black_box, Criterion};
+use criterion::{criterion_group, criterion_main};
+use radix_heap::dimacs::DimacsGraph;
+use radix_heap::RadixHeapMap;
+
+fn load() -> DimacsGraph {
+    match std::env::var("DIMACS_GRAPH") {
+        Ok(path) => std::fs::read_to_string(path).unwrap().parse().unwrap(),
+        Err(_) => include_str!("grid.gr").parse().unwrap(),
+    }
+}
+
+fn dijkstra_radix(graph: &DimacsGraph, start: u32) -> Vec<u32> {
+    let mut costs = vec![u32::MAX; graph.node_count() as usize + 1];
+    let mut heap = RadixHeapMap::new();
+
+    costs[start as usize] = 0;
+    heap.push(Reverse(0), start);
+
+    while let Some((Reverse(cost), node)) = heap.pop() {
+        if cost > costs[node as usize] {
+            continue;
+        }
+
+        for &(next, weight) in graph.arcs(node) {
+            let next_cost = cost + weight;
+
+            if next_cost < costs[next as usize] {
+                costs[next as usize] = next_cost;
+                heap.push(Reverse(next_cost), next);
+            }
+        }
+    }
+
+    costs
+}
+
+fn dijkstra_binary(graph: &DimacsGraph, start: u32) -> Vec<u32> {
+    let mut costs = vec![u32::MAX; graph.node_count() as usize + 1];
+    let mut heap = BinaryHeap::new();
+
+    costs[start as usize] = 0;
+    heap.push(Reverse((0, start)));
+
+    while let Some(Reverse((cost, node))) = heap.pop() {
+        if cost > costs[node as usize] {
+            continue;
+        }
+
+        for &(next, weight) in graph.arcs(node) {
+            let next_cost = cost + weight;
+
+            if next_cost < costs[next as usize] {
+                costs[next as usize] = next_cost;
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    costs
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let graph = load();
+
+    c.bench_function("dimacs_dijkstra_radix", |b| {
+        b.iter(|| dijkstra_radix(&graph, black_box(1)))
+    });
+    c.bench_function("dimacs_dijkstra_binary", |b| {
+        b.iter(|| dijkstra_binary(&graph, black_box(1)))
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
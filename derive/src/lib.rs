@@ -0,0 +1,110 @@
+//! Derive macro for the `Radix` trait of the `radix-heap` crate.
+//!
+//! Use it through the `derive` feature of `radix-heap`, which re-exports it as
+//! `radix_heap::Radix`.
+
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Index, Member};
+
+/// Derives `Radix` for structs, comparing fields lexicographically in
+/// declaration order.
+///
+/// See the documentation of `radix_heap::Radix`.
+#[proc_macro_derive(Radix)]
+pub fn derive_radix(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2, Error> {
+    let (path, fields) = match &input.data {
+        Data::Struct(data) => (quote!(Self), &data.fields),
+        Data::Enum(data) if data.variants.len() == 1 => {
+            let variant = &data.variants[0].ident;
+            (quote!(Self::#variant), &data.variants[0].fields)
+        }
+        Data::Enum(_) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Radix can only be derived for enums with exactly one variant",
+            ))
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Radix cannot be derived for unions",
+            ))
+        }
+    };
+
+    let members: Vec<Member> = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| Member::Named(field.ident.clone().unwrap()))
+            .collect(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len())
+            .map(|i| Member::Unnamed(Index::from(i)))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+
+    let lhs: Vec<_> = (0..members.len())
+        .map(|i| quote::format_ident!("__self_{}", i))
+        .collect();
+    let rhs: Vec<_> = (0..members.len())
+        .map(|i| quote::format_ident!("__other_{}", i))
+        .collect();
+
+    let where_clause = input.generics.make_where_clause();
+    for ty in &types {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::radix_heap::Radix));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Bind the fields by destructuring, which works the same for structs and
+    // single-variant enums.
+    let pattern = |bindings: &[proc_macro2::Ident]| match fields {
+        Fields::Named(_) => quote!(#path { #(#members: #bindings),* }),
+        Fields::Unnamed(_) => quote!(#path ( #(#bindings),* )),
+        Fields::Unit => quote!(#path),
+    };
+    let self_pattern = pattern(&lhs);
+    let other_pattern = pattern(&rhs);
+
+    Ok(quote! {
+        impl #impl_generics ::radix_heap::Radix for #name #ty_generics #where_clause {
+            #[inline]
+            #[allow(unused_variables, unused_mut)]
+            fn radix_similarity(&self, other: &Self) -> u32 {
+                let #self_pattern = self;
+                let #other_pattern = other;
+                let mut similarity = 0;
+
+                #(
+                    let s = ::radix_heap::Radix::radix_similarity(#lhs, #rhs);
+                    similarity += s;
+                    if s < <#types as ::radix_heap::Radix>::RADIX_BITS {
+                        return similarity;
+                    }
+                )*
+
+                similarity
+            }
+
+            const RADIX_BITS: u32 = 0 #(+ <#types as ::radix_heap::Radix>::RADIX_BITS)*;
+        }
+    })
+}
@@ -0,0 +1,130 @@
+//! Python bindings for radix-heap.
+//!
+//! This exposes max-heaps with `i64` keys to Python, either storing a Python
+//! object with each key or storing only keys.
+
+use ::radix_heap::RadixHeapMap;
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+/// Checks that `key` can be pushed onto `heap`, since pushing a key larger
+/// than the top key panics.
+fn check_key<V>(heap: &RadixHeapMap<i64, V>, key: i64) -> PyResult<()> {
+    match heap.top() {
+        Some(top) if key > top => Err(PyValueError::new_err(format!(
+            "key {} is larger than the top key {}",
+            key, top
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// A monotone max-heap of integer keys and Python objects.
+///
+/// Pushed keys must not be larger than the last popped key.
+#[pyclass]
+#[derive(Default)]
+struct RadixHeap {
+    heap: RadixHeapMap<i64, PyObject>,
+}
+
+#[pymethods]
+impl RadixHeap {
+    #[new]
+    fn new() -> RadixHeap {
+        RadixHeap::default()
+    }
+
+    /// Pushes a key and value. Raises ValueError if the key is larger than
+    /// the top key.
+    fn push(&mut self, key: i64, value: PyObject) -> PyResult<()> {
+        check_key(&self.heap, key)?;
+        self.heap.push(key, value);
+        Ok(())
+    }
+
+    /// Removes the entry with the largest key and returns it as a tuple of
+    /// key and value, or None if empty.
+    fn pop(&mut self) -> Option<(i64, PyObject)> {
+        self.heap.pop()
+    }
+
+    /// Returns the entry with the largest key without removing it, or None if
+    /// empty.
+    fn peek(&mut self, py: Python) -> Option<(i64, PyObject)> {
+        self.heap
+            .peek()
+            .map(|(key, value)| (*key, value.clone_ref(py)))
+    }
+
+    /// The top key, which pushed keys must not be larger than, or None if
+    /// not set yet.
+    fn top(&self) -> Option<i64> {
+        self.heap.top()
+    }
+
+    /// Removes all entries and resets the top key.
+    fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    fn __len__(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+/// A monotone max-heap of integer keys.
+///
+/// Pushed keys must not be larger than the last popped key.
+#[pyclass]
+#[derive(Default)]
+struct RadixHeapKeys {
+    heap: RadixHeapMap<i64, ()>,
+}
+
+#[pymethods]
+impl RadixHeapKeys {
+    #[new]
+    fn new() -> RadixHeapKeys {
+        RadixHeapKeys::default()
+    }
+
+    /// Pushes a key. Raises ValueError if the key is larger than the top key.
+    fn push(&mut self, key: i64) -> PyResult<()> {
+        check_key(&self.heap, key)?;
+        self.heap.push(key, ());
+        Ok(())
+    }
+
+    /// Removes and returns the largest key, or None if empty.
+    fn pop(&mut self) -> Option<i64> {
+        self.heap.pop().map(|(key, ())| key)
+    }
+
+    /// Returns the largest key without removing it, or None if empty.
+    fn peek(&mut self) -> Option<i64> {
+        self.heap.peek_key()
+    }
+
+    /// The top key, which pushed keys must not be larger than, or None if
+    /// not set yet.
+    fn top(&self) -> Option<i64> {
+        self.heap.top()
+    }
+
+    /// Removes all keys and resets the top key.
+    fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    fn __len__(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+/// Fast monotone priority queues.
+#[pymodule]
+fn radix_heap(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<RadixHeap>()?;
+    m.add_class::<RadixHeapKeys>()?;
+    Ok(())
+}
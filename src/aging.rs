@@ -0,0 +1,225 @@
+use crate::{Radix, RadixHeapMap};
+use alloc::collections::VecDeque;
+
+/// A priority queue that boosts entries the longer they wait, so that low
+/// priority entries are not starved.
+///
+/// Time is counted in pops. Entries pushed within the same `step` pops form
+/// an age class, and each further `step` pops raise the class by one, up to
+/// `max_class`. Entries in a higher class are popped before any entry in a
+/// lower class, and entries in the same class are popped by key. So with a
+/// `max_class` of zero this is an ordinary max-heap, and the smaller `step`
+/// is, the sooner waiting entries overtake new ones.
+///
+/// Unlike a [`RadixHeapMap`], any key may be pushed at any time. For a
+/// min-heap, use [`Reverse`](std::cmp::Reverse) keys.
+///
+/// ```
+/// let mut heap = radix_heap::AgingHeap::new(2, 1);
+///
+/// heap.push(1u32, "low");
+/// heap.push(8, "a");
+/// heap.push(9, "b");
+/// assert_eq!(heap.pop(), Some((9, "b")));
+/// assert_eq!(heap.pop(), Some((8, "a")));
+///
+/// // "low" has now waited two pops, so it beats a fresh, higher key.
+/// heap.push(7, "c");
+/// assert_eq!(heap.pop(), Some((1, "low")));
+/// assert_eq!(heap.pop(), Some((7, "c")));
+/// ```
+#[derive(Clone)]
+pub struct AgingHeap<K, V> {
+    /// The entries of each age class by the time they were pushed at, divided
+    /// by `step`, oldest first.
+    epochs: VecDeque<(u64, RadixHeapMap<K, V>)>,
+    clock: u64,
+    step: u64,
+    max_class: u64,
+    len: usize,
+}
+
+impl<K: Radix + Ord + Copy, V> AgingHeap<K, V> {
+    /// Create an empty `AgingHeap` where waiting entries rise one age class
+    /// every `step` pops, up to `max_class`.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `step` is zero.
+    pub fn new(step: u64, max_class: u64) -> AgingHeap<K, V> {
+        assert!(step > 0, "Step must be positive");
+
+        AgingHeap {
+            epochs: VecDeque::new(),
+            clock: 0,
+            step,
+            max_class,
+            len: 0,
+        }
+    }
+
+    /// Sets the aging rate for all entries, including those already in the
+    /// heap.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `step` is zero.
+    pub fn set_aging(&mut self, step: u64, max_class: u64) {
+        assert!(step > 0, "Step must be positive");
+
+        let old_step = self.step;
+        let epochs = core::mem::take(&mut self.epochs);
+
+        for (epoch, heap) in epochs {
+            let epoch = epoch * old_step / step;
+
+            match self.epochs.back_mut() {
+                Some((last, last_heap)) if *last == epoch => {
+                    for (key, value) in heap {
+                        last_heap.raise_top(key);
+                        last_heap.push(key, value);
+                    }
+                }
+                _ => self.epochs.push_back((epoch, heap)),
+            }
+        }
+
+        self.step = step;
+        self.max_class = max_class;
+    }
+
+    /// Pushes a new key value pair onto the heap.
+    pub fn push(&mut self, key: K, value: V) {
+        let epoch = self.clock / self.step;
+
+        if !matches!(self.epochs.back(), Some((last, _)) if *last == epoch) {
+            self.epochs.push_back((epoch, RadixHeapMap::new()));
+        }
+
+        let (_, heap) = self.epochs.back_mut().unwrap();
+        heap.raise_top(key);
+        heap.push(key, value);
+        self.len += 1;
+    }
+
+    /// Removes the entry with the highest age class and greatest key within
+    /// it, and returns it. Returns `None` if empty.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let now = self.clock / self.step;
+        let max_class = self.max_class;
+        let class = |epoch: u64| (now - epoch).min(max_class);
+        let oldest = class(self.epochs.front()?.0);
+
+        let mut best: Option<(usize, K)> = None;
+
+        for (i, (epoch, heap)) in self.epochs.iter_mut().enumerate() {
+            if class(*epoch) < oldest {
+                break;
+            }
+
+            heap.constrain();
+            let key = heap.top().unwrap();
+
+            if !matches!(best, Some((_, best)) if best >= key) {
+                best = Some((i, key));
+            }
+        }
+
+        let (i, _) = best.unwrap();
+        let item = self.epochs[i].1.pop();
+
+        if self.epochs[i].1.is_empty() {
+            self.epochs.remove(i);
+        }
+
+        self.clock += 1;
+        self.len -= 1;
+        item
+    }
+
+    /// Returns the number of entries in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no entries in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Drops all entries from the heap.
+    pub fn clear(&mut self) {
+        self.epochs.clear();
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::AgingHeap;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn no_aging_sorts() {
+        fn prop(xs: Vec<i32>) -> bool {
+            let mut heap = AgingHeap::new(1, 0);
+            xs.iter().for_each(|&x| heap.push(x, ()));
+
+            let mut xs = xs;
+            xs.sort_unstable();
+
+            while let Some(x) = xs.pop() {
+                if heap.pop() != Some((x, ())) {
+                    return false;
+                }
+            }
+
+            heap.is_empty()
+        }
+
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+    }
+
+    #[test]
+    fn no_starvation() {
+        let mut heap = AgingHeap::new(4, 3);
+        heap.push(Reverse(100u32), "low");
+
+        let mut pops = 0;
+        loop {
+            heap.push(Reverse(0), "high");
+            pops += 1;
+
+            if heap.pop() == Some((Reverse(100), "low")) {
+                break;
+            }
+        }
+
+        assert_eq!(pops, 5);
+        assert_eq!(heap.len(), 1);
+    }
+
+    #[test]
+    fn set_aging() {
+        let mut heap = AgingHeap::new(1, 10);
+        heap.push(1u8, 'a');
+        heap.push(2, 'b');
+        assert_eq!(heap.pop(), Some((2, 'b')));
+        heap.push(3, 'c');
+        assert_eq!(heap.pop(), Some((1, 'a')));
+        heap.push(5, 'd');
+
+        assert_eq!(heap.epochs.len(), 2);
+        heap.set_aging(100, 0);
+        assert_eq!(heap.epochs.len(), 1);
+
+        assert_eq!(heap.pop(), Some((5, 'd')));
+        assert_eq!(heap.pop(), Some((3, 'c')));
+        assert!(heap.is_empty());
+    }
+}
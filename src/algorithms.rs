@@ -0,0 +1,406 @@
+//! Shortest path algorithms driven by a [`RadixHeapMap`].
+//!
+//! [`dijkstra_with`] takes the queue to use instead, so that any
+//! [`MonotonePriorityQueue`] can drive it, such as a
+//! [`BucketQueue`](crate::BucketQueue) for small edge costs.
+//!
+//! Costs are pushed as `Reverse<W>` keys so the max-heap pops the cheapest
+//! entry first. Entries are never removed when a cheaper path to a node is
+//! found; instead stale entries are skipped when popped.
+//!
+//! All algorithms take their input as a [`Graph`]. Closures returning the
+//! successors of a node along with the edge costs implement it, as do
+//! `petgraph` graphs when the `petgraph` feature is enabled.
+
+use crate::{MonotonePriorityQueue, Radix, RadixHeapMap};
+use std::{cmp::Reverse, collections::HashMap, hash::Hash, ops::Add};
+
+mod alt;
+mod bicriteria;
+mod flow;
+mod graph;
+mod paths;
+
+pub use self::alt::Landmarks;
+pub use self::bicriteria::bicriteria;
+pub use self::flow::MinCostFlow;
+pub use self::graph::Graph;
+#[cfg(feature = "petgraph")]
+pub use self::graph::PetgraphEdges;
+pub use self::paths::ShortestPaths;
+
+/// Finds the cost of the cheapest path from `start` to every reachable node
+/// using Dijkstra's algorithm.
+///
+/// The successors in `graph` are the nodes reachable from a node along with
+/// the cost of the edge leading to them.
+///
+/// ```
+/// use radix_heap::algorithms::dijkstra;
+///
+/// let neighbors = |n: u32| if n < 10 { vec![(n + 1, 3)] } else { vec![] };
+///
+/// let costs = dijkstra(0, neighbors);
+/// assert_eq!(costs[&10], 30);
+/// assert_eq!(costs.len(), 11);
+/// ```
+pub fn dijkstra<N, W, G>(start: N, graph: G) -> HashMap<N, W>
+where
+    N: Copy + Eq + Hash,
+    W: Radix + Ord + Copy + Default + Add<Output = W>,
+    G: Graph<N, Weight = W>,
+{
+    search(RadixHeapMap::new(), start, graph, None)
+}
+
+/// Like [`dijkstra`], but also records the cheapest path to every reachable
+/// node.
+///
+/// ```
+/// use radix_heap::algorithms::dijkstra_paths;
+///
+/// // Going from 0 to 2 directly costs more than going through 1.
+/// let neighbors = |n: u32| match n {
+///     0 => vec![(1, 1), (2, 5)],
+///     1 => vec![(2, 1)],
+///     _ => vec![],
+/// };
+///
+/// let paths = dijkstra_paths(0, neighbors);
+/// assert_eq!(paths.cost(2), Some(2));
+/// assert_eq!(paths.path(2), Some(vec![0, 1, 2]));
+/// assert_eq!(paths.path(3), None);
+/// ```
+pub fn dijkstra_paths<N, W, G>(start: N, graph: G) -> ShortestPaths<N, W>
+where
+    N: Copy + Eq + Hash,
+    W: Radix + Ord + Copy + Default + Add<Output = W>,
+    G: Graph<N, Weight = W>,
+{
+    let mut parents = HashMap::new();
+    let costs = search(RadixHeapMap::new(), start, graph, Some(&mut parents));
+
+    ShortestPaths {
+        start,
+        costs,
+        parents,
+    }
+}
+
+/// Like [`dijkstra`], but driven by `queue`, which must be empty.
+///
+/// With a [`BucketQueue`] whose spread is at least the largest edge cost,
+/// this is Dial's algorithm.
+///
+/// ```
+/// use radix_heap::{algorithms::dijkstra_with, BucketQueue};
+///
+/// let neighbors = |n: u32| if n < 10 { vec![(n + 1, 3)] } else { vec![] };
+///
+/// let costs = dijkstra_with(BucketQueue::new(3), 0, neighbors);
+/// assert_eq!(costs[&10], 30);
+/// ```
+///
+/// [`BucketQueue`]: crate::BucketQueue
+pub fn dijkstra_with<N, W, G, Q>(queue: Q, start: N, graph: G) -> HashMap<N, W>
+where
+    N: Copy + Eq + Hash,
+    W: Ord + Copy + Default + Add<Output = W>,
+    G: Graph<N, Weight = W>,
+    Q: MonotonePriorityQueue<Reverse<W>, N>,
+{
+    search(queue, start, graph, None)
+}
+
+/// Dijkstra's algorithm, recording the node each node was reached from in
+/// `parents` if given.
+fn search<N, W, G, Q>(
+    mut heap: Q,
+    start: N,
+    mut graph: G,
+    mut parents: Option<&mut HashMap<N, N>>,
+) -> HashMap<N, W>
+where
+    N: Copy + Eq + Hash,
+    W: Ord + Copy + Default + Add<Output = W>,
+    G: Graph<N, Weight = W>,
+    Q: MonotonePriorityQueue<Reverse<W>, N>,
+{
+    let mut costs = HashMap::new();
+
+    costs.insert(start, W::default());
+    heap.push(Reverse(W::default()), start);
+
+    while let Some((Reverse(cost), node)) = heap.pop() {
+        if costs[&node] < cost {
+            continue;
+        }
+
+        for (neighbor, weight) in graph.successors(node) {
+            let neighbor_cost = cost + weight;
+
+            if !matches!(costs.get(&neighbor), Some(&c) if c <= neighbor_cost) {
+                costs.insert(neighbor, neighbor_cost);
+                heap.push(Reverse(neighbor_cost), neighbor);
+
+                if let Some(parents) = parents.as_deref_mut() {
+                    parents.insert(neighbor, node);
+                }
+            }
+        }
+    }
+
+    costs
+}
+
+/// Finds the cost of the cheapest path from any of several sources to every
+/// reachable node, along with the source the path starts at.
+///
+/// Each source is given with an initial cost, such as 0, or an offset to
+/// penalize some sources. The result maps every reachable node to its cost
+/// and the nearest source, which labels the nodes by the source that reaches
+/// them most cheaply. Ties between sources are broken arbitrarily.
+///
+/// ```
+/// use radix_heap::algorithms::multi_source_dijkstra;
+///
+/// // Facilities at both ends of a line of 11 nodes, where the one at 10 has
+/// // a setup cost of 2.
+/// let neighbors = |n: u32| vec![(n + 1, 1), (n.saturating_sub(1), 1)];
+/// let neighbors = move |n: u32| neighbors(n).into_iter().filter(|&(m, _)| m <= 10);
+///
+/// let labels = multi_source_dijkstra(vec![(0, 0), (10, 2)], neighbors);
+/// assert_eq!(labels[&3], (3, 0));
+/// assert_eq!(labels[&7], (5, 10));
+/// ```
+pub fn multi_source_dijkstra<N, W, G, I>(sources: I, mut graph: G) -> HashMap<N, (W, N)>
+where
+    N: Copy + Eq + Hash,
+    W: Radix + Ord + Copy + Add<Output = W>,
+    G: Graph<N, Weight = W>,
+    I: IntoIterator<Item = (N, W)>,
+{
+    let mut heap = RadixHeapMap::new();
+    let mut labels: HashMap<N, (W, N)> = HashMap::new();
+
+    for (source, cost) in sources {
+        if !matches!(labels.get(&source), Some(&(c, _)) if c <= cost) {
+            labels.insert(source, (cost, source));
+            heap.push(Reverse(cost), source);
+        }
+    }
+
+    while let Some((Reverse(cost), node)) = heap.pop() {
+        let (best, source) = labels[&node];
+
+        if best < cost {
+            continue;
+        }
+
+        for (neighbor, weight) in graph.successors(node) {
+            let neighbor_cost = cost + weight;
+
+            if !matches!(labels.get(&neighbor), Some(&(c, _)) if c <= neighbor_cost) {
+                labels.insert(neighbor, (neighbor_cost, source));
+                heap.push(Reverse(neighbor_cost), neighbor);
+            }
+        }
+    }
+
+    labels
+}
+
+/// Finds the cheapest path from `start` to `goal` using A*.
+///
+/// The successors in `graph` are the nodes reachable from a node along with
+/// the cost of the edge leading to them, and `heuristic` estimates the
+/// remaining cost from a node to `goal`. Returns the total cost and the path including both
+/// endpoints, or `None` if `goal` is unreachable.
+///
+/// The heuristic does not have to be consistent. An inconsistent heuristic
+/// can produce an estimate that is lower than the estimate of the node being
+/// expanded, which would normally violate the monotone property of the heap.
+/// Such pushes lower the top of the heap instead of panicking, and nodes
+/// are re-expanded whenever a cheaper path to them is found. This has two
+/// costs:
+///
+/// - Lowering the top moves the buckets near it, and redistributes one of
+///   them, so it is more expensive than a regular push.
+/// - A node can be expanded more than once. With a consistent heuristic each
+///   node is expanded at most once.
+///
+/// As long as the heuristic is admissible, i.e. never overestimates, the
+/// returned path is the cheapest one.
+///
+/// ```
+/// use radix_heap::algorithms::astar;
+///
+/// // A line of nodes where each step costs 2.
+/// let neighbors = |n: u32| vec![(n + 1, 2), (n.saturating_sub(1), 2)];
+/// let heuristic = |n: u32| 2 * (10 - n.min(10));
+///
+/// let (cost, path) = astar(0, 10, neighbors, heuristic).unwrap();
+/// assert_eq!(cost, 20);
+/// assert_eq!(path, (0..=10).collect::<Vec<_>>());
+/// ```
+pub fn astar<N, W, G, FH>(start: N, goal: N, mut graph: G, mut heuristic: FH) -> Option<(W, Vec<N>)>
+where
+    N: Copy + Eq + Hash,
+    W: Radix + Ord + Copy + Default + Add<Output = W>,
+    G: Graph<N, Weight = W>,
+    FH: FnMut(N) -> W,
+{
+    let mut heap = RadixHeapMap::new();
+    let mut costs = HashMap::new();
+    let mut parents = HashMap::new();
+
+    costs.insert(start, W::default());
+    heap.push(Reverse(heuristic(start)), (start, W::default()));
+
+    while let Some((_, (node, cost))) = heap.pop() {
+        if costs[&node] < cost {
+            continue;
+        }
+
+        if node == goal {
+            return Some((cost, path(&parents, start, goal)));
+        }
+
+        for (neighbor, weight) in graph.successors(node) {
+            let neighbor_cost = cost + weight;
+
+            if !matches!(costs.get(&neighbor), Some(&c) if c <= neighbor_cost) {
+                costs.insert(neighbor, neighbor_cost);
+                parents.insert(neighbor, node);
+
+                let key = Reverse(neighbor_cost + heuristic(neighbor));
+                heap.raise_top(key);
+                heap.push(key, (neighbor, neighbor_cost));
+            }
+        }
+    }
+
+    None
+}
+
+/// Follows `parents` back from `goal` to `start`.
+fn path<N: Copy + Eq + Hash>(parents: &HashMap<N, N>, start: N, goal: N) -> Vec<N> {
+    let mut path = vec![goal];
+    let mut node = goal;
+
+    while node != start {
+        node = parents[&node];
+        path.push(node);
+    }
+
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, dijkstra, dijkstra_paths, dijkstra_with, multi_source_dijkstra};
+    use crate::{BucketQueue, ByteRadixHeapMap};
+
+    type Pos = (i32, i32);
+
+    fn grid(n: i32) -> impl FnMut(Pos) -> Vec<(Pos, u32)> {
+        move |(x, y)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .iter()
+                .filter(|&&(x, y)| x >= 0 && y >= 0 && x < n && y < n)
+                .map(|&pos| (pos, 1))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn dijkstra_grid() {
+        let costs = dijkstra((0, 0), grid(10));
+
+        assert_eq!(costs.len(), 100);
+        assert!(costs.iter().all(|(&(x, y), &c)| c == (x + y) as u32));
+    }
+
+    #[test]
+    fn dijkstra_backends() {
+        let costs = dijkstra((0, 0), grid(10));
+
+        assert_eq!(dijkstra_with(BucketQueue::new(1), (0, 0), grid(10)), costs);
+        assert_eq!(
+            dijkstra_with(ByteRadixHeapMap::new(), (0, 0), grid(10)),
+            costs
+        );
+    }
+
+    #[test]
+    fn dijkstra_paths_grid() {
+        let paths = dijkstra_paths((0, 0), grid(10));
+        assert_eq!(paths.costs(), &dijkstra((0, 0), grid(10)));
+
+        for (&node, &cost) in paths.costs() {
+            let path = paths.path(node).unwrap();
+
+            assert_eq!(path.len(), cost as usize + 1);
+            assert_eq!(path.first(), Some(&(0, 0)));
+            assert_eq!(path.last(), Some(&node));
+            assert!(path
+                .windows(2)
+                .all(|w| (w[0].0 - w[1].0).abs() + (w[0].1 - w[1].1).abs() == 1));
+        }
+
+        assert_eq!(paths.path((10, 10)), None);
+        assert_eq!(paths.parent((0, 0)), None);
+    }
+
+    #[test]
+    fn multi_source_grid() {
+        let corners = vec![((0, 0), 0), ((9, 9), 0), ((0, 9), 4)];
+        let labels = multi_source_dijkstra(corners.clone(), grid(10));
+        let costs: Vec<_> = corners
+            .iter()
+            .map(|&(corner, offset)| (corner, offset, dijkstra(corner, grid(10))))
+            .collect();
+
+        assert_eq!(labels.len(), 100);
+
+        for (node, &(cost, source)) in &labels {
+            let nearest = costs.iter().map(|(_, offset, c)| offset + c[node]).min();
+            let (_, offset, from_source) = costs.iter().find(|(c, _, _)| *c == source).unwrap();
+
+            assert_eq!(Some(cost), nearest);
+            assert_eq!(cost, offset + from_source[node]);
+        }
+    }
+
+    #[test]
+    fn astar_manhattan() {
+        let manhattan = |(x, y): Pos| ((9 - x) + (9 - y)) as u32;
+        let (cost, path) = astar((0, 0), (9, 9), grid(10), manhattan).unwrap();
+
+        assert_eq!(cost, 18);
+        assert_eq!(path.len(), 19);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(9, 9)));
+    }
+
+    #[test]
+    fn astar_inconsistent() {
+        // Admissible, but jumps between 0 and the true distance.
+        let heuristic = |(x, y): Pos| {
+            if (x + y) % 3 == 0 {
+                0
+            } else {
+                ((9 - x) + (9 - y)) as u32
+            }
+        };
+
+        let (cost, _) = astar((0, 0), (9, 9), grid(10), heuristic).unwrap();
+        assert_eq!(cost, 18);
+    }
+
+    #[test]
+    fn astar_unreachable() {
+        assert!(astar((0, 0), (20, 20), grid(10), |_| 0u32).is_none());
+    }
+}
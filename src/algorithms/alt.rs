@@ -0,0 +1,234 @@
+use super::{dijkstra, Graph};
+use crate::Radix;
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Add, Sub},
+};
+
+/// Precomputed landmark distances for the ALT (A*, landmarks, triangle
+/// inequality) heuristic.
+///
+/// For every landmark `l`, the triangle inequality gives the lower bounds
+/// `d(l, t) - d(l, v)` and `d(v, l) - d(t, l)` on the distance from `v` to
+/// `t`. The heuristic is the largest of these bounds across all landmarks, so
+/// it is both admissible and consistent and can be used with
+/// [`astar`](super::astar). Landmarks on the far side of the graph give the
+/// best bounds; see [`Landmarks::farthest`].
+///
+/// Preprocessing runs one Dijkstra search per landmark, and the tables hold a
+/// distance for every reachable node per landmark.
+///
+/// ```
+/// use radix_heap::algorithms::{astar, Landmarks};
+///
+/// let neighbors = |n: u32| {
+///     let mut edges = vec![(n + 1, 1)];
+///     if n > 0 {
+///         edges.push((n - 1, 1));
+///     }
+///     edges.retain(|&(m, _)| m < 100);
+///     edges
+/// };
+///
+/// let landmarks = Landmarks::farthest(50, 2, neighbors);
+/// let (cost, _) = astar(10, 90, neighbors, landmarks.heuristic(90)).unwrap();
+/// assert_eq!(cost, 80);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Landmarks<N, W> {
+    /// Distances from each landmark.
+    from: Vec<HashMap<N, W>>,
+
+    /// Distances to each landmark, or `None` if the graph is undirected and
+    /// they equal the distances from each landmark.
+    to: Option<Vec<HashMap<N, W>>>,
+}
+
+impl<N, W> Landmarks<N, W>
+where
+    N: Copy + Eq + Hash,
+    W: Radix + Ord + Copy + Default + Add<Output = W> + Sub<Output = W>,
+{
+    /// Precomputes distances for the given landmarks in an undirected graph,
+    /// where the cost of an edge is the same in both directions.
+    pub fn new<L, G>(landmarks: L, mut graph: G) -> Landmarks<N, W>
+    where
+        L: IntoIterator<Item = N>,
+        G: Graph<N, Weight = W>,
+    {
+        Landmarks {
+            from: landmarks
+                .into_iter()
+                .map(|l| dijkstra(l, |n| graph.successors(n)))
+                .collect(),
+            to: None,
+        }
+    }
+
+    /// Precomputes distances for the given landmarks in a directed graph.
+    ///
+    /// The successors of a node in `reverse` must be the nodes with an edge
+    /// leading to it in `graph`, along with the cost of that edge.
+    pub fn directed<L, G, R>(landmarks: L, mut graph: G, mut reverse: R) -> Landmarks<N, W>
+    where
+        L: IntoIterator<Item = N>,
+        G: Graph<N, Weight = W>,
+        R: Graph<N, Weight = W>,
+    {
+        let (from, to) = landmarks
+            .into_iter()
+            .map(|l| {
+                (
+                    dijkstra(l, |n| graph.successors(n)),
+                    dijkstra(l, |n| reverse.successors(n)),
+                )
+            })
+            .unzip();
+
+        Landmarks { from, to: Some(to) }
+    }
+
+    /// Selects `count` landmarks in an undirected graph using farthest-point
+    /// selection, and precomputes their distances.
+    ///
+    /// The first landmark is the node farthest from `start`, and each
+    /// following landmark is the node farthest from all landmarks selected so
+    /// far. Only nodes reachable from `start` are considered.
+    pub fn farthest<G>(start: N, count: usize, mut graph: G) -> Landmarks<N, W>
+    where
+        G: Graph<N, Weight = W>,
+    {
+        let mut from: Vec<HashMap<N, W>> = Vec::with_capacity(count);
+        let mut nearest = dijkstra(start, |n| graph.successors(n));
+
+        while from.len() < count {
+            let landmark = match nearest.iter().max_by_key(|&(_, &d)| d) {
+                Some((&landmark, _)) => landmark,
+                None => break,
+            };
+
+            let distances = dijkstra(landmark, |n| graph.successors(n));
+
+            for (node, d) in nearest.iter_mut() {
+                if let Some(&l) = distances.get(node) {
+                    *d = if from.is_empty() { l } else { l.min(*d) };
+                }
+            }
+
+            from.push(distances);
+        }
+
+        Landmarks { from, to: None }
+    }
+
+    /// Returns the number of landmarks.
+    pub fn len(&self) -> usize {
+        self.from.len()
+    }
+
+    /// Returns true if there are no landmarks.
+    pub fn is_empty(&self) -> bool {
+        self.from.is_empty()
+    }
+
+    /// Returns a lower bound of the distance from `node` to `goal`.
+    pub fn estimate(&self, node: N, goal: N) -> W {
+        let to = self.to.as_ref().unwrap_or(&self.from);
+
+        self.from
+            .iter()
+            .zip(to)
+            .flat_map(|(from, to)| {
+                let forward = bound(from.get(&goal), from.get(&node));
+                let backward = bound(to.get(&node), to.get(&goal));
+                forward.into_iter().chain(backward)
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns the ALT heuristic towards `goal`, for use with
+    /// [`astar`](super::astar).
+    pub fn heuristic(&self, goal: N) -> impl Fn(N) -> W + '_ {
+        move |node| self.estimate(node, goal)
+    }
+}
+
+/// The difference `a - b` if both are known and `a > b`.
+fn bound<W: Ord + Copy + Sub<Output = W>>(a: Option<&W>, b: Option<&W>) -> Option<W> {
+    match (a, b) {
+        (Some(&a), Some(&b)) if a > b => Some(a - b),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{astar, dijkstra};
+    use super::Landmarks;
+
+    type Pos = (u32, u32);
+
+    /// A 20x20 grid with pseudo-random, direction-dependent edge weights.
+    fn directed(pos: Pos) -> Vec<(Pos, u32)> {
+        let (x, y) = pos;
+        let weight = |to: Pos| (x * 7 + y * 13 + to.0 * 3 + to.1 * 5) % 9 + 1;
+
+        [
+            (x + 1, y),
+            (x.wrapping_sub(1), y),
+            (x, y + 1),
+            (x, y.wrapping_sub(1)),
+        ]
+        .iter()
+        .filter(|&&(x, y)| x < 20 && y < 20)
+        .map(|&to| (to, weight(to)))
+        .collect()
+    }
+
+    fn reverse(pos: Pos) -> Vec<(Pos, u32)> {
+        directed(pos)
+            .into_iter()
+            .map(|(from, _)| {
+                let weight = directed(from).into_iter().find(|&(to, _)| to == pos);
+                (from, weight.unwrap().1)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn directed_matches_dijkstra() {
+        let landmarks = Landmarks::directed(vec![(0, 0), (19, 19), (0, 19)], directed, reverse);
+        let goal = (17, 3);
+        let costs = dijkstra((2, 15), directed);
+        let (cost, _) = astar((2, 15), goal, directed, landmarks.heuristic(goal)).unwrap();
+
+        assert_eq!(cost, costs[&goal]);
+
+        for (&node, &c) in &dijkstra(goal, reverse) {
+            assert!(landmarks.estimate(node, goal) <= c);
+        }
+    }
+
+    #[test]
+    fn farthest_corners() {
+        let grid = |(x, y): Pos| {
+            [
+                (x + 1, y),
+                (x.wrapping_sub(1), y),
+                (x, y + 1),
+                (x, y.wrapping_sub(1)),
+            ]
+            .iter()
+            .filter(|&&(x, y)| x < 20 && y < 20)
+            .map(|&to| (to, 1u32))
+            .collect::<Vec<_>>()
+        };
+
+        let landmarks = Landmarks::farthest((5, 5), 2, grid);
+        assert_eq!(landmarks.len(), 2);
+        assert_eq!(landmarks.estimate((0, 0), (19, 19)), 38);
+        assert_eq!(landmarks.estimate((3, 3), (3, 3)), 0);
+    }
+}
@@ -0,0 +1,150 @@
+use super::Graph;
+use crate::{Radix, RadixHeapMap};
+use std::{cmp::Reverse, collections::HashMap, hash::Hash, ops::Add};
+
+/// A label of the search: a path to `node` with the given costs, reached from
+/// the label at index `parent`.
+struct Label<N, A, B> {
+    node: N,
+    cost: (A, B),
+    parent: Option<usize>,
+}
+
+/// Finds all Pareto-optimal paths from `start` to `goal` under two costs.
+///
+/// The successors in `graph` are the nodes reachable from a node along with
+/// the pair of costs of the edge leading to them. A path dominates another if
+/// it is no worse in both costs. Returns the costs and path of every path to
+/// `goal` not dominated by any other, ordered by increasing first cost (and
+/// thus decreasing second cost). The first entry is the lexicographically
+/// cheapest path, e.g. the fastest path and among those the cheapest.
+///
+/// This is a label-setting search: labels are popped in lexicographic order
+/// of their `(A, B)` costs using `Reverse<(A, B)>` keys. Because of this
+/// order, a label is dominated exactly when its second cost is not lower than
+/// that of the last label settled at the same node, so dominance pruning is
+/// a single comparison. Labels that are dominated by a path already found to
+/// `goal` are pruned as well.
+///
+/// ```
+/// use radix_heap::algorithms::bicriteria;
+///
+/// // Going 0 -> 1 -> 3 is fast but expensive, 0 -> 2 -> 3 is slow but cheap.
+/// let neighbors = |n: u32| match n {
+///     0 => vec![(1, (1u32, 10u32)), (2, (5, 1))],
+///     1 | 2 => vec![(3, (1, 1))],
+///     _ => vec![],
+/// };
+///
+/// let paths = bicriteria(0, 3, neighbors);
+/// assert_eq!(paths, vec![((2, 11), vec![0, 1, 3]), ((6, 2), vec![0, 2, 3])]);
+/// ```
+pub fn bicriteria<N, A, B, G>(start: N, goal: N, mut graph: G) -> Vec<((A, B), Vec<N>)>
+where
+    N: Copy + Eq + Hash,
+    A: Radix + Ord + Copy + Default + Add<Output = A>,
+    B: Radix + Ord + Copy + Default + Add<Output = B>,
+    G: Graph<N, Weight = (A, B)>,
+{
+    let mut heap = RadixHeapMap::new();
+    let mut labels = vec![Label {
+        node: start,
+        cost: (A::default(), B::default()),
+        parent: None,
+    }];
+
+    // The second cost of the last label settled at each node.
+    let mut settled: HashMap<N, B> = HashMap::new();
+    let mut found = Vec::new();
+
+    heap.push(Reverse(labels[0].cost), 0);
+
+    while let Some((Reverse((a, b)), index)) = heap.pop() {
+        let node = labels[index].node;
+
+        if dominated(&settled, node, b) || dominated(&settled, goal, b) {
+            continue;
+        }
+
+        settled.insert(node, b);
+
+        if node == goal {
+            found.push(index);
+            continue;
+        }
+
+        for (neighbor, (wa, wb)) in graph.successors(node) {
+            let cost = (a + wa, b + wb);
+
+            if !dominated(&settled, neighbor, cost.1) && !dominated(&settled, goal, cost.1) {
+                labels.push(Label {
+                    node: neighbor,
+                    cost,
+                    parent: Some(index),
+                });
+                heap.push(Reverse(cost), labels.len() - 1);
+            }
+        }
+    }
+
+    found
+        .into_iter()
+        .map(|index| {
+            let mut path = vec![];
+            let mut label = Some(index);
+
+            while let Some(index) = label {
+                path.push(labels[index].node);
+                label = labels[index].parent;
+            }
+
+            path.reverse();
+            (labels[index].cost, path)
+        })
+        .collect()
+}
+
+/// Whether a label at `node` with second cost `b` is dominated by a settled
+/// label, given that its first cost is at least that of every settled label.
+fn dominated<N: Eq + Hash, B: Ord>(settled: &HashMap<N, B>, node: N, b: B) -> bool {
+    matches!(settled.get(&node), Some(settled) if *settled <= b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bicriteria;
+
+    #[test]
+    fn pareto_front() {
+        // A chain of 6 nodes where each step offers a fast or a cheap edge.
+        let neighbors = |n: u32| {
+            if n < 5 {
+                vec![(n + 1, (1u32, 3u32)), (n + 1, (2, 1))]
+            } else {
+                vec![]
+            }
+        };
+
+        let paths = bicriteria(0, 5, neighbors);
+        let costs: Vec<_> = paths.iter().map(|&(cost, _)| cost).collect();
+
+        assert_eq!(
+            costs,
+            vec![(5, 15), (6, 13), (7, 11), (8, 9), (9, 7), (10, 5)]
+        );
+        assert!(paths.iter().all(|(_, path)| path == &[0, 1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn dominated_paths() {
+        // 0 -> 2 is dominated by 0 -> 1 -> 2 in both costs.
+        let neighbors = |n: u32| match n {
+            0 => vec![(1, (1u8, 1u8)), (2, (5, 5))],
+            1 => vec![(2, (1, 1))],
+            _ => vec![],
+        };
+
+        assert_eq!(bicriteria(0, 2, neighbors), vec![((2, 2), vec![0, 1, 2])]);
+        assert!(bicriteria(2, 0, neighbors).is_empty());
+    }
+}
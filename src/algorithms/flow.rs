@@ -0,0 +1,313 @@
+use super::search;
+use crate::{Radix, RadixHeapMap};
+use std::{
+    collections::HashMap,
+    ops::{Add, Mul, Sub},
+};
+
+/// An edge of the residual graph. Edges are stored in pairs, so the reverse of
+/// edge `i` is edge `i ^ 1`.
+#[derive(Clone, Debug)]
+struct Edge<W> {
+    to: usize,
+    residual: W,
+    cost: W,
+    reverse: bool,
+}
+
+/// A flow network for finding a minimum-cost maximum flow.
+///
+/// Flow is found using successive shortest paths: the cheapest path from the
+/// source to the sink in the residual graph is found using
+/// [`dijkstra`](super::dijkstra) and saturated, until no path remains.
+/// Reverse edges in the residual graph have negative costs, which would break
+/// the monotone property of the heap, so costs are reduced using Johnson-style
+/// node potentials. The potential of a node is its distance from the source in
+/// the previous search, which keeps all reduced costs non-negative.
+///
+/// Nodes are identified by indices from 0 up to the number of nodes, and the
+/// costs of all edges must be non-negative.
+///
+/// ```
+/// use radix_heap::algorithms::MinCostFlow;
+///
+/// let mut network = MinCostFlow::new(4);
+/// let direct = network.add_edge(0, 3, 2u32, 10);
+/// network.add_edge(0, 1, 3, 1);
+/// network.add_edge(1, 2, 3, 1);
+/// network.add_edge(2, 3, 1, 1);
+///
+/// assert_eq!(network.solve(0, 3), (3, 23));
+/// assert_eq!(network.flow(direct), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MinCostFlow<W> {
+    nodes: Vec<Vec<usize>>,
+    edges: Vec<Edge<W>>,
+}
+
+impl<W> MinCostFlow<W>
+where
+    W: Radix + Ord + Copy + Default + Add<Output = W> + Sub<Output = W> + Mul<Output = W>,
+{
+    /// Create a network with `nodes` nodes and no edges.
+    pub fn new(nodes: usize) -> MinCostFlow<W> {
+        MinCostFlow {
+            nodes: vec![Vec::new(); nodes],
+            edges: Vec::new(),
+        }
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if there are no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Adds an edge and returns its index, for use with
+    /// [`flow`](MinCostFlow::flow).
+    ///
+    /// Panics
+    /// ------
+    /// Panics if either node is out of bounds, or if `cost` is negative.
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: W, cost: W) -> usize {
+        assert!(from < self.len() && to < self.len(), "Node out of bounds");
+        assert!(cost >= W::default(), "Cost must be non-negative");
+
+        let index = self.edges.len();
+        self.nodes[from].push(index);
+        self.nodes[to].push(index + 1);
+
+        self.edges.push(Edge {
+            to,
+            residual: capacity,
+            cost,
+            reverse: false,
+        });
+        self.edges.push(Edge {
+            to: from,
+            residual: W::default(),
+            cost,
+            reverse: true,
+        });
+
+        index
+    }
+
+    /// The flow through the edge with the given index.
+    pub fn flow(&self, edge: usize) -> W {
+        assert!(!self.edges[edge].reverse, "Not an edge index");
+        self.edges[edge ^ 1].residual
+    }
+
+    /// Pushes as much flow as possible from `source` to `sink` at the lowest
+    /// possible cost, and returns the total flow and its cost.
+    pub fn solve(&mut self, source: usize, sink: usize) -> (W, W) {
+        self.solve_limited(source, sink, None)
+    }
+
+    /// Like [`solve`](MinCostFlow::solve), but stops once `limit` flow has
+    /// been pushed if given.
+    pub fn solve_limited(&mut self, source: usize, sink: usize, limit: Option<W>) -> (W, W) {
+        let zero = W::default();
+        let mut potentials = vec![zero; self.len()];
+        let mut flow = zero;
+        let mut cost = zero;
+
+        while !matches!(limit, Some(limit) if flow >= limit) {
+            let mut parents = HashMap::new();
+            let distances = {
+                let (nodes, edges, potentials) = (&self.nodes, &self.edges, &potentials);
+                let successors = |node: usize| {
+                    nodes[node].iter().filter_map(move |&index| {
+                        let edge = &edges[index];
+
+                        if edge.residual == zero {
+                            return None;
+                        }
+
+                        // The reduced cost c + p(from) - p(to) of the edge,
+                        // where c is negated for reverse edges.
+                        let reduced = if edge.reverse {
+                            potentials[node] - (edge.cost + potentials[edge.to])
+                        } else {
+                            (edge.cost + potentials[node]) - potentials[edge.to]
+                        };
+
+                        Some((edge.to, reduced))
+                    })
+                };
+
+                search(RadixHeapMap::new(), source, successors, Some(&mut parents))
+            };
+
+            if !distances.contains_key(&sink) {
+                break;
+            }
+
+            for (&node, &distance) in &distances {
+                potentials[node] = potentials[node] + distance;
+            }
+
+            let path = self.path(&parents, &potentials, source, sink);
+
+            let mut amount = path
+                .iter()
+                .map(|&index| self.edges[index].residual)
+                .min()
+                .expect("Expected non-empty path");
+
+            if let Some(limit) = limit {
+                amount = amount.min(limit - flow);
+            }
+
+            for index in path {
+                self.edges[index].residual = self.edges[index].residual - amount;
+                self.edges[index ^ 1].residual = self.edges[index ^ 1].residual + amount;
+            }
+
+            flow = flow + amount;
+            cost = cost + amount * (potentials[sink] - potentials[source]);
+        }
+
+        (flow, cost)
+    }
+
+    /// The edges of the shortest path to `sink`, given the node each node was
+    /// reached from and the updated potentials.
+    fn path(
+        &self,
+        parents: &HashMap<usize, usize>,
+        potentials: &[W],
+        source: usize,
+        sink: usize,
+    ) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut node = sink;
+
+        while node != source {
+            let parent = parents[&node];
+
+            // With the updated potentials, the edge used has a reduced cost of
+            // zero. There may be parallel edges, so find it again.
+            let index = self.nodes[parent]
+                .iter()
+                .copied()
+                .find(|&index| {
+                    let edge = &self.edges[index];
+                    let tight = if edge.reverse {
+                        potentials[node] + edge.cost == potentials[parent]
+                    } else {
+                        edge.cost + potentials[parent] == potentials[node]
+                    };
+
+                    edge.to == node && edge.residual != W::default() && tight
+                })
+                .expect("Expected tight edge along shortest path");
+
+            path.push(index);
+            node = parent;
+        }
+
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::{quickcheck, TestResult};
+    use super::MinCostFlow;
+
+    #[test]
+    fn parallel_edges() {
+        let mut network = MinCostFlow::new(2);
+        let expensive = network.add_edge(0, 1, 5u64, 7);
+        let cheap = network.add_edge(0, 1, 3, 2);
+
+        assert_eq!(network.solve_limited(0, 1, Some(4)), (4, 13));
+        assert_eq!(network.flow(cheap), 3);
+        assert_eq!(network.flow(expensive), 1);
+    }
+
+    #[test]
+    fn optimal() {
+        fn prop(edges: Vec<(u8, u8, u8, u8)>) -> TestResult {
+            let n = 6;
+            let mut network = MinCostFlow::new(n);
+
+            for &(from, to, capacity, cost) in &edges {
+                let (from, to) = (from as usize % n, to as usize % n);
+                if from != to {
+                    network.add_edge(from, to, u32::from(capacity % 8), u32::from(cost % 16));
+                }
+            }
+
+            let (flow, cost) = network.solve(0, n - 1);
+
+            // The flow is conserved and matches the reported cost.
+            let mut balance = vec![0i64; n];
+            let mut total = 0;
+            for (index, edge) in network.edges.iter().enumerate().step_by(2) {
+                let amount = i64::from(network.flow(index));
+                balance[network.edges[index ^ 1].to] -= amount;
+                balance[edge.to] += amount;
+                total += amount * i64::from(edge.cost);
+            }
+
+            if balance[n - 1] != i64::from(flow) || total != i64::from(cost) {
+                return TestResult::failed();
+            }
+
+            if balance[1..n - 1].iter().any(|&b| b != 0) {
+                return TestResult::failed();
+            }
+
+            // The flow is maximal: the sink is unreachable in the residual
+            // graph.
+            let mut reachable = vec![false; n];
+            let mut stack = vec![0];
+            reachable[0] = true;
+            while let Some(node) = stack.pop() {
+                for &index in &network.nodes[node] {
+                    let edge = &network.edges[index];
+                    if edge.residual > 0 && !reachable[edge.to] {
+                        reachable[edge.to] = true;
+                        stack.push(edge.to);
+                    }
+                }
+            }
+
+            if reachable[n - 1] {
+                return TestResult::failed();
+            }
+
+            // The cost is minimal: there is no negative cycle in the residual
+            // graph.
+            let mut distances = vec![0i64; n];
+            for _ in 0..=n {
+                for (index, edge) in network.edges.iter().enumerate() {
+                    let from = network.edges[index ^ 1].to;
+                    let cost = if edge.reverse { -1 } else { 1 } * i64::from(edge.cost);
+
+                    if edge.residual > 0 && distances[from] + cost < distances[edge.to] {
+                        distances[edge.to] = distances[from] + cost;
+                    }
+                }
+            }
+
+            TestResult::from_bool(network.edges.iter().enumerate().all(|(index, edge)| {
+                let from = network.edges[index ^ 1].to;
+                let cost = if edge.reverse { -1 } else { 1 } * i64::from(edge.cost);
+                edge.residual == 0 || distances[from] + cost >= distances[edge.to]
+            }))
+        }
+
+        quickcheck(prop as fn(Vec<(u8, u8, u8, u8)>) -> TestResult);
+    }
+}
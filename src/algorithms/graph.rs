@@ -0,0 +1,113 @@
+/// A graph with nodes identified by `N`, given by the successors of each node.
+///
+/// This is implemented for closures taking a node and returning an iterable
+/// of `(successor, weight)` pairs, so any adjacency structure can be used
+/// without an explicit implementation:
+///
+/// ```
+/// use radix_heap::algorithms::dijkstra;
+///
+/// let edges = vec![vec![(1, 4), (2, 1)], vec![], vec![(1, 2)]];
+/// let costs = dijkstra(0, |n: usize| edges[n].iter().copied());
+/// assert_eq!(costs[&1], 3);
+/// ```
+///
+/// With the `petgraph` feature, it is also implemented for references to
+/// `petgraph::Graph` with `Copy` edge weights.
+pub trait Graph<N> {
+    /// The cost of an edge.
+    type Weight;
+
+    /// The successors of a node along with the cost of the edge leading to
+    /// them.
+    type Edges: IntoIterator<Item = (N, Self::Weight)>;
+
+    /// Returns the successors of `node`.
+    fn successors(&mut self, node: N) -> Self::Edges;
+}
+
+impl<N, W, I, F> Graph<N> for F
+where
+    F: FnMut(N) -> I,
+    I: IntoIterator<Item = (N, W)>,
+{
+    type Weight = W;
+    type Edges = I;
+
+    #[inline]
+    fn successors(&mut self, node: N) -> I {
+        self(node)
+    }
+}
+
+#[cfg(feature = "petgraph")]
+mod petgraph_impl {
+    use super::Graph;
+    use petgraph::{
+        graph::{self, EdgeReference, IndexType, NodeIndex},
+        visit::EdgeRef,
+        EdgeType,
+    };
+
+    /// The successors of a node in a `petgraph::Graph`.
+    pub struct PetgraphEdges<'a, E, Ty: EdgeType, Ix: IndexType>(graph::Edges<'a, E, Ty, Ix>);
+
+    impl<'a, E: Copy, Ty: EdgeType, Ix: IndexType> Iterator for PetgraphEdges<'a, E, Ty, Ix> {
+        type Item = (NodeIndex<Ix>, E);
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0
+                .next()
+                .map(|edge: EdgeReference<'a, E, Ix>| (edge.target(), *edge.weight()))
+        }
+    }
+
+    impl<'a, N, E, Ty, Ix> Graph<NodeIndex<Ix>> for &'a graph::Graph<N, E, Ty, Ix>
+    where
+        E: Copy,
+        Ty: EdgeType,
+        Ix: IndexType,
+    {
+        type Weight = E;
+        type Edges = PetgraphEdges<'a, E, Ty, Ix>;
+
+        #[inline]
+        fn successors(&mut self, node: NodeIndex<Ix>) -> Self::Edges {
+            PetgraphEdges(self.edges(node))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::algorithms::{astar, dijkstra};
+        use petgraph::{Directed, Undirected};
+
+        #[test]
+        fn petgraph_dijkstra() {
+            let mut graph = petgraph::Graph::<(), u32, Directed>::new();
+            let a = graph.add_node(());
+            let b = graph.add_node(());
+            let c = graph.add_node(());
+            graph.extend_with_edges([(a, b, 5), (a, c, 1), (c, b, 2)]);
+
+            let costs = dijkstra(a, &graph);
+            assert_eq!(costs[&b], 3);
+            assert_eq!(astar(a, b, &graph, |_| 0), Some((3, vec![a, c, b])));
+            assert!(!dijkstra(b, &graph).contains_key(&a));
+        }
+
+        #[test]
+        fn petgraph_undirected() {
+            let mut graph = petgraph::Graph::<(), u8, Undirected>::new_undirected();
+            let a = graph.add_node(());
+            let b = graph.add_node(());
+            graph.add_edge(a, b, 7);
+
+            assert_eq!(dijkstra(b, &graph)[&a], 7);
+        }
+    }
+}
+
+#[cfg(feature = "petgraph")]
+pub use self::petgraph_impl::PetgraphEdges;
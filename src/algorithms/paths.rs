@@ -0,0 +1,57 @@
+use super::path;
+use std::{collections::HashMap, hash::Hash};
+
+/// The cheapest paths from a start node to every reachable node, as found by
+/// [`dijkstra_paths`](super::dijkstra_paths).
+///
+/// Each node stores only the node it was reached from, so a path is
+/// followed back from its end when asked for.
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<N, W> {
+    pub(super) start: N,
+    pub(super) costs: HashMap<N, W>,
+    pub(super) parents: HashMap<N, N>,
+}
+
+impl<N: Copy + Eq + Hash, W: Copy> ShortestPaths<N, W> {
+    /// The node the paths start at.
+    #[inline]
+    pub fn start(&self) -> N {
+        self.start
+    }
+
+    /// The cost of the cheapest path to `node`, or `None` if it is
+    /// unreachable.
+    #[inline]
+    pub fn cost(&self, node: N) -> Option<W> {
+        self.costs.get(&node).copied()
+    }
+
+    /// The cheapest path to `node`, including both endpoints, or `None` if it
+    /// is unreachable.
+    pub fn path(&self, node: N) -> Option<Vec<N>> {
+        if !self.costs.contains_key(&node) {
+            return None;
+        }
+
+        Some(path(&self.parents, self.start, node))
+    }
+
+    /// The node preceding `node` on its cheapest path, or `None` if it is the
+    /// start node or unreachable.
+    #[inline]
+    pub fn parent(&self, node: N) -> Option<N> {
+        self.parents.get(&node).copied()
+    }
+
+    /// The costs of the cheapest paths to every reachable node.
+    #[inline]
+    pub fn costs(&self) -> &HashMap<N, W> {
+        &self.costs
+    }
+
+    /// Returns the costs of the cheapest paths to every reachable node.
+    pub fn into_costs(self) -> HashMap<N, W> {
+        self.costs
+    }
+}
@@ -0,0 +1,160 @@
+//! Generating heaps for property tests.
+//!
+//! A heap is generated from a list of pushes, each followed by a pop with a
+//! probability of one in four, so generated heaps are in intermediate states
+//! with a top key set and partially emptied buckets. A push with a key larger
+//! than the top key at that point is skipped, so the monotone invariant
+//! always holds.
+
+use crate::{Radix, RadixHeapMap};
+
+/// Builds a heap by pushing each pair, and popping after those marked.
+fn from_ops<K, V, I>(ops: I) -> RadixHeapMap<K, V>
+where
+    K: Radix + Ord + Clone,
+    I: IntoIterator<Item = (K, V, bool)>,
+{
+    let mut heap = RadixHeapMap::new();
+
+    for (key, value, pop) in ops {
+        if heap.try_push(key, value).is_ok() && pop {
+            heap.pop();
+        }
+    }
+
+    heap
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impl {
+    use super::from_ops;
+    use crate::{Radix, RadixHeapMap};
+    use alloc::{boxed::Box, vec::Vec};
+    use quickcheck::{Arbitrary, Gen};
+
+    /// Shrinks by shrinking the pairs in the heap, which are pushed again
+    /// below the same top key. A heap with a top key first shrinks to the
+    /// same pairs without a top key.
+    impl<K, V> Arbitrary for RadixHeapMap<K, V>
+    where
+        K: Arbitrary + Radix + Ord,
+        V: Arbitrary,
+    {
+        fn arbitrary(g: &mut Gen) -> RadixHeapMap<K, V> {
+            let pairs: Vec<(K, V)> = Arbitrary::arbitrary(g);
+
+            from_ops(pairs.into_iter().map(|(key, value)| {
+                let pop = *g.choose(&[false, false, false, true]).unwrap();
+                (key, value, pop)
+            }))
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = RadixHeapMap<K, V>>> {
+            let top = self.top.clone();
+            let pairs: Vec<(K, V)> = self.iter().cloned().collect();
+
+            let untopped = top.as_ref().map(|_| pairs.iter().cloned().collect());
+
+            Box::new(untopped.into_iter().chain(pairs.shrink().map(move |pairs| {
+                let mut heap = match &top {
+                    Some(top) => RadixHeapMap::new_at(top.clone()),
+                    None => RadixHeapMap::new(),
+                };
+
+                for (key, value) in pairs {
+                    // Shrinking can make a key larger, such as a negative
+                    // one moving toward zero.
+                    let _ = heap.try_push(key, value);
+                }
+
+                heap
+            })))
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+mod proptest_impl {
+    use super::from_ops;
+    use crate::{Radix, RadixHeapMap};
+    use alloc::vec::Vec;
+    use core::fmt::Debug;
+    use proptest::{
+        arbitrary::{any, Arbitrary, StrategyFor},
+        bool::{weighted, Weighted},
+        collection::{vec, VecStrategy},
+        strategy::{Map, Strategy},
+    };
+
+    type Ops<K, V> = VecStrategy<(StrategyFor<K>, StrategyFor<V>, Weighted)>;
+
+    /// Generates up to 64 pushes. Shrinking removes and shrinks pushes and
+    /// pops.
+    impl<K, V> Arbitrary for RadixHeapMap<K, V>
+    where
+        K: Arbitrary + Radix + Ord + Clone + Debug,
+        V: Arbitrary + Debug,
+    {
+        type Parameters = ();
+        type Strategy = Map<Ops<K, V>, fn(Vec<(K, V, bool)>) -> RadixHeapMap<K, V>>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            vec((any::<K>(), any::<V>(), weighted(0.25)), 0..64).prop_map(from_ops)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Radix, RadixHeapMap};
+
+    /// Whether the heap pops its length in keys, in decreasing order and no
+    /// larger than its top key.
+    fn monotone<K: Radix + Ord + Clone, V>(mut heap: RadixHeapMap<K, V>) -> bool {
+        let len = heap.len();
+        let mut top = heap.top();
+        let mut popped = 0;
+
+        while let Some((key, _)) = heap.pop() {
+            if matches!(&top, Some(top) if key > *top) {
+                return false;
+            }
+
+            top = Some(key);
+            popped += 1;
+        }
+
+        popped == len
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn quickcheck_heaps() {
+        use quickcheck::{quickcheck, Arbitrary};
+
+        fn prop(heap: RadixHeapMap<i16, u8>) -> bool {
+            heap.shrink().take(20).all(monotone) && monotone(heap)
+        }
+
+        quickcheck(prop as fn(RadixHeapMap<i16, u8>) -> bool);
+    }
+
+    #[cfg(feature = "proptest")]
+    #[test]
+    fn proptest_heaps() {
+        use proptest::{arbitrary::any, test_runner::TestRunner};
+        use std::cell::Cell;
+
+        let popped = Cell::new(false);
+
+        TestRunner::default()
+            .run(&any::<RadixHeapMap<i16, u8>>(), |heap| {
+                popped.set(popped.get() || heap.top().is_some());
+                assert!(monotone(heap));
+                Ok(())
+            })
+            .unwrap();
+
+        assert!(popped.get());
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{Iter, Radix, RadixHeapMap};
+use core::fmt;
+
+/// A heap keeping at most a fixed number of the greatest keys pushed, such as
+/// the `k` best candidates of a beam search.
+///
+/// Pushing onto a full heap evicts the smallest key, which is in the non-empty
+/// bucket furthest from the top key, so only that bucket is searched. Unlike
+/// [`RadixHeapMap`], keys can be pushed in any order, since the top key is
+/// raised when a larger key is pushed.
+///
+/// Finding and removing the smallest key takes time linear in the size of
+/// that bucket, so every push onto a full heap costs `O(bucket size)`. This
+/// is small when the keys are spread over many buckets, but approaches
+/// `O(limit)` when most of them are equally far from the top key.
+///
+/// ```
+/// let mut best = radix_heap::BoundedRadixHeapMap::new(2);
+/// assert_eq!(best.push(5u32, 'a'), None);
+/// assert_eq!(best.push(9, 'b'), None);
+/// assert_eq!(best.push(7, 'c'), Some((5, 'a')));
+/// assert_eq!(best.push(1, 'd'), Some((1, 'd')));
+///
+/// assert_eq!(best.pop(), Some((9, 'b')));
+/// assert_eq!(best.pop(), Some((7, 'c')));
+/// ```
+#[derive(Clone)]
+pub struct BoundedRadixHeapMap<K, V> {
+    heap: RadixHeapMap<K, V>,
+    limit: usize,
+}
+
+impl<K: Radix + Ord + Copy, V> BoundedRadixHeapMap<K, V> {
+    /// Create an empty `BoundedRadixHeapMap` holding at most `limit` items.
+    pub fn new(limit: usize) -> BoundedRadixHeapMap<K, V> {
+        BoundedRadixHeapMap {
+            heap: RadixHeapMap::new(),
+            limit,
+        }
+    }
+
+    /// Pushes a new key value pair onto the heap. If the heap was full,
+    /// removes and returns the pair with the smallest key, which may be the
+    /// pushed one.
+    ///
+    /// Of several pairs with the smallest key, the one that would be popped
+    /// last is evicted, and a pushed pair is evicted before the pairs already
+    /// in the heap.
+    pub fn push(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if self.heap.len() < self.limit {
+            self.push_unbounded(key, value);
+            return None;
+        }
+
+        if self.heap.top.is_none() {
+            self.heap.constrain();
+        }
+
+        let (distance, index) = match self.smallest() {
+            Some(smallest) => smallest,
+            None => return Some((key, value)),
+        };
+
+        let bucket = &mut self.heap.buckets[distance];
+        if key <= bucket[index].0 {
+            return Some((key, value));
+        }
+
+        let evicted = bucket.remove(index);
+        self.heap.len -= 1;
+        self.push_unbounded(key, value);
+        Some(evicted)
+    }
+
+    /// Removes the greatest element from the heap and returns it, or `None`
+    /// if empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.heap.pop()
+    }
+
+    /// Returns the greatest key without removing it, or `None` if empty.
+    #[inline]
+    pub fn peek_key(&mut self) -> Option<K> {
+        self.heap.peek_key()
+    }
+
+    /// Returns the smallest key, which is the next to be evicted, or `None`
+    /// if empty.
+    pub fn min_key(&mut self) -> Option<K> {
+        if self.heap.top.is_none() {
+            self.heap.constrain();
+        }
+
+        self.smallest()
+            .map(|(distance, index)| self.heap.buckets[distance][index].0)
+    }
+
+    /// The largest number of items the heap holds.
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the number of elements in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if there are no elements in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns true if the heap holds `limit` items, so that the next push
+    /// evicts one.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.heap.len() >= self.limit
+    }
+
+    /// Drops all items from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// Returns an iterator of all key-value pairs in the heap in arbitrary
+    /// order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.heap.iter()
+    }
+
+    /// Returns the underlying heap.
+    pub fn into_map(self) -> RadixHeapMap<K, V> {
+        self.heap
+    }
+
+    fn push_unbounded(&mut self, key: K, value: V) {
+        self.heap.raise_top(key);
+        self.heap.push_unchecked(key, value);
+    }
+
+    /// The bucket and index of the smallest key, in the non-empty bucket
+    /// furthest from the top key. Of several smallest keys, this is the first,
+    /// which would be popped last.
+    fn smallest(&self) -> Option<(usize, usize)> {
+        let distance = self
+            .heap
+            .buckets
+            .iter()
+            .rposition(|bucket| !bucket.is_empty())?;
+
+        let bucket = &self.heap.buckets[distance];
+        let mut index = 0;
+        for (i, &(key, _)) in bucket.iter().enumerate().skip(1) {
+            if key < bucket[index].0 {
+                index = i;
+            }
+        }
+
+        Some((distance, index))
+    }
+}
+
+impl<K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug> fmt::Debug for BoundedRadixHeapMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundedRadixHeapMap")
+            .field("limit", &self.limit)
+            .field("heap", &self.heap)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::BoundedRadixHeapMap;
+
+    #[test]
+    fn top_k() {
+        fn prop(xs: Vec<i16>, limit: u8) -> bool {
+            let limit = limit as usize % 8;
+            let mut heap = BoundedRadixHeapMap::new(limit);
+            let mut evicted = Vec::new();
+
+            for (i, &x) in xs.iter().enumerate() {
+                if heap.len() > limit {
+                    return false;
+                }
+                evicted.extend(heap.push(x, i));
+            }
+
+            let mut expected = xs.clone();
+            expected.sort_unstable();
+            let kept = expected.split_off(xs.len().saturating_sub(limit));
+
+            let mut evicted: Vec<_> = evicted.into_iter().map(|(k, _)| k).collect();
+            evicted.sort_unstable();
+
+            heap.min_key() == kept.first().copied()
+                && std::iter::from_fn(|| heap.pop())
+                    .map(|(k, _)| k)
+                    .eq(kept.into_iter().rev())
+                && evicted == expected
+        }
+
+        quickcheck(prop as fn(Vec<i16>, u8) -> bool);
+    }
+
+    #[test]
+    fn push_after_pop() {
+        let mut heap = BoundedRadixHeapMap::new(2);
+        heap.push(3u8, 'a');
+        heap.push(8, 'b');
+        assert_eq!(heap.pop(), Some((8, 'b')));
+
+        assert_eq!(heap.push(10, 'c'), None);
+        assert_eq!(heap.push(5, 'd'), Some((3, 'a')));
+        assert!(heap.is_full());
+        assert_eq!(heap.pop(), Some((10, 'c')));
+        assert_eq!(heap.pop(), Some((5, 'd')));
+    }
+}
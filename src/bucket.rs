@@ -0,0 +1,105 @@
+//! The storage of the entries of each bucket.
+//!
+//! Buckets are `Vec`s, or with the `smallvec` feature, `SmallVec`s storing
+//! the first few entries inline. Since most buckets only ever hold a few
+//! entries, this avoids allocating for them, at the cost of larger buckets.
+
+use alloc::{collections::TryReserveError, vec::Vec};
+
+/// The number of entries stored inline in each bucket, before the bucket
+/// allocates.
+#[cfg(all(test, not(feature = "smallvec")))]
+pub(crate) const INLINE_ENTRIES: usize = 0;
+#[cfg(feature = "smallvec")]
+pub(crate) const INLINE_ENTRIES: usize = 4;
+
+#[cfg(not(feature = "smallvec"))]
+pub(crate) type Bucket<K, V> = Vec<(K, V)>;
+
+#[cfg(feature = "smallvec")]
+pub(crate) type Bucket<K, V> = smallvec::SmallVec<[(K, V); INLINE_ENTRIES]>;
+
+/// An owning iterator over the entries of a bucket.
+pub(crate) type BucketIntoIter<K, V> = <Bucket<K, V> as IntoIterator>::IntoIter;
+
+/// Tries to reserve capacity for at least `additional` more entries.
+#[inline]
+pub(crate) fn try_reserve<K, V>(
+    bucket: &mut Bucket<K, V>,
+    additional: usize,
+) -> Result<(), TryReserveError> {
+    #[cfg(not(feature = "smallvec"))]
+    return bucket.try_reserve(additional);
+
+    // The error of `SmallVec::try_reserve` cannot be converted, so allocate
+    // with a `Vec` and move the entries into it.
+    #[cfg(feature = "smallvec")]
+    {
+        if bucket.capacity() - bucket.len() >= additional {
+            return Ok(());
+        }
+
+        let mut vec = Vec::new();
+        vec.try_reserve(bucket.len().saturating_add(additional))?;
+        vec.extend(bucket.drain(..));
+        *bucket = Bucket::from_vec(vec);
+        Ok(())
+    }
+}
+
+/// Moves all entries of the bucket to the end of `vec`.
+#[inline]
+pub(crate) fn append<K, V>(vec: &mut Vec<(K, V)>, bucket: &mut Bucket<K, V>) {
+    #[cfg(not(feature = "smallvec"))]
+    vec.append(bucket);
+
+    #[cfg(feature = "smallvec")]
+    vec.extend(bucket.drain(..));
+}
+
+/// Moves the entries of the bucket into a new allocation of exactly the
+/// right size, or inline if they fit.
+pub(crate) fn compact<K, V>(bucket: &mut Bucket<K, V>) {
+    #[cfg(not(feature = "smallvec"))]
+    if bucket.capacity() != bucket.len() {
+        let mut compacted = Vec::with_capacity(bucket.len());
+        compacted.append(bucket);
+        *bucket = compacted;
+    }
+
+    #[cfg(feature = "smallvec")]
+    if bucket.capacity() != bucket.len().max(INLINE_ENTRIES) {
+        let mut compacted = Bucket::with_capacity(bucket.len());
+        compacted.extend(bucket.drain(..));
+        *bucket = compacted;
+    }
+}
+
+/// Shrinks the capacity of the bucket to at most `min_capacity`, or to its
+/// length if that is larger.
+pub(crate) fn shrink_to<K, V>(bucket: &mut Bucket<K, V>, min_capacity: usize) {
+    #[cfg(not(feature = "smallvec"))]
+    bucket.shrink_to(min_capacity);
+
+    #[cfg(feature = "smallvec")]
+    {
+        let capacity = min_capacity.max(bucket.len());
+
+        if bucket.capacity() > capacity {
+            let mut shrunk = Bucket::with_capacity(capacity);
+            shrunk.extend(bucket.drain(..));
+            *bucket = shrunk;
+        }
+    }
+}
+
+/// Converts the bucket into a `Vec`, which does not allocate unless the
+/// entries are stored inline.
+#[inline]
+pub(crate) fn into_vec<K, V>(bucket: Bucket<K, V>) -> Vec<(K, V)> {
+    #[cfg(not(feature = "smallvec"))]
+    return bucket;
+
+    #[cfg(feature = "smallvec")]
+    bucket.into_vec()
+}
@@ -0,0 +1,158 @@
+use crate::{Radix, RadixHeapMap, Values};
+
+/// A heap of whole entries, ordered by a key computed from each entry.
+///
+/// This is like a [`RadixHeapMap`] where the key is derived from the value,
+/// similar to [`slice::sort_by_key`]. The key is computed once when an entry
+/// is pushed and stored alongside it.
+///
+/// ```
+/// use radix_heap::RadixHeapBy;
+/// use std::cmp::Reverse;
+///
+/// struct Entry {
+///     pos: (u32, u32),
+///     cost: u32,
+/// }
+///
+/// let mut heap = RadixHeapBy::new(|entry: &Entry| Reverse(entry.cost));
+/// heap.push(Entry { pos: (0, 0), cost: 4 });
+/// heap.push(Entry { pos: (1, 0), cost: 2 });
+///
+/// assert_eq!(heap.pop().map(|entry| entry.pos), Some((1, 0)));
+/// assert_eq!(heap.top(), Some(Reverse(2)));
+/// ```
+#[derive(Clone)]
+pub struct RadixHeapBy<K, T, F> {
+    heap: RadixHeapMap<K, T>,
+    key: F,
+}
+
+impl<K, T, F> RadixHeapBy<K, T, F>
+where
+    K: Radix + Ord + Copy,
+    F: FnMut(&T) -> K,
+{
+    /// Create an empty `RadixHeapBy` that orders entries by `key`.
+    pub fn new(key: F) -> RadixHeapBy<K, T, F> {
+        RadixHeapBy {
+            heap: RadixHeapMap::new(),
+            key,
+        }
+    }
+
+    /// Create an empty `RadixHeapBy` that orders entries by `key`, with the
+    /// top key set to `top`.
+    pub fn new_at(top: K, key: F) -> RadixHeapBy<K, T, F> {
+        RadixHeapBy {
+            heap: RadixHeapMap::new_at(top),
+            key,
+        }
+    }
+
+    /// Pushes an entry onto the heap.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key of the entry is larger than the current top key.
+    #[inline]
+    pub fn push(&mut self, entry: T) {
+        let key = (self.key)(&entry);
+        self.heap.push(key, entry);
+    }
+
+    /// Removes the entry with the greatest key and returns it, or `None` if
+    /// empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|(_, entry)| entry)
+    }
+
+    /// Returns the entry with the greatest key without removing it, or `None`
+    /// if empty.
+    pub fn peek(&mut self) -> Option<&T> {
+        self.heap.peek_value()
+    }
+
+    /// The current top key. All keys of pushed entries must be smaller than
+    /// this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.heap.top()
+    }
+
+    /// Returns the number of entries in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if there are no entries in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Drops all entries from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// Returns an iterator of all entries in the heap in arbitrary order.
+    pub fn iter(&self) -> Values<'_, K, T> {
+        self.heap.values()
+    }
+
+    /// The underlying heap of keys and entries.
+    #[inline]
+    pub fn as_map(&self) -> &RadixHeapMap<K, T> {
+        &self.heap
+    }
+
+    /// Returns the underlying heap of keys and entries.
+    pub fn into_map(self) -> RadixHeapMap<K, T> {
+        self.heap
+    }
+}
+
+impl<K, T, F> Extend<T> for RadixHeapBy<K, T, F>
+where
+    K: Radix + Ord + Copy,
+    F: FnMut(&T) -> K,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for entry in iter {
+            self.push(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::RadixHeapBy;
+    use std::cell::Cell;
+
+    #[test]
+    fn sort_by_key() {
+        fn prop(mut xs: Vec<(u16, u8)>) -> bool {
+            let calls = Cell::new(0);
+            let mut heap = RadixHeapBy::new(|&(key, _): &(u16, u8)| {
+                calls.set(calls.get() + 1);
+                key
+            });
+            heap.extend(xs.iter().copied());
+
+            xs.sort_by_key(|&(key, _)| key);
+
+            std::iter::from_fn(|| heap.pop())
+                .map(|(key, _)| key)
+                .eq(xs.iter().rev().map(|&(key, _)| key))
+                && calls.get() == xs.len()
+        }
+
+        quickcheck(prop as fn(Vec<(u16, u8)>) -> bool);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::Radix;
+use core::{cmp::Ordering, fmt};
+
+/// A byte string of at most `N` bytes, usable as a key.
+///
+/// Byte strings are ordered lexicographically, so a string sorts before any
+/// longer string it is a prefix of. Unlike a padded `[u8; N]`, this keeps
+/// `b"ab"` and `b"ab\0"` distinct.
+///
+/// For the radix distance, each byte is preceded by a bit telling whether it
+/// is present, so comparing the encodings bit by bit gives the same order as
+/// comparing the strings.
+///
+/// ```
+/// use radix_heap::{BoundedBytes, RadixHeapMap};
+///
+/// let key = |bytes: &[u8]| BoundedBytes::<8>::new(bytes).unwrap();
+///
+/// let mut heap = RadixHeapMap::new();
+/// heap.push(key(b"ab"), 1);
+/// heap.push(key(b"abc"), 2);
+/// heap.push(key(b"a"), 3);
+///
+/// assert_eq!(heap.pop(), Some((key(b"abc"), 2)));
+/// assert_eq!(heap.pop(), Some((key(b"ab"), 1)));
+/// assert_eq!(heap.pop(), Some((key(b"a"), 3)));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundedBytes<const N: usize> {
+    len: usize,
+
+    /// The bytes, padded with zeros after `len`.
+    bytes: [u8; N],
+}
+
+impl<const N: usize> BoundedBytes<N> {
+    /// Create a `BoundedBytes` from a slice, or `None` if it is longer than
+    /// `N` bytes.
+    pub fn new(bytes: &[u8]) -> Option<BoundedBytes<N>> {
+        if bytes.len() > N {
+            return None;
+        }
+
+        let mut padded = [0; N];
+        padded[..bytes.len()].copy_from_slice(bytes);
+
+        Some(BoundedBytes {
+            len: bytes.len(),
+            bytes: padded,
+        })
+    }
+
+    /// The bytes of the string.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// The number of bytes in the string.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the string has no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<const N: usize> Default for BoundedBytes<N> {
+    fn default() -> BoundedBytes<N> {
+        BoundedBytes {
+            len: 0,
+            bytes: [0; N],
+        }
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for BoundedBytes<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> fmt::Debug for BoundedBytes<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("BoundedBytes")
+            .field(&self.as_bytes())
+            .finish()
+    }
+}
+
+impl<const N: usize> Ord for BoundedBytes<N> {
+    #[inline]
+    fn cmp(&self, other: &BoundedBytes<N>) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl<const N: usize> PartialOrd for BoundedBytes<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &BoundedBytes<N>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Radix for BoundedBytes<N> {
+    #[inline]
+    fn radix_similarity(&self, other: &BoundedBytes<N>) -> u32 {
+        for i in 0..N {
+            let (present, other_present) = (i < self.len, i < other.len);
+
+            if present != other_present {
+                return 9 * i as u32;
+            } else if !present {
+                break;
+            } else if self.bytes[i] != other.bytes[i] {
+                return 9 * i as u32 + 1 + self.bytes[i].radix_similarity(&other.bytes[i]);
+            }
+        }
+
+        Self::RADIX_BITS
+    }
+
+    const RADIX_BITS: u32 = 9 * N as u32;
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::BoundedBytes;
+    use crate::{Radix, RadixHeapMap};
+
+    type Key = BoundedBytes<3>;
+
+    fn key(bytes: &[u8]) -> Key {
+        Key::new(&bytes[..bytes.len().min(3)]).unwrap()
+    }
+
+    #[test]
+    fn similarity() {
+        assert_eq!(key(b"ab").radix_similarity(&key(b"ab")), 27);
+        assert_eq!(key(b"ab").radix_similarity(&key(b"abc")), 18);
+        assert_eq!(key(b"").radix_similarity(&key(b"\0")), 0);
+        assert_eq!(key(b"a\x80").radix_similarity(&key(b"a\0")), 10);
+        assert!(Key::new(b"abcd").is_none());
+    }
+
+    #[test]
+    fn sort() {
+        fn prop(xs: Vec<Vec<u8>>) -> bool {
+            let mut xs: Vec<_> = xs.iter().map(|x| key(x)).collect();
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&k| (k, ())).collect();
+            xs.sort();
+
+            while let Some(x) = xs.pop() {
+                if heap.pop() != Some((x, ())) {
+                    return false;
+                }
+            }
+
+            heap.is_empty()
+        }
+
+        quickcheck(prop as fn(Vec<Vec<u8>>) -> bool);
+    }
+}
@@ -0,0 +1,395 @@
+use crate::Radix;
+use alloc::vec::Vec;
+use core::{cmp::Reverse, iter::FromIterator, num::Wrapping};
+
+/// A key whose bits can be split into bytes, for use with
+/// [`ByteRadixHeapMap`].
+///
+/// The bytes are those of an encoding of the key that is ordered like the
+/// key, most significant first. If two keys first differ in bit `s`, as given
+/// by [`radix_similarity`](Radix::radix_similarity), their bytes before byte
+/// `s / 8` must be equal, and their bytes at `s / 8` must be ordered like the
+/// keys.
+pub trait RadixBytes: Radix {
+    /// The byte at `index`, counting from the most significant byte.
+    fn radix_byte(&self, index: u32) -> u8;
+}
+
+macro_rules! radix_bytes_unsigned_impl {
+    ($t:ty) => {
+        impl RadixBytes for $t {
+            #[inline]
+            fn radix_byte(&self, index: u32) -> u8 {
+                (self >> (<$t>::RADIX_BITS - 8 - 8 * index)) as u8
+            }
+        }
+    };
+}
+
+macro_rules! radix_bytes_signed_impl {
+    ($t:ty, $u:ty) => {
+        impl RadixBytes for $t {
+            #[inline]
+            fn radix_byte(&self, index: u32) -> u8 {
+                // Flipping the sign bit orders negative keys before positive
+                // ones without changing which bits differ.
+                ((*self as $u) ^ (1 << (<$t>::RADIX_BITS - 1))).radix_byte(index)
+            }
+        }
+    };
+}
+
+radix_bytes_unsigned_impl!(u8);
+radix_bytes_unsigned_impl!(u16);
+radix_bytes_unsigned_impl!(u32);
+radix_bytes_unsigned_impl!(u64);
+radix_bytes_unsigned_impl!(u128);
+radix_bytes_unsigned_impl!(usize);
+
+radix_bytes_signed_impl!(i8, u8);
+radix_bytes_signed_impl!(i16, u16);
+radix_bytes_signed_impl!(i32, u32);
+radix_bytes_signed_impl!(i64, u64);
+radix_bytes_signed_impl!(i128, u128);
+radix_bytes_signed_impl!(isize, usize);
+
+impl<T: RadixBytes> RadixBytes for Reverse<T> {
+    #[inline]
+    fn radix_byte(&self, index: u32) -> u8 {
+        !self.0.radix_byte(index)
+    }
+}
+
+impl<T: RadixBytes> RadixBytes for Wrapping<T> {
+    #[inline]
+    fn radix_byte(&self, index: u32) -> u8 {
+        self.0.radix_byte(index)
+    }
+}
+
+/// The buckets of the entries first differing from the top key in one byte,
+/// indexed by their value of that byte.
+#[derive(Clone)]
+struct Level<K, V> {
+    buckets: Vec<Vec<(K, V)>>,
+
+    /// A bit set of the non-empty buckets.
+    occupied: [u64; 4],
+}
+
+impl<K, V> Level<K, V> {
+    fn new() -> Level<K, V> {
+        Level {
+            buckets: (0..256).map(|_| Vec::new()).collect(),
+            occupied: [0; 4],
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, byte: u8, entry: (K, V)) {
+        self.buckets[byte as usize].push(entry);
+        self.occupied[byte as usize / 64] |= 1 << (byte % 64);
+    }
+
+    /// The largest byte with a non-empty bucket.
+    fn highest(&self) -> Option<u8> {
+        (0..4).rev().find_map(|word| {
+            let bits = self.occupied[word];
+            (bits != 0).then(|| (word * 64 + 63 - bits.leading_zeros() as usize) as u8)
+        })
+    }
+
+    fn take(&mut self, byte: u8) -> Vec<(K, V)> {
+        self.occupied[byte as usize / 64] &= !(1 << (byte % 64));
+        core::mem::take(&mut self.buckets[byte as usize])
+    }
+
+    fn clear(&mut self) {
+        for bucket in &mut self.buckets {
+            bucket.clear();
+        }
+
+        self.occupied = [0; 4];
+    }
+}
+
+/// A monotone priority queue that buckets entries by byte rather than by
+/// bit.
+///
+/// This is an alternative to [`RadixHeapMap`](crate::RadixHeapMap) for keys
+/// with large spreads, such as 64-bit timestamps. Entries are bucketed by the
+/// first byte in which they differ from the top key, and by their value of
+/// that byte. When a pop empties the buckets of the top key, only the entries
+/// of the bucket holding the greatest keys are redistributed, and each entry
+/// moves at least one byte closer to the top key. An entry is thereby
+/// redistributed at most once per byte of the key, rather than once per bit.
+///
+/// In exchange, every heap holds 256 buckets for each byte of the key, and
+/// finding the next bucket scans a bit set of them. Which of the two is faster
+/// depends on the workload, so compare them in its benchmarks.
+///
+/// ```
+/// use radix_heap::ByteRadixHeapMap;
+///
+/// let mut heap = ByteRadixHeapMap::new();
+/// heap.extend(vec![(1u64 << 40, 'a'), (7, 'b'), (1 << 20, 'c')]);
+///
+/// assert_eq!(heap.pop(), Some((1 << 40, 'a')));
+/// heap.push(1 << 30, 'd');
+/// assert_eq!(heap.pop(), Some((1 << 30, 'd')));
+/// assert_eq!(heap.top(), Some(1 << 30));
+/// ```
+#[derive(Clone)]
+pub struct ByteRadixHeapMap<K, V> {
+    len: usize,
+
+    /// The current top key, or none if one is not set yet.
+    top: Option<K>,
+
+    /// The entries with keys equal to the top key.
+    equal: Vec<(K, V)>,
+
+    /// The entries first differing from the top key in byte `i`, for each
+    /// byte of the key.
+    levels: Vec<Level<K, V>>,
+
+    /// The initial entries before a top key is found.
+    initial: Vec<(K, V)>,
+}
+
+impl<K: RadixBytes + Ord + Copy, V> ByteRadixHeapMap<K, V> {
+    /// Create an empty `ByteRadixHeapMap`.
+    pub fn new() -> ByteRadixHeapMap<K, V> {
+        let levels = K::RADIX_BITS / 8 + (K::RADIX_BITS % 8 != 0) as u32;
+
+        ByteRadixHeapMap {
+            len: 0,
+            top: None,
+            equal: Vec::new(),
+            levels: (0..levels).map(|_| Level::new()).collect(),
+            initial: Vec::new(),
+        }
+    }
+
+    /// Create an empty `ByteRadixHeapMap` with the top key set to a specific
+    /// value.
+    pub fn new_at(top: K) -> ByteRadixHeapMap<K, V> {
+        let mut heap = ByteRadixHeapMap::new();
+        heap.top = Some(top);
+        heap
+    }
+
+    /// Drops all items from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.top = None;
+        self.equal.clear();
+        self.initial.clear();
+
+        for level in &mut self.levels {
+            level.clear();
+        }
+    }
+
+    /// Pushes a new key value pair onto the heap.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        match self.top {
+            Some(top) => {
+                assert!(key <= top, "Key must be lower or equal to current top key");
+                self.place(top, (key, value));
+            }
+            None => self.initial.push((key, value)),
+        }
+
+        self.len += 1;
+    }
+
+    /// Remove the greatest element from the heap and returns it, or `None` if
+    /// empty.
+    ///
+    /// If there is a tie between multiple elements, the last inserted element
+    /// will be popped first. This will set the top key to the extracted key.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.equal.is_empty() {
+            self.refill();
+        }
+
+        let entry = self.equal.pop()?;
+        self.len -= 1;
+        Some(entry)
+    }
+
+    /// The current top key. All keys pushed onto the heap must be smaller than
+    /// this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.top
+    }
+
+    /// Returns the number of elements in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no elements in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Puts an entry into the bucket for its distance from `top`.
+    #[inline]
+    fn place(&mut self, top: K, entry: (K, V)) {
+        let similarity = entry.0.radix_similarity(&top);
+
+        if similarity >= K::RADIX_BITS {
+            self.equal.push(entry);
+        } else {
+            let index = similarity / 8;
+            let byte = entry.0.radix_byte(index);
+            self.levels[index as usize].push(byte, entry);
+        }
+    }
+
+    /// Sets the top key to the greatest key in the heap, and moves the entries
+    /// of its bucket closer to it.
+    fn refill(&mut self) {
+        let bucket = if self.top.is_none() {
+            core::mem::take(&mut self.initial)
+        } else {
+            // Entries differing from the top key in a later byte are closer to
+            // it, and so greater.
+            let found = self
+                .levels
+                .iter()
+                .enumerate()
+                .rev()
+                .find_map(|(index, level)| Some((index, level.highest()?)));
+
+            match found {
+                Some((index, byte)) => self.levels[index].take(byte),
+                None => return,
+            }
+        };
+
+        let top = match bucket.iter().map(|&(key, _)| key).max() {
+            Some(top) => top,
+            None => return,
+        };
+
+        self.top = Some(top);
+
+        for entry in bucket {
+            self.place(top, entry);
+        }
+    }
+}
+
+impl<K: RadixBytes + Ord + Copy, V> Default for ByteRadixHeapMap<K, V> {
+    fn default() -> ByteRadixHeapMap<K, V> {
+        ByteRadixHeapMap::new()
+    }
+}
+
+impl<K: RadixBytes + Ord + Copy, V> FromIterator<(K, V)> for ByteRadixHeapMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> ByteRadixHeapMap<K, V> {
+        let mut heap = ByteRadixHeapMap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<K: RadixBytes + Ord + Copy, V> Extend<(K, V)> for ByteRadixHeapMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.push(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::{quickcheck, TestResult};
+    use super::{ByteRadixHeapMap, RadixBytes};
+    use crate::RadixHeapMap;
+    use std::cmp::{Ordering, Reverse};
+
+    fn consistent<K: RadixBytes + Ord>(a: K, b: K) -> bool {
+        let similarity = a.radix_similarity(&b);
+
+        if similarity >= K::RADIX_BITS {
+            return a == b;
+        }
+
+        let index = similarity / 8;
+        (0..index).all(|i| a.radix_byte(i) == b.radix_byte(i))
+            && a.radix_byte(index).cmp(&b.radix_byte(index)) == a.cmp(&b)
+            && a.cmp(&b) != Ordering::Equal
+    }
+
+    #[test]
+    fn bytes_consistent() {
+        fn prop(a: i64, b: i64, c: u16, d: u16) -> bool {
+            consistent(a, b) && consistent(Reverse(a), Reverse(b)) && consistent(c, d)
+        }
+
+        quickcheck(prop as fn(i64, i64, u16, u16) -> bool);
+    }
+
+    #[test]
+    fn matches_radix_heap() {
+        fn prop(ops: Vec<Option<(u8, i32)>>) -> TestResult {
+            let mut heap = ByteRadixHeapMap::new();
+            let mut reference = RadixHeapMap::new();
+
+            for op in ops {
+                match op {
+                    Some((delta, value)) => {
+                        let key = reference
+                            .top()
+                            .map_or(value as i64, |top: i64| top - delta as i64);
+                        heap.push(key, value);
+                        reference.push(key, value);
+                    }
+                    None => {
+                        let popped = heap.pop().map(|(key, _)| key);
+                        if popped != reference.pop().map(|(key, _)| key) {
+                            return TestResult::failed();
+                        }
+                    }
+                }
+
+                if heap.len() != reference.len() || heap.top() != reference.top() {
+                    return TestResult::failed();
+                }
+            }
+
+            TestResult::from_bool(
+                std::iter::from_fn(|| heap.pop())
+                    .map(|(key, _)| key)
+                    .eq(std::iter::from_fn(|| reference.pop()).map(|(key, _)| key)),
+            )
+        }
+
+        quickcheck(prop as fn(Vec<Option<(u8, i32)>>) -> TestResult);
+    }
+
+    #[test]
+    fn wide_spread() {
+        let keys = [u64::MAX, 1 << 63, 1 << 40, 1 << 40, 255, 0];
+        let mut heap: ByteRadixHeapMap<_, _> = keys.iter().rev().map(|&k| (k, ())).collect();
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).map(|(k, _)| k).collect();
+        assert_eq!(popped, keys);
+        assert!(heap.is_empty());
+    }
+}
@@ -0,0 +1,470 @@
+use crate::Radix;
+use alloc::vec::Vec;
+use core::{
+    cmp::Reverse,
+    fmt,
+    iter::{FromIterator, FusedIterator},
+    num::Wrapping,
+    slice,
+};
+
+/// The number of low bits stored for entries close to the top key.
+const NEAR_BITS: u32 = 32;
+
+/// A key that converts to and from the integer made of its
+/// [`RADIX_BITS`](Radix::RADIX_BITS) bits, for use with
+/// [`CompactRadixHeapMap`].
+///
+/// The integers must be ordered like the keys, and two keys must first differ
+/// in the same bit as their integers, as given by
+/// [`radix_similarity`](Radix::radix_similarity).
+pub trait CompactKey: Radix + Ord + Copy {
+    /// The bits of the key, in the low `RADIX_BITS` bits.
+    fn to_radix_bits(self) -> u128;
+
+    /// The key with the given bits.
+    fn from_radix_bits(bits: u128) -> Self;
+}
+
+macro_rules! compact_unsigned_impl {
+    ($t:ty) => {
+        impl CompactKey for $t {
+            #[inline]
+            fn to_radix_bits(self) -> u128 {
+                self as u128
+            }
+
+            #[inline]
+            fn from_radix_bits(bits: u128) -> $t {
+                bits as $t
+            }
+        }
+    };
+}
+
+macro_rules! compact_signed_impl {
+    ($t:ty, $u:ty) => {
+        impl CompactKey for $t {
+            #[inline]
+            fn to_radix_bits(self) -> u128 {
+                // Flipping the sign bit orders negative keys before positive
+                // ones without changing which bits differ.
+                ((self as $u) ^ (1 << (<$t>::RADIX_BITS - 1))) as u128
+            }
+
+            #[inline]
+            fn from_radix_bits(bits: u128) -> $t {
+                ((bits as $u) ^ (1 << (<$t>::RADIX_BITS - 1))) as $t
+            }
+        }
+    };
+}
+
+compact_unsigned_impl!(u8);
+compact_unsigned_impl!(u16);
+compact_unsigned_impl!(u32);
+compact_unsigned_impl!(u64);
+compact_unsigned_impl!(u128);
+compact_unsigned_impl!(usize);
+
+compact_signed_impl!(i8, u8);
+compact_signed_impl!(i16, u16);
+compact_signed_impl!(i32, u32);
+compact_signed_impl!(i64, u64);
+compact_signed_impl!(i128, u128);
+compact_signed_impl!(isize, usize);
+
+impl<T: CompactKey> CompactKey for Reverse<T> {
+    #[inline]
+    fn to_radix_bits(self) -> u128 {
+        !self.0.to_radix_bits() & (u128::MAX >> (128 - T::RADIX_BITS))
+    }
+
+    #[inline]
+    fn from_radix_bits(bits: u128) -> Reverse<T> {
+        Reverse(T::from_radix_bits(
+            !bits & (u128::MAX >> (128 - T::RADIX_BITS)),
+        ))
+    }
+}
+
+impl<T: CompactKey> CompactKey for Wrapping<T> {
+    #[inline]
+    fn to_radix_bits(self) -> u128 {
+        self.0.to_radix_bits()
+    }
+
+    #[inline]
+    fn from_radix_bits(bits: u128) -> Wrapping<T> {
+        Wrapping(T::from_radix_bits(bits))
+    }
+}
+
+/// The high bits of a key shared by every entry within [`NEAR_BITS`] of it.
+#[inline]
+fn high_bits<K: CompactKey>(key: K) -> u128 {
+    key.to_radix_bits() >> NEAR_BITS << NEAR_BITS
+}
+
+/// A monotone priority queue like [`RadixHeapMap`](crate::RadixHeapMap) that
+/// stores only the low 32 bits of keys close to the top key.
+///
+/// An entry in bucket `i` shares every bit above bit `i` with the top key,
+/// and still does once the top key moves closer to it. For buckets up to 32,
+/// the low 32 bits are stored instead of the key, and the key is rebuilt from
+/// them and the top key on pop or iteration. Entries in further buckets are
+/// stored whole. With `u64` or `u128` keys, this shrinks the entries that
+/// make up most of a large frontier, at the cost of converting keys on every
+/// push and pop.
+///
+/// ```
+/// use radix_heap::CompactRadixHeapMap;
+///
+/// let mut heap = CompactRadixHeapMap::new();
+/// heap.extend(vec![(1u64 << 40, 'a'), (7, 'b'), ((1 << 40) - 3, 'c')]);
+///
+/// assert_eq!(heap.pop(), Some((1 << 40, 'a')));
+/// assert_eq!(heap.pop(), Some(((1 << 40) - 3, 'c')));
+/// assert_eq!(heap.pop(), Some((7, 'b')));
+/// ```
+#[derive(Clone)]
+pub struct CompactRadixHeapMap<K, V> {
+    len: usize,
+
+    /// The current top key, or none if one is not set yet.
+    top: Option<K>,
+
+    /// The low bits of the entries in each bucket up to `NEAR_BITS`.
+    near: Vec<Vec<(u32, V)>>,
+
+    /// The entries in each bucket after `NEAR_BITS`.
+    far: Vec<Vec<(K, V)>>,
+
+    /// The initial entries before a top key is found.
+    initial: Vec<(K, V)>,
+}
+
+impl<K: CompactKey, V> CompactRadixHeapMap<K, V> {
+    /// Create an empty `CompactRadixHeapMap`.
+    pub fn new() -> CompactRadixHeapMap<K, V> {
+        let near = K::RADIX_BITS.min(NEAR_BITS);
+        let far = K::RADIX_BITS - near;
+
+        CompactRadixHeapMap {
+            len: 0,
+            top: None,
+            near: (0..=near).map(|_| Vec::new()).collect(),
+            far: (0..far).map(|_| Vec::new()).collect(),
+            initial: Vec::new(),
+        }
+    }
+
+    /// Create an empty `CompactRadixHeapMap` with the top key set to a
+    /// specific value.
+    pub fn new_at(top: K) -> CompactRadixHeapMap<K, V> {
+        let mut heap = CompactRadixHeapMap::new();
+        heap.top = Some(top);
+        heap
+    }
+
+    /// Drops all items from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.top = None;
+        self.initial.clear();
+
+        for bucket in &mut self.near {
+            bucket.clear();
+        }
+
+        for bucket in &mut self.far {
+            bucket.clear();
+        }
+    }
+
+    /// Pushes a new key value pair onto the heap.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        match self.top {
+            Some(top) => {
+                assert!(key <= top, "Key must be lower or equal to current top key");
+                self.place(top, key, value);
+            }
+            None => self.initial.push((key, value)),
+        }
+
+        self.len += 1;
+    }
+
+    /// Remove the greatest element from the heap and returns it, or `None` if
+    /// empty.
+    ///
+    /// If there is a tie between multiple elements, the last inserted element
+    /// will be popped first. This will set the top key to the extracted key.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.near[0].is_empty() {
+            self.constrain();
+        }
+
+        let (_, value) = self.near[0].pop()?;
+        self.len -= 1;
+        self.top.map(|top| (top, value))
+    }
+
+    /// The current top key. All keys pushed onto the heap must be smaller than
+    /// this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.top
+    }
+
+    /// Returns the number of elements in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no elements in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator of all key-value pairs in the heap in arbitrary
+    /// order. Keys are rebuilt from the top key, so they are returned by
+    /// value.
+    pub fn iter(&self) -> CompactIter<'_, K, V> {
+        CompactIter {
+            high: self.top.map_or(0, high_bits),
+            initial: self.initial.iter(),
+            near: self.near.iter(),
+            near_bucket: [].iter(),
+            far: self.far.iter(),
+            far_bucket: [].iter(),
+            remaining: self.len,
+        }
+    }
+
+    /// Puts an entry into the bucket for its distance from `top`.
+    #[inline]
+    fn place(&mut self, top: K, key: K, value: V) {
+        let distance = key.radix_distance(&top);
+
+        if distance <= NEAR_BITS {
+            self.near[distance as usize].push((key.to_radix_bits() as u32, value));
+        } else {
+            self.far[(distance - NEAR_BITS - 1) as usize].push((key, value));
+        }
+    }
+
+    /// Sets the top key to the greatest key in the heap, and moves the entries
+    /// of its bucket closer to it.
+    fn constrain(&mut self) {
+        let top = match self.top {
+            Some(top) => top,
+            None => {
+                let mut bucket = core::mem::take(&mut self.initial);
+                self.redistribute(&mut bucket);
+                self.initial = bucket;
+                return;
+            }
+        };
+
+        if let Some(index) = self.near.iter().position(|bucket| !bucket.is_empty()) {
+            // The stored bits are only valid with the high bits of the old top
+            // key, which every key in the bucket shares, so the largest low
+            // bits belong to the largest key.
+            let high = high_bits(top);
+            let key = |low: u32| K::from_radix_bits(high | low as u128);
+
+            let mut bucket = core::mem::take(&mut self.near[index]);
+            let top = match bucket.iter().map(|&(low, _)| low).max() {
+                Some(low) => key(low),
+                None => return,
+            };
+
+            self.top = Some(top);
+
+            for (low, value) in bucket.drain(..) {
+                self.place(top, key(low), value);
+            }
+
+            self.near[index] = bucket;
+        } else if let Some(index) = self.far.iter().position(|bucket| !bucket.is_empty()) {
+            let mut bucket = core::mem::take(&mut self.far[index]);
+            self.redistribute(&mut bucket);
+            self.far[index] = bucket;
+        }
+    }
+
+    /// Sets the top key to the greatest key of `entries`, and moves them into
+    /// their buckets, leaving `entries` empty.
+    fn redistribute(&mut self, entries: &mut Vec<(K, V)>) {
+        let top = match entries.iter().map(|&(key, _)| key).max() {
+            Some(top) => top,
+            None => return,
+        };
+
+        self.top = Some(top);
+
+        for (key, value) in entries.drain(..) {
+            self.place(top, key, value);
+        }
+    }
+}
+
+impl<K: CompactKey, V> Default for CompactRadixHeapMap<K, V> {
+    fn default() -> CompactRadixHeapMap<K, V> {
+        CompactRadixHeapMap::new()
+    }
+}
+
+impl<K: CompactKey, V> FromIterator<(K, V)> for CompactRadixHeapMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> CompactRadixHeapMap<K, V> {
+        let mut heap = CompactRadixHeapMap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<K: CompactKey, V> Extend<(K, V)> for CompactRadixHeapMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.push(key, value);
+        }
+    }
+}
+
+impl<K: CompactKey + fmt::Debug, V: fmt::Debug> fmt::Debug for CompactRadixHeapMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the elements of a [`CompactRadixHeapMap`].
+///
+/// This `struct` is created by [`CompactRadixHeapMap::iter`].
+#[derive(Clone)]
+pub struct CompactIter<'a, K, V> {
+    /// The high bits of the top key, which the near entries share.
+    high: u128,
+    initial: slice::Iter<'a, (K, V)>,
+    near: slice::Iter<'a, Vec<(u32, V)>>,
+    near_bucket: slice::Iter<'a, (u32, V)>,
+    far: slice::Iter<'a, Vec<(K, V)>>,
+    far_bucket: slice::Iter<'a, (K, V)>,
+    remaining: usize,
+}
+
+impl<'a, K: CompactKey, V> Iterator for CompactIter<'a, K, V> {
+    type Item = (K, &'a V);
+
+    fn next(&mut self) -> Option<(K, &'a V)> {
+        if let Some((key, value)) = self.initial.next() {
+            self.remaining -= 1;
+            return Some((*key, value));
+        }
+
+        loop {
+            if let Some((low, value)) = self.near_bucket.next() {
+                self.remaining -= 1;
+                return Some((K::from_radix_bits(self.high | *low as u128), value));
+            }
+
+            match self.near.next() {
+                Some(bucket) => self.near_bucket = bucket.iter(),
+                None => break,
+            }
+        }
+
+        loop {
+            if let Some((key, value)) = self.far_bucket.next() {
+                self.remaining -= 1;
+                return Some((*key, value));
+            }
+
+            self.far_bucket = self.far.next()?.iter();
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: CompactKey, V> ExactSizeIterator for CompactIter<'a, K, V> {}
+
+impl<'a, K: CompactKey, V> FusedIterator for CompactIter<'a, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::{CompactKey, CompactRadixHeapMap};
+    use crate::RadixHeapMap;
+    use std::cmp::Reverse;
+
+    fn consistent<K: CompactKey>(a: K, b: K) -> bool {
+        let (x, y) = (a.to_radix_bits(), b.to_radix_bits());
+        let distance = 128 - (x ^ y).leading_zeros();
+
+        K::from_radix_bits(x) == a && a.cmp(&b) == x.cmp(&y) && a.radix_distance(&b) == distance
+    }
+
+    #[test]
+    fn bits() {
+        fn prop(a: i64, b: i64) -> bool {
+            consistent(a, b)
+                && consistent(a as u64, b as u64)
+                && consistent(Reverse(a as i8), Reverse(b as i8))
+                && consistent(Reverse(a as u128), Reverse((b as u128).wrapping_mul(3)))
+        }
+
+        quickcheck(prop as fn(i64, i64) -> bool);
+    }
+
+    #[test]
+    fn matches_radix_heap() {
+        fn prop(ops: Vec<Option<(u64, u8)>>) -> bool {
+            let mut heap = CompactRadixHeapMap::new();
+            let mut expected = RadixHeapMap::new();
+
+            for op in ops {
+                match op {
+                    Some((key, shift)) => {
+                        // Spread the keys across both near and far buckets.
+                        let key = key >> (shift % 64);
+                        let key = expected.top().map_or(key, |top| top - key.min(top));
+                        heap.push(key, ());
+                        expected.push(key, ());
+                    }
+                    None => {
+                        if heap.pop() != expected.pop() {
+                            return false;
+                        }
+                    }
+                }
+
+                let mut keys: Vec<_> = heap.iter().map(|(key, _)| key).collect();
+                let mut expected_keys: Vec<_> = expected.iter().map(|&(key, _)| key).collect();
+                keys.sort_unstable();
+                expected_keys.sort_unstable();
+
+                if keys != expected_keys || heap.top() != expected.top() {
+                    return false;
+                }
+            }
+
+            std::iter::from_fn(|| heap.pop()).eq(std::iter::from_fn(|| expected.pop()))
+        }
+
+        quickcheck(prop as fn(Vec<Option<(u64, u8)>>) -> bool);
+    }
+}
@@ -0,0 +1,204 @@
+//! A common interface for comparing priority queue backends.
+//!
+//! [`PriorityQueue`] is implemented for [`RadixHeapMap`] as well as
+//! [`BinaryHeap`], the heaps of [`dary_heap`] and the pairing heap of
+//! [`pairing_heap`], so code written against it can swap backends with a
+//! type parameter and benchmark them on real workloads.
+//!
+//! A radix heap is a monotone priority queue, so the comparison is only
+//! meaningful for workloads that never push an item greater than the last one
+//! popped. With entries of `(key, value)`, the other backends order by value
+//! as well between equal keys, so the order of values with equal keys may
+//! differ between backends.
+//!
+//! ```
+//! use radix_heap::{compare::PriorityQueue, RadixHeapMap};
+//! use std::{cmp::Reverse, collections::BinaryHeap};
+//!
+//! fn countdown<Q: PriorityQueue<Item = (Reverse<u32>, char)>>(mut queue: Q) -> Vec<char> {
+//!     queue.push((Reverse(2), 'b'));
+//!     queue.push((Reverse(1), 'a'));
+//!     std::iter::from_fn(|| queue.pop()).map(|(_, c)| c).collect()
+//! }
+//!
+//! assert_eq!(countdown(RadixHeapMap::new()), vec!['a', 'b']);
+//! assert_eq!(countdown(BinaryHeap::new()), vec!['a', 'b']);
+//! assert_eq!(countdown(dary_heap::QuaternaryHeap::new()), vec!['a', 'b']);
+//! ```
+
+use crate::{Radix, RadixHeapMap};
+use alloc::collections::BinaryHeap;
+use dary_heap::DaryHeap;
+use pairing_heap::PairingHeap;
+
+/// A max-priority queue.
+pub trait PriorityQueue {
+    /// The items in the queue.
+    type Item;
+
+    /// Pushes an item onto the queue.
+    ///
+    /// Panics
+    /// ------
+    /// For a [`RadixHeapMap`], panics if the key is larger than the current
+    /// top key.
+    fn push(&mut self, item: Self::Item);
+
+    /// Removes the greatest item from the queue and returns it, or `None` if
+    /// empty.
+    fn pop(&mut self) -> Option<Self::Item>;
+
+    /// Returns the number of items in the queue.
+    fn len(&self) -> usize;
+
+    /// Returns true if there are no items in the queue.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all items from the queue.
+    fn clear(&mut self);
+}
+
+impl<K: Radix + Ord + Clone, V> PriorityQueue for RadixHeapMap<K, V> {
+    type Item = (K, V);
+
+    #[inline]
+    fn push(&mut self, (key, value): (K, V)) {
+        RadixHeapMap::push(self, key, value);
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<(K, V)> {
+        RadixHeapMap::pop(self)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        RadixHeapMap::len(self)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        RadixHeapMap::clear(self);
+    }
+}
+
+impl<T: Ord> PriorityQueue for BinaryHeap<T> {
+    type Item = T;
+
+    #[inline]
+    fn push(&mut self, item: T) {
+        BinaryHeap::push(self, item);
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        BinaryHeap::pop(self)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        BinaryHeap::clear(self);
+    }
+}
+
+impl<T: Ord, const D: usize> PriorityQueue for DaryHeap<T, D> {
+    type Item = T;
+
+    #[inline]
+    fn push(&mut self, item: T) {
+        DaryHeap::push(self, item);
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        DaryHeap::pop(self)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        DaryHeap::len(self)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        DaryHeap::clear(self);
+    }
+}
+
+impl<T: Ord> PriorityQueue for PairingHeap<T> {
+    type Item = T;
+
+    #[inline]
+    fn push(&mut self, item: T) {
+        PairingHeap::push(self, item);
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        PairingHeap::pop(self)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        PairingHeap::len(self)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        PairingHeap::clear(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::PriorityQueue;
+    use crate::RadixHeapMap;
+    use dary_heap::TernaryHeap;
+    use pairing_heap::PairingHeap;
+    use std::collections::BinaryHeap;
+
+    /// Pushes `(key, value)` for each key and then pops until empty.
+    fn sort<Q: PriorityQueue<Item = (u16, usize)>>(mut queue: Q, keys: &[u16]) -> Vec<u16> {
+        for (i, &key) in keys.iter().enumerate() {
+            queue.push((key, i));
+        }
+        assert_eq!(queue.len(), keys.len());
+
+        let sorted = std::iter::from_fn(|| queue.pop()).map(|(k, _)| k).collect();
+        assert!(queue.is_empty());
+        sorted
+    }
+
+    #[test]
+    fn backends_agree() {
+        fn prop(keys: Vec<u16>) -> bool {
+            let sorted = sort(RadixHeapMap::new(), &keys);
+
+            sorted == sort(BinaryHeap::new(), &keys)
+                && sorted == sort(TernaryHeap::new(), &keys)
+                && sorted == sort(PairingHeap::new(), &keys)
+        }
+
+        quickcheck(prop as fn(Vec<u16>) -> bool);
+    }
+
+    #[test]
+    fn clear() {
+        let mut heap = RadixHeapMap::new();
+        PriorityQueue::push(&mut heap, (1u8, ()));
+        PriorityQueue::clear(&mut heap);
+        assert!(PriorityQueue::is_empty(&heap));
+        assert_eq!(heap.top(), None);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{Radix, RadixHeapMap};
+use std::{
+    cell::Cell,
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, MutexGuard, TryLockError,
+    },
+};
+
+thread_local! {
+    /// The state of the xorshift generator picking shards on this thread.
+    static SEED: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish() | 1);
+}
+
+/// Returns a pseudo-random index below `n`.
+fn random_below(n: usize) -> usize {
+    SEED.with(|seed| {
+        let mut x = seed.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        seed.set(x);
+        (x % n as u64) as usize
+    })
+}
+
+/// A radix heap shared between threads, split into shards that are locked
+/// independently, as in a MultiQueue.
+///
+/// A push goes to a random shard. A pop looks at the top of two random
+/// shards and pops from the one with the larger key, so threads rarely wait
+/// on each other. Each shard raises its top key when given a larger one, so
+/// pushing never panics.
+///
+/// Ordering
+/// --------
+/// The ordering is relaxed: [`pop_any`](ConcurrentRadixHeap::pop_any) returns
+/// a large key, but not necessarily the largest one, and keys popped on one
+/// thread are not in order. A search using it, such as a parallel Dijkstra,
+/// must therefore accept revisiting a node when it is later reached with a
+/// better key. With more shards there is less contention but a looser order,
+/// and about two to four shards per thread is a good start.
+///
+/// ```
+/// use radix_heap::ConcurrentRadixHeap;
+/// use std::{sync::Arc, thread};
+///
+/// let heap = Arc::new(ConcurrentRadixHeap::new(8));
+///
+/// let workers: Vec<_> = (0..4u32)
+///     .map(|t| {
+///         let heap = heap.clone();
+///         thread::spawn(move || {
+///             for i in 0..100 {
+///                 heap.push(t * 100 + i, ());
+///             }
+///         })
+///     })
+///     .collect();
+///
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+///
+/// let mut popped: Vec<_> = std::iter::from_fn(|| heap.pop_any()).map(|(k, ())| k).collect();
+/// popped.sort_unstable();
+/// assert_eq!(popped, (0..400).collect::<Vec<_>>());
+/// assert!(heap.is_empty());
+/// ```
+pub struct ConcurrentRadixHeap<K, V> {
+    shards: Vec<Mutex<RadixHeapMap<K, V>>>,
+    len: AtomicUsize,
+}
+
+impl<K: Radix + Ord + Clone, V> ConcurrentRadixHeap<K, V> {
+    /// Create an empty heap split into `shards` shards.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `shards` is zero.
+    pub fn new(shards: usize) -> ConcurrentRadixHeap<K, V> {
+        assert!(shards > 0, "Number of shards must be positive");
+
+        ConcurrentRadixHeap {
+            shards: (0..shards)
+                .map(|_| Mutex::new(RadixHeapMap::new()))
+                .collect(),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// The number of shards.
+    #[inline]
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Pushes a key value pair onto a random shard. Unlike
+    /// [`RadixHeapMap::push`], the key may be larger than keys already popped.
+    pub fn push(&self, key: K, value: V) {
+        let mut shard = self.lock_any();
+        shard.raise_top(key.clone());
+        shard.push_unchecked(key, value);
+        // Counted while the shard is still locked, so a pop of this item
+        // cannot be counted first.
+        self.len.fetch_add(1, Ordering::Release);
+    }
+
+    /// Removes a large element from the heap and returns it, or `None` if the
+    /// heap is empty. See the [ordering guarantees](ConcurrentRadixHeap#ordering).
+    pub fn pop_any(&self) -> Option<(K, V)> {
+        for _ in 0..self.shards.len() {
+            if self.is_empty() {
+                return None;
+            }
+
+            let (i, j) = (self.random_shard(), self.random_shard());
+
+            let mut first = match self.try_lock(i) {
+                Some(shard) => shard,
+                None => continue,
+            };
+            let mut second = if i == j { None } else { self.try_lock(j) };
+
+            let second_key = second.as_mut().and_then(|shard| shard.peek_key());
+            let shard = match &mut second {
+                Some(second) if second_key > first.peek_key() => second,
+                _ => &mut first,
+            };
+
+            if let Some(entry) = shard.pop() {
+                self.len.fetch_sub(1, Ordering::Relaxed);
+                return Some(entry);
+            }
+        }
+
+        // Random picks keep missing the few non-empty shards, or their locks,
+        // so fall back to visiting every shard in turn.
+        for shard in &self.shards {
+            if let Some(entry) = lock(shard).pop() {
+                self.len.fetch_sub(1, Ordering::Relaxed);
+                return Some(entry);
+            }
+        }
+
+        None
+    }
+
+    /// Returns the number of elements in the heap. Pushes and pops on other
+    /// threads can change it at any time.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    /// Returns true if there are no elements in the heap. Pushes and pops on
+    /// other threads can change it at any time.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the shards, each a heap of its own.
+    pub fn into_shards(self) -> Vec<RadixHeapMap<K, V>> {
+        self.shards
+            .into_iter()
+            .map(|shard| shard.into_inner().unwrap_or_else(|e| e.into_inner()))
+            .collect()
+    }
+
+    /// Locks a random shard, trying others while the ones picked are locked.
+    fn lock_any(&self) -> MutexGuard<'_, RadixHeapMap<K, V>> {
+        for _ in 0..self.shards.len() {
+            if let Some(shard) = self.try_lock(self.random_shard()) {
+                return shard;
+            }
+        }
+
+        lock(&self.shards[self.random_shard()])
+    }
+
+    #[inline]
+    fn random_shard(&self) -> usize {
+        random_below(self.shards.len())
+    }
+
+    /// Locks a shard, or returns `None` if another thread holds it.
+    fn try_lock(&self, index: usize) -> Option<MutexGuard<'_, RadixHeapMap<K, V>>> {
+        match self.shards[index].try_lock() {
+            Ok(shard) => Some(shard),
+            Err(TryLockError::Poisoned(e)) => Some(e.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+}
+
+/// Locks a shard, ignoring poisoning: a panic in another thread leaves the
+/// shard a valid heap.
+fn lock<T>(shard: &Mutex<T>) -> MutexGuard<'_, T> {
+    shard.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl<K, V> fmt::Debug for ConcurrentRadixHeap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConcurrentRadixHeap")
+            .field("shards", &self.shards.len())
+            .field("len", &self.len.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::ConcurrentRadixHeap;
+    use std::{sync::Arc, thread};
+
+    #[test]
+    fn pops_everything_pushed() {
+        fn prop(xs: Vec<u32>, shards: u8) -> bool {
+            let heap = ConcurrentRadixHeap::new(shards as usize % 8 + 1);
+
+            for (i, &x) in xs.iter().enumerate() {
+                heap.push(x, i);
+
+                // Pushing above popped keys must not panic.
+                if i % 3 == 0 {
+                    heap.pop_any();
+                }
+            }
+
+            let left = heap.len();
+            let popped = std::iter::from_fn(|| heap.pop_any()).count();
+            popped == left && heap.is_empty()
+        }
+
+        quickcheck(prop as fn(Vec<u32>, u8) -> bool);
+    }
+
+    #[test]
+    fn pops_larger_keys_first() {
+        let heap = ConcurrentRadixHeap::new(1);
+        heap.push(3u8, 'a');
+        heap.push(9, 'b');
+        heap.push(5, 'c');
+        assert_eq!(heap.pop_any(), Some((9, 'b')));
+        assert_eq!(heap.pop_any(), Some((5, 'c')));
+        assert_eq!(heap.pop_any(), Some((3, 'a')));
+        assert_eq!(heap.pop_any(), None);
+    }
+
+    #[test]
+    fn threads() {
+        let heap = Arc::new(ConcurrentRadixHeap::new(8));
+
+        let workers: Vec<_> = (0..4u64)
+            .map(|t| {
+                let heap = heap.clone();
+                thread::spawn(move || {
+                    let mut popped = Vec::new();
+
+                    for i in 0..1000 {
+                        heap.push(t * 1000 + i, t);
+
+                        if i % 2 == 0 {
+                            popped.extend(heap.pop_any().map(|(k, _)| k));
+                        }
+                    }
+
+                    popped
+                })
+            })
+            .collect();
+
+        let mut popped: Vec<u64> = workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect();
+        popped.extend(std::iter::from_fn(|| heap.pop_any()).map(|(k, _)| k));
+        popped.sort_unstable();
+
+        assert_eq!(popped, (0..4000).collect::<Vec<_>>());
+        assert!(heap.is_empty());
+        let heap = Arc::try_unwrap(heap).unwrap();
+        assert_eq!(heap.into_shards().iter().map(|s| s.len()).sum::<usize>(), 0);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{Radix, RadixHeapMap};
+
+/// A value along with the number of times it was pushed, for heaps with many
+/// duplicate entries.
+///
+/// A `RadixHeapMap<K, Counted<V>>` stores each duplicated entry once using
+/// [`push_count`](RadixHeapMap::push_count), and
+/// [`pop_one`](RadixHeapMap::pop_one) hands out one copy at a time, so memory
+/// stays proportional to the number of distinct entries. Note that
+/// [`len`](RadixHeapMap::len) counts distinct entries.
+///
+/// ```
+/// let mut heap = radix_heap::RadixHeapMap::new();
+/// heap.push_count(5, 'a', 1000);
+/// heap.push_count(3, 'b', 2);
+///
+/// assert_eq!(heap.len(), 2);
+/// for _ in 0..1000 {
+///     assert_eq!(heap.pop_one(), Some((5, 'a')));
+/// }
+/// assert_eq!(heap.pop_one(), Some((3, 'b')));
+/// assert_eq!(heap.pop_one(), Some((3, 'b')));
+/// assert_eq!(heap.pop_one(), None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Counted<V> {
+    /// The value.
+    pub value: V,
+
+    /// The number of copies of the value left.
+    pub count: usize,
+}
+
+impl<K: Radix + Ord + Copy, V: Clone> RadixHeapMap<K, Counted<V>> {
+    /// Pushes `n` copies of a key value pair as a single entry.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key, or if `n` is
+    /// zero.
+    pub fn push_count(&mut self, key: K, value: V, n: usize) {
+        assert!(n > 0, "Count must be positive");
+        self.push(key, Counted { value, count: n });
+    }
+
+    /// Removes one copy of the greatest element and returns it, or `None` if
+    /// empty. The entry is removed once its last copy is popped.
+    ///
+    /// This will set the top key to the extracted key.
+    pub fn pop_one(&mut self) -> Option<(K, V)> {
+        if self.buckets[0].is_empty() {
+            self.constrain();
+        }
+
+        let (key, counted) = self.buckets[0].last_mut()?;
+
+        if counted.count > 1 {
+            counted.count -= 1;
+            self.stats.record_pop();
+            self.hooks.pop(key);
+            return Some((*key, counted.value.clone()));
+        }
+
+        self.pop().map(|(key, counted)| (key, counted.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RadixHeapMap;
+
+    #[test]
+    fn pop_one() {
+        let mut heap = RadixHeapMap::new();
+        heap.push_count(10u8, "a", 2);
+        heap.push_count(10, "b", 1);
+        heap.push_count(4, "c", 3);
+
+        let mut popped = Vec::new();
+        while let Some((key, value)) = heap.pop_one() {
+            popped.push((key, value));
+
+            if popped.len() == 2 {
+                heap.push_count(7, "d", 2);
+            }
+        }
+
+        assert_eq!(
+            popped,
+            vec![
+                (10, "b"),
+                (10, "a"),
+                (10, "a"),
+                (7, "d"),
+                (7, "d"),
+                (4, "c"),
+                (4, "c"),
+                (4, "c")
+            ]
+        );
+        assert_eq!(heap.top(), Some(4));
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn pop_one_stats() {
+        let mut heap = RadixHeapMap::new();
+        heap.push_count(10u8, "a", 3);
+
+        assert_eq!(heap.pop_one(), Some((10, "a")));
+        assert_eq!(heap.pop_one(), Some((10, "a")));
+        assert_eq!(heap.stats().pops(), 2);
+        assert_eq!(heap.pop_one(), Some((10, "a")));
+        assert_eq!(heap.stats().pops(), 3);
+    }
+}
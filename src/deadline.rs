@@ -0,0 +1,228 @@
+use crate::{Radix, RadixHeapMap};
+use core::{cmp::Reverse, fmt, iter::FusedIterator};
+
+/// A queue of items that expire at integer deadlines.
+///
+/// Time is advanced using [`advance_to`](DeadlineQueue::advance_to), which
+/// yields every item whose deadline has passed. The last time advanced to is
+/// the watermark, and items with deadlines before the watermark are rejected
+/// since they would have expired already.
+///
+/// ```
+/// let mut queue = radix_heap::DeadlineQueue::new();
+///
+/// queue.insert(10u64, "a").unwrap();
+/// queue.insert(30, "b").unwrap();
+/// queue.insert(20, "c").unwrap();
+///
+/// let expired: Vec<_> = queue.advance_to(25).collect();
+/// assert_eq!(expired, vec![(10, "a"), (20, "c")]);
+///
+/// assert!(queue.insert(15, "d").is_err());
+/// assert_eq!(queue.watermark(), Some(25));
+/// ```
+#[derive(Clone)]
+pub struct DeadlineQueue<D, T> {
+    heap: RadixHeapMap<Reverse<D>, T>,
+    watermark: Option<D>,
+}
+
+impl<D: Radix + Ord + Copy, T> DeadlineQueue<D, T> {
+    /// Create an empty `DeadlineQueue` without a watermark.
+    pub fn new() -> DeadlineQueue<D, T> {
+        DeadlineQueue {
+            heap: RadixHeapMap::new(),
+            watermark: None,
+        }
+    }
+
+    /// Create an empty `DeadlineQueue` with the watermark set to `now`.
+    pub fn new_at(now: D) -> DeadlineQueue<D, T> {
+        DeadlineQueue {
+            heap: RadixHeapMap::new_at(Reverse(now)),
+            watermark: Some(now),
+        }
+    }
+
+    /// Inserts an item that expires at `deadline`.
+    ///
+    /// Returns an error containing the item if `deadline` is before the
+    /// watermark.
+    pub fn insert(&mut self, deadline: D, item: T) -> Result<(), PastDeadline<D, T>> {
+        match self.watermark {
+            Some(watermark) if deadline < watermark => Err(PastDeadline { deadline, item }),
+            _ => {
+                // Advancing moves the top key to the earliest deadline, which
+                // may be later than this one.
+                self.heap.raise_top(Reverse(deadline));
+                self.heap.push(Reverse(deadline), item);
+                Ok(())
+            }
+        }
+    }
+
+    /// Advances the watermark to `now` and returns an iterator of all items
+    /// with a deadline at or before `now`, in order of increasing deadline.
+    ///
+    /// The watermark never moves backwards, so advancing to a time before the
+    /// watermark only yields the items at or before that time. Items not
+    /// consumed by the iterator stay in the queue.
+    pub fn advance_to(&mut self, now: D) -> AdvanceTo<'_, D, T> {
+        if !matches!(self.watermark, Some(watermark) if watermark >= now) {
+            self.watermark = Some(now);
+        }
+
+        AdvanceTo {
+            heap: &mut self.heap,
+            now,
+        }
+    }
+
+    /// The earliest deadline of any item, or `None` if empty.
+    pub fn next_deadline(&mut self) -> Option<D> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        self.heap.constrain();
+        self.heap.top().map(|Reverse(deadline)| deadline)
+    }
+
+    /// The time last advanced to, or `None` if the queue has not been
+    /// advanced yet.
+    #[inline]
+    pub fn watermark(&self) -> Option<D> {
+        self.watermark
+    }
+
+    /// Returns the number of items in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if there are no items in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Drops all items from the queue. The watermark is kept.
+    pub fn clear(&mut self) {
+        match self.watermark {
+            Some(watermark) => self.heap.clear_to(Reverse(watermark)),
+            None => self.heap.clear(),
+        }
+    }
+}
+
+impl<D: Radix + Ord + Copy, T> Default for DeadlineQueue<D, T> {
+    fn default() -> DeadlineQueue<D, T> {
+        DeadlineQueue::new()
+    }
+}
+
+/// An iterator over the expired items of a [`DeadlineQueue`].
+///
+/// This `struct` is created by [`DeadlineQueue::advance_to`].
+pub struct AdvanceTo<'a, D, T> {
+    heap: &'a mut RadixHeapMap<Reverse<D>, T>,
+    now: D,
+}
+
+impl<'a, D: Radix + Ord + Copy, T> Iterator for AdvanceTo<'a, D, T> {
+    type Item = (D, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        self.heap.constrain();
+
+        match self.heap.top() {
+            Some(Reverse(deadline)) if deadline <= self.now => self
+                .heap
+                .pop()
+                .map(|(Reverse(deadline), item)| (deadline, item)),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.heap.len()))
+    }
+}
+
+impl<'a, D: Radix + Ord + Copy, T> FusedIterator for AdvanceTo<'a, D, T> {}
+
+/// The error returned when inserting an item into a [`DeadlineQueue`] with a
+/// deadline before the watermark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PastDeadline<D, T> {
+    /// The deadline of the rejected item.
+    pub deadline: D,
+
+    /// The rejected item.
+    pub item: T,
+}
+
+impl<D: fmt::Debug, T> fmt::Display for PastDeadline<D, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "deadline {:?} is before the watermark", self.deadline)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D: fmt::Debug, T: fmt::Debug> std::error::Error for PastDeadline<D, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::DeadlineQueue;
+
+    #[test]
+    fn advance() {
+        let mut queue = DeadlineQueue::new_at(5u32);
+        assert!(queue.insert(4, 'a').is_err());
+
+        queue.insert(5, 'b').unwrap();
+        queue.insert(9, 'c').unwrap();
+        queue.insert(7, 'd').unwrap();
+
+        assert_eq!(queue.advance_to(5).collect::<Vec<_>>(), vec![(5, 'b')]);
+        assert_eq!(queue.next_deadline(), Some(7));
+
+        // Only the first expired item is consumed.
+        assert_eq!(queue.advance_to(10).next(), Some((7, 'd')));
+        assert_eq!(queue.watermark(), Some(10));
+
+        // Going back in time keeps the watermark.
+        assert_eq!(queue.advance_to(8).next(), None);
+        assert_eq!(queue.watermark(), Some(10));
+        assert!(queue.insert(9, 'e').is_err());
+
+        queue.insert(10, 'f').unwrap();
+        assert_eq!(
+            queue.advance_to(10).collect::<Vec<_>>(),
+            vec![(9, 'c'), (10, 'f')]
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn insert_before_next_deadline() {
+        let mut queue = DeadlineQueue::new();
+        queue.insert(5u8, 'a').unwrap();
+        queue.insert(40, 'b').unwrap();
+
+        assert_eq!(queue.advance_to(10).collect::<Vec<_>>(), vec![(5, 'a')]);
+        assert_eq!(queue.next_deadline(), Some(40));
+
+        queue.insert(20, 'c').unwrap();
+        assert_eq!(queue.next_deadline(), Some(20));
+        assert_eq!(
+            queue.advance_to(40).collect::<Vec<_>>(),
+            vec![(20, 'c'), (40, 'b')]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{Radix, RadixHeapMap};
+use std::{collections::HashMap, fmt, hash::Hash};
+
+/// A radix heap holding each value at most once, with the greatest key it was
+/// pushed with, for lazy-deletion searches such as Dijkstra's algorithm.
+///
+/// Pushing a value that is already in the heap with an equal or greater key
+/// does nothing. Pushing it with a greater key supersedes the entry already in
+/// the heap, which stays behind as a tombstone that
+/// [`pop`](DedupRadixHeapMap::pop) skips. A map from each value to its best
+/// key and the stamp of its live entry decides which entries are live, so
+/// values are stored twice and must be `Clone`, `Eq` and `Hash`.
+///
+/// Unlike [`RadixIndexHeapMap`](crate::RadixIndexHeapMap), which moves an
+/// entry when its key changes, this needs no handles, at the cost of keeping
+/// tombstones until they are popped or
+/// [removed](DedupRadixHeapMap::remove_tombstones).
+///
+/// ```
+/// use radix_heap::DedupRadixHeapMap;
+/// use std::cmp::Reverse;
+///
+/// let mut heap = DedupRadixHeapMap::new();
+/// assert!(heap.push(Reverse(5u32), 'a'));
+/// assert!(heap.push(Reverse(3), 'a'));
+/// assert!(!heap.push(Reverse(4), 'a'));
+/// assert!(heap.push(Reverse(4), 'b'));
+///
+/// assert_eq!((heap.len(), heap.tombstones()), (2, 1));
+/// assert_eq!(heap.pop(), Some((Reverse(3), 'a')));
+/// assert_eq!(heap.pop(), Some((Reverse(4), 'b')));
+/// assert_eq!(heap.pop(), None);
+/// ```
+#[derive(Clone)]
+pub struct DedupRadixHeapMap<K, V> {
+    heap: RadixHeapMap<K, (V, u64)>,
+
+    /// The key and stamp of the live entry of each value in the heap. A
+    /// tombstone may have the same key as a later live entry, but never the
+    /// same stamp.
+    best: HashMap<V, (K, u64)>,
+
+    /// The stamp of the next entry pushed.
+    stamp: u64,
+}
+
+impl<K, V> DedupRadixHeapMap<K, V>
+where
+    K: Radix + Ord + Clone,
+    V: Eq + Hash + Clone,
+{
+    /// Create an empty `DedupRadixHeapMap`.
+    pub fn new() -> DedupRadixHeapMap<K, V> {
+        DedupRadixHeapMap {
+            heap: RadixHeapMap::new(),
+            best: HashMap::new(),
+            stamp: 0,
+        }
+    }
+
+    /// Create an empty `DedupRadixHeapMap` with the top key set to a specific
+    /// value.
+    pub fn new_at(top: K) -> DedupRadixHeapMap<K, V> {
+        DedupRadixHeapMap {
+            heap: RadixHeapMap::new_at(top),
+            best: HashMap::new(),
+            stamp: 0,
+        }
+    }
+
+    /// Pushes a key value pair onto the heap, unless the value is already in
+    /// the heap with an equal or greater key. Returns whether it was pushed.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    pub fn push(&mut self, key: K, value: V) -> bool {
+        if matches!(self.best.get(&value), Some((best, _)) if *best >= key) {
+            return false;
+        }
+
+        // Pushed before `best` is updated, so a key above the top key panics
+        // without leaving a live entry that is not in the heap.
+        let stamp = self.stamp;
+        self.heap.push(key.clone(), (value.clone(), stamp));
+        self.stamp += 1;
+        self.best.insert(value, (key, stamp));
+        true
+    }
+
+    /// Removes the greatest live element from the heap and returns it, or
+    /// `None` if empty. Tombstones above it are dropped on the way.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        loop {
+            let (key, (value, stamp)) = self.heap.pop()?;
+
+            if is_live(&self.best, &value, stamp) {
+                self.best.remove(&value);
+                return Some((key, value));
+            }
+        }
+    }
+
+    /// Returns the greatest key of a live element without removing it, or
+    /// `None` if empty. Tombstones above it are dropped.
+    pub fn peek_key(&mut self) -> Option<K> {
+        loop {
+            let (key, (value, stamp)) = self.heap.peek()?;
+
+            if is_live(&self.best, value, *stamp) {
+                return Some(key.clone());
+            }
+
+            self.heap.pop();
+        }
+    }
+
+    /// The key `value` is in the heap with, or `None` if it is not in the
+    /// heap.
+    #[inline]
+    pub fn get(&self, value: &V) -> Option<&K> {
+        self.best.get(value).map(|(key, _)| key)
+    }
+
+    /// Removes `value` from the heap, leaving its entry as a tombstone, and
+    /// returns its key, or `None` if it was not in the heap.
+    #[inline]
+    pub fn remove(&mut self, value: &V) -> Option<K> {
+        self.best.remove(value).map(|(key, _)| key)
+    }
+
+    /// The current top key. All keys pushed onto the heap must be smaller
+    /// than this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.heap.top()
+    }
+
+    /// Returns the number of live elements in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.best.len()
+    }
+
+    /// Returns true if there are no live elements in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.best.is_empty()
+    }
+
+    /// Returns the number of superseded or removed entries still in the heap.
+    #[inline]
+    pub fn tombstones(&self) -> usize {
+        self.heap.len() - self.best.len()
+    }
+
+    /// Drops all tombstones from the heap.
+    pub fn remove_tombstones(&mut self) {
+        let best = &self.best;
+        self.heap
+            .retain(|_, (value, stamp)| is_live(best, value, *stamp));
+    }
+
+    /// Drops all items from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.best.clear();
+    }
+}
+
+/// Whether the entry of `value` stamped `stamp` is its live entry.
+#[inline]
+fn is_live<K, V: Eq + Hash>(best: &HashMap<V, (K, u64)>, value: &V, stamp: u64) -> bool {
+    matches!(best.get(value), Some(&(_, live)) if live == stamp)
+}
+
+impl<K, V> Default for DedupRadixHeapMap<K, V>
+where
+    K: Radix + Ord + Clone,
+    V: Eq + Hash + Clone,
+{
+    fn default() -> DedupRadixHeapMap<K, V> {
+        DedupRadixHeapMap::new()
+    }
+}
+
+impl<K, V> Extend<(K, V)> for DedupRadixHeapMap<K, V>
+where
+    K: Radix + Ord + Clone,
+    V: Eq + Hash + Clone,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.push(key, value);
+        }
+    }
+}
+
+/// Lists the live elements.
+impl<K: fmt::Debug, V: fmt::Debug + Eq + Hash> fmt::Debug for DedupRadixHeapMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.best.iter().map(|(value, (key, _))| (key, value)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::DedupRadixHeapMap;
+    use std::{
+        collections::HashMap,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    #[test]
+    fn pops_best_keys() {
+        fn prop(ops: Vec<Option<(u8, u8)>>) -> bool {
+            let mut heap = DedupRadixHeapMap::new();
+            let mut best = HashMap::new();
+
+            for op in ops {
+                match op {
+                    Some((step, value)) => {
+                        let key = heap.top().unwrap_or(u16::MAX).saturating_sub(step as u16);
+                        let better = !matches!(best.get(&(value % 8)), Some(&k) if key <= k);
+                        if better {
+                            best.insert(value % 8, key);
+                        }
+
+                        if heap.push(key, value % 8) != better {
+                            return false;
+                        }
+                    }
+                    None => {
+                        let max = best.values().max().copied();
+                        let popped = heap.pop();
+                        let expected = popped.and_then(|(_, value)| best.remove(&value));
+
+                        if popped.map(|(key, _)| key) != max || expected != max {
+                            return false;
+                        }
+                    }
+                }
+
+                if heap.len() != best.len() {
+                    return false;
+                }
+            }
+
+            heap.remove_tombstones();
+            heap.tombstones() == 0 && heap.peek_key() == best.values().max().copied()
+        }
+
+        quickcheck(prop as fn(Vec<Option<(u8, u8)>>) -> bool);
+    }
+
+    #[test]
+    fn remove_leaves_tombstone() {
+        let mut heap = DedupRadixHeapMap::new();
+        heap.extend(vec![(3u32, 'a'), (5, 'b')]);
+
+        assert_eq!(heap.remove(&'b'), Some(5));
+        assert_eq!(heap.get(&'a'), Some(&3));
+        assert_eq!((heap.len(), heap.tombstones()), (1, 1));
+        assert_eq!(heap.pop(), Some((3, 'a')));
+        assert_eq!(heap.tombstones(), 0);
+    }
+
+    #[test]
+    fn repush_after_pop() {
+        let mut heap = DedupRadixHeapMap::new();
+        heap.extend(vec![(3u32, 'a'), (5, 'a')]);
+
+        assert_eq!(heap.pop(), Some((5, 'a')));
+        assert!(heap.push(3, 'a'));
+        assert_eq!((heap.len(), heap.tombstones()), (1, 1));
+
+        heap.remove_tombstones();
+        assert_eq!((heap.len(), heap.tombstones()), (1, 0));
+        assert_eq!(heap.pop(), Some((3, 'a')));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn push_above_top() {
+        let mut heap = DedupRadixHeapMap::new_at(5u32);
+        heap.push(3, 'a');
+
+        let pushed = panic::catch_unwind(AssertUnwindSafe(|| heap.push(7, 'a')));
+        assert!(pushed.is_err());
+        assert_eq!(heap.get(&'a'), Some(&3));
+        assert_eq!(heap.pop(), Some((3, 'a')));
+    }
+}
@@ -0,0 +1,106 @@
+/// The minimum number of items before warnings are emitted, so small heaps
+/// do not produce noise.
+#[cfg(feature = "log")]
+const MIN_ITEMS: u64 = 1024;
+
+/// The fraction of pushes landing at the top key above which a warning is
+/// emitted.
+#[cfg(feature = "log")]
+const TOP_PUSH_FRACTION: f64 = 0.9;
+
+/// The default fraction of the heap moved by a constrain above which a
+/// warning is emitted.
+#[cfg(feature = "log")]
+const CONSTRAIN_FRACTION: f64 = 0.5;
+
+/// Tracks signs that the key encoding is poorly matched to the workload and
+/// logs a warning the first time each is seen. This is empty unless the `log`
+/// feature is enabled.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Diagnostics {
+    #[cfg(feature = "log")]
+    pushes: u64,
+    #[cfg(feature = "log")]
+    top_pushes: u64,
+    #[cfg(feature = "log")]
+    warned_top_pushes: bool,
+    #[cfg(feature = "log")]
+    pub(crate) constrain_fraction: Option<f64>,
+    #[cfg(feature = "log")]
+    warned_constrain: bool,
+}
+
+impl Diagnostics {
+    /// Records a push at the given radix distance from the top key.
+    #[inline]
+    pub(crate) fn record_push(&mut self, _distance: u32) {
+        #[cfg(feature = "log")]
+        {
+            self.pushes += 1;
+
+            if _distance == 0 {
+                self.top_pushes += 1;
+            }
+
+            if !self.warned_top_pushes
+                && self.pushes >= MIN_ITEMS
+                && self.top_pushes as f64 > TOP_PUSH_FRACTION * self.pushes as f64
+            {
+                self.warned_top_pushes = true;
+                log::warn!(
+                    "{} of {} pushes onto a RadixHeapMap were equal to the top key; \
+                     consider a key encoding with more distinct keys",
+                    self.top_pushes,
+                    self.pushes
+                );
+            }
+        }
+    }
+
+    /// Records a constrain moving `_moved` of the `_len` items in the heap.
+    #[inline]
+    pub(crate) fn record_constrain(&mut self, _moved: usize, _len: usize) {
+        #[cfg(feature = "log")]
+        {
+            if !self.warned_constrain
+                && _len as u64 >= MIN_ITEMS
+                && _moved as f64
+                    > self.constrain_fraction.unwrap_or(CONSTRAIN_FRACTION) * _len as f64
+            {
+                self.warned_constrain = true;
+                log::warn!(
+                    "constraining a RadixHeapMap moved {} of {} items between buckets; \
+                     consider a key encoding that spreads keys over more buckets",
+                    _moved,
+                    _len
+                );
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+    use crate::RadixHeapMap;
+
+    #[test]
+    fn warnings() {
+        let mut heap = RadixHeapMap::new_at(0u32);
+        heap.extend((0..2000).map(|_| (0, ())));
+        assert!(heap.diagnostics.warned_top_pushes);
+        assert!(!heap.diagnostics.warned_constrain);
+
+        let mut heap = RadixHeapMap::new_at(u32::MAX);
+        heap.extend((0..2000).map(|k| (k, ())));
+        heap.set_constrain_warning(1.0);
+        heap.pop();
+        assert!(!heap.diagnostics.warned_top_pushes);
+        assert!(!heap.diagnostics.warned_constrain);
+
+        heap.set_constrain_warning(0.5);
+        heap.clear_to(u32::MAX);
+        heap.extend((0..2000).map(|k| (k, ())));
+        heap.pop();
+        assert!(heap.diagnostics.warned_constrain);
+    }
+}
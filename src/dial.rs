@@ -0,0 +1,337 @@
+use alloc::vec::Vec;
+use core::{cmp::Reverse, fmt};
+
+/// A key that maps to a position on a line of buckets, for use with
+/// [`BucketQueue`].
+///
+/// Positions must be ordered like the keys, and equal only for equal keys.
+pub trait BucketKey: Ord + Copy {
+    /// The position of the key.
+    fn position(&self) -> u64;
+}
+
+macro_rules! bucket_key_unsigned_impl {
+    ($t:ty) => {
+        impl BucketKey for $t {
+            #[inline]
+            fn position(&self) -> u64 {
+                *self as u64
+            }
+        }
+    };
+}
+
+macro_rules! bucket_key_signed_impl {
+    ($t:ty) => {
+        impl BucketKey for $t {
+            #[inline]
+            fn position(&self) -> u64 {
+                // Flipping the sign bit orders negative keys before positive
+                // ones.
+                (*self as i64 as u64) ^ (1 << 63)
+            }
+        }
+    };
+}
+
+bucket_key_unsigned_impl!(u8);
+bucket_key_unsigned_impl!(u16);
+bucket_key_unsigned_impl!(u32);
+bucket_key_unsigned_impl!(u64);
+bucket_key_unsigned_impl!(usize);
+
+bucket_key_signed_impl!(i8);
+bucket_key_signed_impl!(i16);
+bucket_key_signed_impl!(i32);
+bucket_key_signed_impl!(i64);
+bucket_key_signed_impl!(isize);
+
+impl<T: BucketKey> BucketKey for Reverse<T> {
+    #[inline]
+    fn position(&self) -> u64 {
+        !self.0.position()
+    }
+}
+
+/// A monotone priority queue with one bucket per key, as used by Dial's
+/// algorithm.
+///
+/// Every key in the queue must be within `max_spread` of the top key, so the
+/// buckets form a ring of at least `max_spread + 1` buckets, each holding the
+/// entries of a single key. The ring is rounded up to a power of two, so that
+/// finding the bucket of a key is a mask rather than a division. Pushes are a
+/// single `Vec` push, and pops step down the ring to the next non-empty
+/// bucket. For Dijkstra's algorithm with edge costs of at most `c`, a spread
+/// of `c` suffices.
+///
+/// This beats a [`RadixHeapMap`](crate::RadixHeapMap) for small, bounded
+/// spreads, but the number of buckets, and the steps of a pop, grow with the
+/// spread.
+///
+/// ```
+/// use radix_heap::BucketQueue;
+/// use std::cmp::Reverse;
+///
+/// let mut queue = BucketQueue::new(3);
+/// queue.push(Reverse(0u32), 'a');
+/// assert_eq!(queue.pop(), Some((Reverse(0), 'a')));
+///
+/// queue.push(Reverse(3), 'b');
+/// queue.push(Reverse(1), 'c');
+/// assert_eq!(queue.pop(), Some((Reverse(1), 'c')));
+/// assert_eq!(queue.pop(), Some((Reverse(3), 'b')));
+/// ```
+#[derive(Clone)]
+pub struct BucketQueue<K, V> {
+    len: usize,
+
+    /// The current top key, or none if one is not set yet.
+    top: Option<K>,
+
+    /// The largest distance between the top key and a key in the queue.
+    max_spread: usize,
+
+    /// The entries of each key, at its position modulo the number of buckets.
+    buckets: Vec<Vec<(K, V)>>,
+
+    /// The initial entries before a top key is found.
+    initial: Vec<(K, V)>,
+}
+
+impl<K: BucketKey, V> BucketQueue<K, V> {
+    /// Create an empty `BucketQueue` for keys at most `max_spread` below the
+    /// top key.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if there is no power of two above `max_spread`.
+    pub fn new(max_spread: usize) -> BucketQueue<K, V> {
+        let buckets = max_spread
+            .checked_add(1)
+            .and_then(usize::checked_next_power_of_two)
+            .expect("Spread is too large");
+
+        BucketQueue {
+            len: 0,
+            top: None,
+            max_spread,
+            buckets: (0..buckets).map(|_| Vec::new()).collect(),
+            initial: Vec::new(),
+        }
+    }
+
+    /// Create an empty `BucketQueue` with the top key set to a specific
+    /// value.
+    pub fn new_at(max_spread: usize, top: K) -> BucketQueue<K, V> {
+        let mut queue = BucketQueue::new(max_spread);
+        queue.top = Some(top);
+        queue
+    }
+
+    /// The largest distance between the top key and a key in the queue.
+    #[inline]
+    pub fn max_spread(&self) -> usize {
+        self.max_spread
+    }
+
+    /// Drops all items from the queue and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.top = None;
+        self.initial.clear();
+
+        for bucket in &mut self.buckets {
+            bucket.clear();
+        }
+    }
+
+    /// Pushes a new key value pair onto the queue.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key, or more than
+    /// `max_spread` below it.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        match self.top {
+            Some(top) => {
+                assert!(key <= top, "Key must be lower or equal to current top key");
+                self.place(top, (key, value));
+            }
+            None => self.initial.push((key, value)),
+        }
+
+        self.len += 1;
+    }
+
+    /// Remove the greatest element from the queue and returns it, or `None` if
+    /// empty.
+    ///
+    /// If there is a tie between multiple elements, the last inserted element
+    /// will be popped first. This will set the top key to the extracted key.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if this is the first pop and the keys pushed before it are
+    /// spread further apart than `max_spread`.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let top = match self.top {
+            Some(top) => top,
+            None => self.distribute(),
+        };
+
+        let mut slot = self.slot(top);
+        while self.buckets[slot].is_empty() {
+            slot = slot.wrapping_sub(1) & (self.buckets.len() - 1);
+        }
+
+        let entry = self.buckets[slot].pop()?;
+        self.top = Some(entry.0);
+        self.len -= 1;
+        Some(entry)
+    }
+
+    /// The current top key. All keys pushed onto the queue must be smaller
+    /// than this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.top
+    }
+
+    /// Returns the number of elements in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no elements in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    fn slot(&self, key: K) -> usize {
+        key.position() as usize & (self.buckets.len() - 1)
+    }
+
+    /// Puts an entry into the bucket of its key.
+    #[inline]
+    fn place(&mut self, top: K, entry: (K, V)) {
+        assert!(
+            top.position() - entry.0.position() <= self.max_spread() as u64,
+            "Key must be within max_spread of the top key"
+        );
+
+        let slot = self.slot(entry.0);
+        self.buckets[slot].push(entry);
+    }
+
+    /// Sets the top key to the greatest initial key, and moves the initial
+    /// entries into their buckets.
+    fn distribute(&mut self) -> K {
+        let initial = core::mem::take(&mut self.initial);
+        let top = initial
+            .iter()
+            .map(|&(key, _)| key)
+            .max()
+            .expect("Non-empty queue without top key has initial entries");
+
+        for entry in initial {
+            self.place(top, entry);
+        }
+
+        self.top = Some(top);
+        top
+    }
+}
+
+impl<K: BucketKey, V> Extend<(K, V)> for BucketQueue<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.push(key, value);
+        }
+    }
+}
+
+impl<K: BucketKey + fmt::Debug, V: fmt::Debug> fmt::Debug for BucketQueue<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.initial.iter().chain(self.buckets.iter().flatten()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::{BucketKey, BucketQueue};
+    use crate::RadixHeapMap;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn position_order() {
+        fn prop(a: i32, b: i32) -> bool {
+            a.cmp(&b) == a.position().cmp(&b.position())
+                && Reverse(a).cmp(&Reverse(b)) == Reverse(a).position().cmp(&Reverse(b).position())
+                && (a as u16).cmp(&(b as u16)) == (a as u16).position().cmp(&(b as u16).position())
+        }
+
+        quickcheck(prop as fn(i32, i32) -> bool);
+    }
+
+    #[test]
+    fn matches_radix_heap() {
+        fn prop(initial: Vec<u8>, ops: Vec<Option<u8>>, spread: u8) -> bool {
+            let spread = spread as usize % 16;
+            let mut queue = BucketQueue::new(spread);
+            let mut heap = RadixHeapMap::new();
+
+            let base = initial.iter().max().copied().unwrap_or(0) as i32;
+            for (i, &step) in initial.iter().enumerate() {
+                let key = base - (step as usize % (spread + 1)) as i32;
+                queue.push(key, i);
+                heap.push(key, i);
+            }
+
+            for (i, op) in ops.into_iter().enumerate() {
+                match (op, heap.top()) {
+                    (Some(step), Some(top)) => {
+                        let key = top - (step as usize % (spread + 1)) as i32;
+                        queue.push(key, i);
+                        heap.push(key, i);
+                    }
+                    _ => {
+                        if queue.pop().map(|(k, _)| k) != heap.pop().map(|(k, _)| k) {
+                            return false;
+                        }
+                    }
+                }
+
+                if queue.len() != heap.len() || queue.top() != heap.top() {
+                    return false;
+                }
+            }
+
+            std::iter::from_fn(|| queue.pop())
+                .map(|(k, _)| k)
+                .eq(std::iter::from_fn(|| heap.pop()).map(|(k, _)| k))
+        }
+
+        quickcheck(prop as fn(Vec<u8>, Vec<Option<u8>>, u8) -> bool);
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_beyond_spread() {
+        let mut queue = BucketQueue::new_at(4, 10u32);
+        queue.push(5, ());
+    }
+}
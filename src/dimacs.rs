@@ -0,0 +1,336 @@
+//! Graphs in the DIMACS shortest path format.
+//!
+//! The road networks of the
+//! [9th DIMACS Implementation Challenge](http://www.diag.uniroma1.it/challenge9/download.shtml)
+//! are the standard benchmark for shortest path algorithms. A
+//! [`DimacsGraph`] is parsed from a `.gr` file, optionally along with node
+//! coordinates from a `.co` file, and can be passed directly to the
+//! [`algorithms`](crate::algorithms).
+//!
+//! Nodes keep the ids they have in the file, which start at 1.
+//!
+//! ```
+//! use radix_heap::{algorithms::dijkstra, dimacs::DimacsGraph};
+//!
+//! let graph: DimacsGraph = "c a small graph\n\
+//!                           p sp 3 3\n\
+//!                           a 1 2 7\n\
+//!                           a 1 3 2\n\
+//!                           a 3 2 4\n"
+//!     .parse()
+//!     .unwrap();
+//!
+//! let costs = dijkstra(1, &graph);
+//! assert_eq!(costs[&2], 6);
+//! ```
+
+use crate::algorithms::Graph;
+use std::{error::Error, fmt, iter::Copied, slice, str::FromStr};
+
+/// A directed graph with `u32` arc weights, in compressed adjacency form.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DimacsGraph {
+    nodes: u32,
+
+    /// The arcs of node `n` are `arcs[offsets[n]..offsets[n + 1]]`. Nodes past
+    /// the end, up to `nodes`, have no arcs.
+    offsets: Vec<usize>,
+    arcs: Vec<(u32, u32)>,
+    coordinates: Option<Vec<(i32, i32)>>,
+}
+
+impl DimacsGraph {
+    /// Parses a graph in the DIMACS `.gr` format.
+    ///
+    /// The node and arc counts of the problem line are not trusted for
+    /// allocating. Memory is allocated for the arcs in `text` and up to the
+    /// largest node with arcs, and failing to allocate it is an error.
+    pub fn parse(text: &str) -> Result<DimacsGraph, ParseDimacsError> {
+        let mut nodes = None;
+        let mut arcs = Vec::new();
+
+        for (number, line) in lines(text) {
+            let error = |reason| ParseDimacsError {
+                line: number,
+                reason,
+            };
+            let mut words = line.split_whitespace();
+
+            match words.next() {
+                Some("p") if nodes.is_none() => {
+                    if words.next() != Some("sp") {
+                        return Err(error("expected a shortest path problem"));
+                    }
+
+                    let n: u32 = parse(words.next(), error("invalid node count"))?;
+                    let _: usize = parse(words.next(), error("invalid arc count"))?;
+                    nodes = Some(n);
+                }
+                Some("a") => {
+                    let n = nodes.ok_or_else(|| error("arc before problem line"))?;
+                    let node = |word| match parse(word, error("invalid node")) {
+                        Ok(node) if node >= 1 && node <= n => Ok(node),
+                        _ => Err(error("invalid node")),
+                    };
+
+                    let from = node(words.next())?;
+                    let to = node(words.next())?;
+                    let weight = parse(words.next(), error("invalid weight"))?;
+                    arcs.push((from, to, weight));
+                }
+                _ => return Err(error("unexpected line")),
+            }
+        }
+
+        let nodes = nodes.ok_or(ParseDimacsError {
+            line: 0,
+            reason: "missing problem line",
+        })?;
+
+        let last = arcs.iter().map(|&(from, _, _)| from).max().unwrap_or(0);
+        let mut offsets = filled(
+            (last as usize).saturating_add(2),
+            0,
+            ParseDimacsError {
+                line: 0,
+                reason: "too many nodes",
+            },
+        )?;
+        for &(from, _, _) in &arcs {
+            offsets[from as usize + 1] += 1;
+        }
+        for n in 1..offsets.len() {
+            offsets[n] += offsets[n - 1];
+        }
+
+        let mut next = offsets.clone();
+        let mut sorted = vec![(0, 0); arcs.len()];
+        for (from, to, weight) in arcs {
+            sorted[next[from as usize]] = (to, weight);
+            next[from as usize] += 1;
+        }
+
+        Ok(DimacsGraph {
+            nodes,
+            offsets,
+            arcs: sorted,
+            coordinates: None,
+        })
+    }
+
+    /// Parses node coordinates in the DIMACS `.co` format and attaches them
+    /// to the graph. Nodes missing from the file get the coordinates
+    /// `(0, 0)`.
+    pub fn parse_coordinates(&mut self, text: &str) -> Result<(), ParseDimacsError> {
+        let mut coordinates = Vec::new();
+
+        for (number, line) in lines(text) {
+            let error = |reason| ParseDimacsError {
+                line: number,
+                reason,
+            };
+            let mut words = line.split_whitespace();
+
+            match words.next() {
+                Some("p") => {}
+                Some("v") => {
+                    let node: usize = parse(words.next(), error("invalid node"))?;
+                    if node == 0 || node > self.node_count() as usize {
+                        return Err(error("invalid node"));
+                    }
+
+                    let x = parse(words.next(), error("invalid coordinate"))?;
+                    let y = parse(words.next(), error("invalid coordinate"))?;
+                    if coordinates.len() <= node {
+                        coordinates
+                            .try_reserve(node + 1 - coordinates.len())
+                            .map_err(|_| error("too many nodes"))?;
+                        coordinates.resize(node + 1, (0, 0));
+                    }
+                    coordinates[node] = (x, y);
+                }
+                _ => return Err(error("unexpected line")),
+            }
+        }
+
+        self.coordinates = Some(coordinates);
+        Ok(())
+    }
+
+    /// The number of nodes. Nodes are numbered from 1 to this number.
+    #[inline]
+    pub fn node_count(&self) -> u32 {
+        self.nodes
+    }
+
+    /// The number of arcs.
+    #[inline]
+    pub fn arc_count(&self) -> usize {
+        self.arcs.len()
+    }
+
+    /// The successors of `node` along with the weights of the arcs leading to
+    /// them.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `node` is not in the graph.
+    pub fn arcs(&self, node: u32) -> &[(u32, u32)] {
+        assert!(node >= 1 && node <= self.nodes, "Node must be in the graph");
+
+        let node = node as usize;
+        match self.offsets.get(node + 1) {
+            Some(&end) => &self.arcs[self.offsets[node]..end],
+            None => &[],
+        }
+    }
+
+    /// The coordinates of `node`, or `None` if no coordinates were parsed.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if coordinates were parsed and `node` is not in the graph.
+    pub fn coordinates(&self, node: u32) -> Option<(i32, i32)> {
+        let coordinates = self.coordinates.as_ref()?;
+        assert!(node >= 1 && node <= self.nodes, "Node must be in the graph");
+
+        Some(coordinates.get(node as usize).copied().unwrap_or((0, 0)))
+    }
+}
+
+impl FromStr for DimacsGraph {
+    type Err = ParseDimacsError;
+
+    fn from_str(text: &str) -> Result<DimacsGraph, ParseDimacsError> {
+        DimacsGraph::parse(text)
+    }
+}
+
+impl<'a> Graph<u32> for &'a DimacsGraph {
+    type Weight = u32;
+    type Edges = Copied<slice::Iter<'a, (u32, u32)>>;
+
+    #[inline]
+    fn successors(&mut self, node: u32) -> Self::Edges {
+        self.arcs(node).iter().copied()
+    }
+}
+
+/// The numbered lines of `text` that are not blank or comments.
+fn lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('c'))
+}
+
+/// A vector of `len` copies of `value`, or `error` if it cannot be allocated.
+fn filled<T: Clone>(
+    len: usize,
+    value: T,
+    error: ParseDimacsError,
+) -> Result<Vec<T>, ParseDimacsError> {
+    let mut vec = Vec::new();
+    vec.try_reserve_exact(len).map_err(|_| error)?;
+    vec.resize(len, value);
+    Ok(vec)
+}
+
+fn parse<T: FromStr>(word: Option<&str>, error: ParseDimacsError) -> Result<T, ParseDimacsError> {
+    word.and_then(|word| word.parse().ok()).ok_or(error)
+}
+
+/// An error returned when parsing a DIMACS file fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseDimacsError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl ParseDimacsError {
+    /// The line the error occurred at, starting at 1, or 0 if the error is
+    /// not at a specific line.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ParseDimacsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid DIMACS file at line {}: {}",
+            self.line, self.reason
+        )
+    }
+}
+
+impl Error for ParseDimacsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::DimacsGraph;
+    use crate::algorithms::{astar, dijkstra};
+
+    const GRAPH: &str = include_str!("../benches/grid.gr");
+    const COORDINATES: &str = include_str!("../benches/grid.co");
+
+    #[test]
+    fn parse() {
+        let mut graph: DimacsGraph = GRAPH.parse().unwrap();
+        assert_eq!(graph.node_count(), 1024);
+        assert_eq!(graph.arc_count(), 3968);
+        assert_eq!(graph.coordinates(1), None);
+
+        graph.parse_coordinates(COORDINATES).unwrap();
+        assert_eq!(graph.coordinates(1), Some((0, 0)));
+        assert_eq!(graph.coordinates(1024), Some((31_000, 31_000)));
+    }
+
+    #[test]
+    fn errors() {
+        let error = |text: &str| DimacsGraph::parse(text).unwrap_err().line();
+
+        assert_eq!(error("c nothing\n"), 0);
+        assert_eq!(error("a 1 2 3\n"), 1);
+        assert_eq!(error("p sp 2 1\nc\na 1 3 1\n"), 3);
+        assert_eq!(error("p sp 2 1\na 1 2 -1\n"), 2);
+        assert_eq!(error("p max 2 1\n"), 1);
+    }
+
+    #[test]
+    fn huge_header() {
+        let text = format!("p sp {} {}\na 3 1 5\n", u32::MAX, usize::MAX);
+        let mut graph = DimacsGraph::parse(&text).unwrap();
+        assert_eq!(graph.node_count(), u32::MAX);
+        assert_eq!(graph.arcs(3), &[(1, 5)]);
+        assert_eq!(graph.arcs(u32::MAX), &[]);
+
+        graph.parse_coordinates("v 2 7 8\n").unwrap();
+        assert_eq!(graph.coordinates(2), Some((7, 8)));
+        assert_eq!(graph.coordinates(u32::MAX), Some((0, 0)));
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let mut graph: DimacsGraph = GRAPH.parse().unwrap();
+        graph.parse_coordinates(COORDINATES).unwrap();
+
+        let costs = dijkstra(1, &graph);
+        assert_eq!(costs.len(), 1024);
+
+        // Coordinates are 1000 apart and arcs weigh at least 1000, so the
+        // Manhattan distance is an admissible heuristic.
+        let goal = graph.coordinates(1024).unwrap();
+        let heuristic = |node| {
+            let (x, y) = graph.coordinates(node).unwrap();
+            ((goal.0 - x).abs() + (goal.1 - y).abs()) as u32
+        };
+
+        let (cost, path) = astar(1, 1024, &graph, heuristic).unwrap();
+        assert_eq!(cost, costs[&1024]);
+        assert_eq!(path.first(), Some(&1));
+        assert_eq!(path.last(), Some(&1024));
+    }
+}
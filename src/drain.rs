@@ -0,0 +1,400 @@
+use crate::{Radix, RadixHeapMap};
+use core::iter::FusedIterator;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// When a [`DrainUntil`] iterator stops yielding items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DrainBudget {
+    /// Stop once this instant has been reached.
+    #[cfg(feature = "std")]
+    Deadline(Instant),
+
+    /// Stop after this many items.
+    Polls(usize),
+}
+
+#[cfg(feature = "std")]
+impl From<Instant> for DrainBudget {
+    fn from(deadline: Instant) -> DrainBudget {
+        DrainBudget::Deadline(deadline)
+    }
+}
+
+impl From<usize> for DrainBudget {
+    fn from(polls: usize) -> DrainBudget {
+        DrainBudget::Polls(polls)
+    }
+}
+
+impl<K: Radix + Ord + Clone, V> RadixHeapMap<K, V> {
+    /// Returns an iterator that pops items in order until the heap is empty
+    /// or the budget runs out. The budget is either an [`Instant`] deadline or
+    /// a number of items.
+    ///
+    /// Items not yielded stay in the heap, so the next call continues from
+    /// the next greatest item. This is useful for processing as many items as
+    /// possible within a time slice.
+    ///
+    /// ```
+    /// let mut heap: radix_heap::RadixHeapMap<_, _> = (0..5u32).map(|k| (k, ())).collect();
+    ///
+    /// let first: Vec<_> = heap.drain_until(2).map(|(k, _)| k).collect();
+    /// let rest: Vec<_> = heap.drain_until(10).map(|(k, _)| k).collect();
+    ///
+    /// assert_eq!(first, vec![4, 3]);
+    /// assert_eq!(rest, vec![2, 1, 0]);
+    /// ```
+    pub fn drain_until<B: Into<DrainBudget>>(&mut self, budget: B) -> DrainUntil<'_, K, V> {
+        DrainUntil {
+            heap: self,
+            budget: budget.into(),
+            exhausted: false,
+        }
+    }
+
+    /// Removes all items from the heap and returns them in arbitrary order,
+    /// keeping the allocated capacity of the buckets for reuse.
+    ///
+    /// Items not yielded are dropped when the iterator is dropped. The heap
+    /// is then empty with the top key set to `None`, as after
+    /// [`clear`](RadixHeapMap::clear).
+    ///
+    /// ```
+    /// let mut heap: radix_heap::RadixHeapMap<_, _> = (0..5u32).map(|k| (k, ())).collect();
+    ///
+    /// let mut keys: Vec<_> = heap.drain().map(|(k, _)| k).collect();
+    /// keys.sort_unstable();
+    ///
+    /// assert_eq!(keys, vec![0, 1, 2, 3, 4]);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        Drain {
+            heap: self,
+            bucket: 0,
+        }
+    }
+
+    /// Removes all items from the heap and returns them in order, keeping the
+    /// allocated capacity of the buckets for reuse.
+    ///
+    /// Items not yielded are dropped when the iterator is dropped. The heap
+    /// is then empty with the top key set to `None`, as after
+    /// [`clear`](RadixHeapMap::clear).
+    ///
+    /// ```
+    /// let mut heap: radix_heap::RadixHeapMap<_, _> = (0..5u32).map(|k| (k, ())).collect();
+    ///
+    /// let first: Vec<_> = heap.drain_sorted().take(2).map(|(k, _)| k).collect();
+    ///
+    /// assert_eq!(first, vec![4, 3]);
+    /// assert!(heap.is_empty());
+    /// assert_eq!(heap.top(), None);
+    /// ```
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, K, V> {
+        DrainSorted { heap: self }
+    }
+
+    /// Returns an iterator that pops every item tied for the greatest key,
+    /// last inserted first, like repeated calls to
+    /// [`pop`](RadixHeapMap::pop).
+    ///
+    /// The heap is constrained once, after which the tied items are exactly
+    /// those in bucket 0, so no keys are compared while popping them. Items
+    /// not yielded stay in the heap.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 'a'), (8, 'b'), (5, 'c'), (8, 'd')]);
+    ///
+    /// let ties: Vec<_> = heap.pop_ties().collect();
+    /// assert_eq!(ties, vec![(8, 'd'), (8, 'b')]);
+    /// assert_eq!(heap.pop(), Some((5, 'c')));
+    /// ```
+    pub fn pop_ties(&mut self) -> PopTies<'_, K, V> {
+        if self.buckets[0].is_empty() {
+            self.constrain();
+        }
+
+        PopTies { heap: self }
+    }
+}
+
+/// A draining iterator over a [`RadixHeapMap`] that stops when its budget
+/// runs out.
+///
+/// This `struct` is created by [`RadixHeapMap::drain_until`].
+pub struct DrainUntil<'a, K, V> {
+    heap: &'a mut RadixHeapMap<K, V>,
+    budget: DrainBudget,
+    exhausted: bool,
+}
+
+impl<'a, K, V> DrainUntil<'a, K, V> {
+    /// Returns true if the iterator stopped, or will stop, because the budget
+    /// ran out rather than because the heap is empty.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+            || match self.budget {
+                #[cfg(feature = "std")]
+                DrainBudget::Deadline(deadline) => Instant::now() >= deadline,
+                DrainBudget::Polls(polls) => polls == 0,
+            }
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone, V> Iterator for DrainUntil<'a, K, V> {
+    type Item = (K, V);
+
+    #[cfg_attr(not(feature = "std"), allow(irrefutable_let_patterns))]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        if self.is_exhausted() {
+            self.exhausted = true;
+            return None;
+        }
+
+        if let DrainBudget::Polls(polls) = &mut self.budget {
+            *polls -= 1;
+        }
+
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.budget {
+            _ if self.exhausted => (0, Some(0)),
+            #[cfg(feature = "std")]
+            DrainBudget::Deadline(_) => (0, Some(self.heap.len())),
+            DrainBudget::Polls(polls) => {
+                let len = polls.min(self.heap.len());
+                (len, Some(len))
+            }
+        }
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone, V> FusedIterator for DrainUntil<'a, K, V> {}
+
+/// A draining iterator over the items of a [`RadixHeapMap`] in arbitrary
+/// order.
+///
+/// This `struct` is created by [`RadixHeapMap::drain`].
+pub struct Drain<'a, K: Radix + Ord + Clone, V> {
+    heap: &'a mut RadixHeapMap<K, V>,
+
+    /// The bucket being drained, where 0 is the initial bucket and `i + 1` is
+    /// bucket `i`.
+    bucket: usize,
+}
+
+impl<'a, K: Radix + Ord + Clone, V> Iterator for Drain<'a, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let bucket = match self.bucket {
+                0 => &mut self.heap.initial,
+                i => self.heap.buckets.get_mut(i - 1)?,
+            };
+
+            // Popping keeps the heap consistent even if the iterator is
+            // leaked, and leaves the capacity of the bucket in place.
+            if let Some(item) = bucket.pop() {
+                self.heap.len -= 1;
+                return Some(item);
+            }
+
+            self.bucket += 1;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone, V> ExactSizeIterator for Drain<'a, K, V> {}
+
+impl<'a, K: Radix + Ord + Clone, V> FusedIterator for Drain<'a, K, V> {}
+
+impl<'a, K: Radix + Ord + Clone, V> Drop for Drain<'a, K, V> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+/// A draining iterator over the items of a [`RadixHeapMap`] in order.
+///
+/// This `struct` is created by [`RadixHeapMap::drain_sorted`].
+pub struct DrainSorted<'a, K: Radix + Ord + Clone, V> {
+    heap: &'a mut RadixHeapMap<K, V>,
+}
+
+impl<'a, K: Radix + Ord + Clone, V> Iterator for DrainSorted<'a, K, V> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone, V> ExactSizeIterator for DrainSorted<'a, K, V> {}
+
+impl<'a, K: Radix + Ord + Clone, V> FusedIterator for DrainSorted<'a, K, V> {}
+
+impl<'a, K: Radix + Ord + Clone, V> Drop for DrainSorted<'a, K, V> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+/// An iterator popping the items tied for the greatest key of a
+/// [`RadixHeapMap`].
+///
+/// This `struct` is created by [`RadixHeapMap::pop_ties`].
+pub struct PopTies<'a, K, V> {
+    heap: &'a mut RadixHeapMap<K, V>,
+}
+
+impl<'a, K: Radix + Ord + Clone, V> Iterator for PopTies<'a, K, V> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.heap.buckets[0].is_empty() {
+            None
+        } else {
+            self.heap.pop()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heap.buckets[0].len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone, V> ExactSizeIterator for PopTies<'a, K, V> {}
+
+impl<'a, K: Radix + Ord + Clone, V> FusedIterator for PopTies<'a, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use crate::RadixHeapMap;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn polls() {
+        let mut heap: RadixHeapMap<_, _> = (0..10u32).map(|k| (k, k)).collect();
+
+        let mut drain = heap.drain_until(3);
+        assert_eq!(drain.size_hint(), (3, Some(3)));
+        assert_eq!(drain.by_ref().count(), 3);
+        assert!(drain.is_exhausted());
+
+        heap.push(6, 100);
+        let drained: Vec<_> = heap.drain_until(2).collect();
+        assert_eq!(drained, vec![(6, 100), (6, 6)]);
+
+        let mut drain = heap.drain_until(100);
+        assert_eq!(drain.by_ref().count(), 6);
+        assert!(!drain.is_exhausted());
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn deadline() {
+        let mut heap: RadixHeapMap<_, _> = (0..10u32).map(|k| (k, ())).collect();
+
+        let mut drain = heap.drain_until(Instant::now());
+        assert_eq!(drain.next(), None);
+        assert!(drain.is_exhausted());
+        assert_eq!(heap.len(), 10);
+
+        let later = Instant::now() + Duration::from_secs(3600);
+        assert_eq!(heap.drain_until(later).count(), 10);
+    }
+
+    #[test]
+    fn drain_keeps_capacity() {
+        let mut heap: RadixHeapMap<_, _> = (0..100u32).map(|k| (k, k)).collect();
+        heap.pop();
+
+        let capacity = |heap: &RadixHeapMap<u32, u32>| -> usize {
+            heap.buckets
+                .iter()
+                .map(|bucket| bucket.capacity())
+                .sum::<usize>()
+                + heap.initial.capacity()
+        };
+        let before = capacity(&heap);
+
+        let mut drained: Vec<_> = heap.drain().map(|(k, _)| k).collect();
+        drained.sort_unstable();
+        assert!(drained.iter().copied().eq(0..99));
+        assert!(heap.is_empty());
+        assert_eq!(heap.top(), None);
+        assert_eq!(capacity(&heap), before);
+
+        heap.extend((0..100u32).map(|k| (k, k)));
+        let mut drain = heap.drain();
+        assert_eq!(drain.len(), 100);
+        drain.next();
+        drop(drain);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn drain_sorted() {
+        let mut heap: RadixHeapMap<_, _> = (0..10u32).map(|k| (k, k)).collect();
+        heap.push(7, 100);
+
+        let drained: Vec<_> = heap
+            .drain_sorted()
+            .skip(2)
+            .take(3)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(drained, vec![7, 7, 6]);
+        assert!(heap.is_empty());
+
+        heap.push(20, 0);
+        assert_eq!(heap.drain_sorted().collect::<Vec<_>>(), vec![(20, 0)]);
+    }
+
+    #[test]
+    fn pop_ties() {
+        fn prop(xs: Vec<u8>) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&k| (k / 16, k)).collect();
+            let mut expected = heap.clone();
+
+            while !heap.is_empty() {
+                let ties: Vec<_> = heap.pop_ties().collect();
+                let top = expected.peek_key();
+                let popped: Vec<_> =
+                    std::iter::from_fn(|| expected.pop_if(|&k, _| Some(k) == top)).collect();
+
+                if ties.is_empty() || ties != popped {
+                    return false;
+                }
+            }
+
+            heap.pop_ties().next().is_none() && expected.is_empty()
+        }
+
+        quickcheck(prop as fn(Vec<u8>) -> bool);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{Bucket, Radix, RadixHeapMap};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::{Debug, Write};
+
+/// The number of entries shown for each bucket.
+const SAMPLE: usize = 4;
+
+impl<K: Radix + Ord + Clone + Debug, V: Debug> RadixHeapMap<K, V> {
+    /// Renders the top key and the non-empty buckets as a Graphviz graph,
+    /// showing the first few entries of each bucket.
+    ///
+    /// This is meant for debugging, such as including the state of a heap with
+    /// a custom [`Radix`] implementation in a bug report.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(8u8, 'a'), (1, 'b')]);
+    /// heap.constrain();
+    ///
+    /// let dot = heap.to_dot();
+    /// assert!(dot.starts_with("digraph"));
+    /// assert!(dot.contains("bucket 4"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph RadixHeapMap {\n    node [shape=box];\n");
+        let nodes = self.dump_nodes();
+
+        for (id, lines) in &nodes {
+            let label: Vec<_> = lines.iter().map(|line| escape_dot(line)).collect();
+            writeln!(out, "    {} [label=\"{}\"];", id, label.join("\\n")).unwrap();
+        }
+
+        let ids: Vec<_> = nodes.iter().map(|(id, _)| id.as_str()).collect();
+        writeln!(out, "    {};", ids.join(" -> ")).unwrap();
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the top key and the non-empty buckets as a Mermaid flowchart,
+    /// showing the first few entries of each bucket.
+    ///
+    /// See [`to_dot`](RadixHeapMap::to_dot).
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart LR\n");
+        let nodes = self.dump_nodes();
+
+        for (id, lines) in &nodes {
+            let label: Vec<_> = lines.iter().map(|line| escape_mermaid(line)).collect();
+            writeln!(out, "    {}[\"{}\"]", id, label.join("<br/>")).unwrap();
+        }
+
+        let ids: Vec<_> = nodes.iter().map(|(id, _)| id.as_str()).collect();
+        writeln!(out, "    {}", ids.join(" --> ")).unwrap();
+        out
+    }
+
+    /// The id and lines of text of each node to render.
+    fn dump_nodes(&self) -> Vec<(String, Vec<String>)> {
+        let mut nodes = vec![(
+            "top".to_string(),
+            vec![format!("top: {:?}", self.top), format!("len: {}", self.len)],
+        )];
+
+        let bucket = |title: String, bucket: &Bucket<K, V>| {
+            let mut lines = vec![format!("{} ({} items)", title, bucket.len())];
+            lines.extend(
+                bucket
+                    .iter()
+                    .take(SAMPLE)
+                    .map(|(key, value)| format!("{:?}: {:?}", key, value)),
+            );
+
+            if bucket.len() > SAMPLE {
+                lines.push(format!("... {} more", bucket.len() - SAMPLE));
+            }
+
+            lines
+        };
+
+        if !self.initial.is_empty() {
+            nodes.push((
+                "initial".to_string(),
+                bucket("initial".to_string(), &self.initial),
+            ));
+        }
+
+        for (index, b) in self.buckets.iter().enumerate() {
+            if !b.is_empty() {
+                nodes.push((
+                    format!("bucket{}", index),
+                    bucket(format!("bucket {}", index), b),
+                ));
+            }
+        }
+
+        nodes
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RadixHeapMap;
+
+    #[test]
+    fn dump() {
+        let mut heap = RadixHeapMap::new();
+        heap.extend((0..10u32).map(|k| (k, '"')));
+        assert!(heap.to_dot().contains("initial (10 items)"));
+
+        heap.pop();
+
+        let dot = heap.to_dot();
+        assert!(dot.contains("top: Some(9)"));
+        assert!(dot.contains("bucket 4 (8 items)"));
+        assert!(dot.contains("... 4 more"));
+        assert!(dot.contains("8: '\\\"'"));
+        assert!(dot.contains("top -> bucket1 -> bucket4;"));
+
+        let mermaid = heap.to_mermaid();
+        assert!(mermaid.contains("bucket1[\"bucket 1 (1 items)<br/>8: '#quot;'\"]"));
+        assert!(mermaid.contains("top --> bucket1 --> bucket4"));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{Radix, RadixHeapMap};
+use core::{
+    cmp::Reverse,
+    ops::{Add, Sub},
+};
+
+/// An earliest-deadline-first scheduler.
+///
+/// Tasks are submitted with a deadline and dispatched in order of increasing
+/// deadline. Dispatching a task after its deadline has passed is recorded in
+/// the [`Lateness`] statistics.
+///
+/// A task may be submitted with a deadline earlier than that of a task which
+/// has already been dispatched. This lowers the top of the underlying heap,
+/// which is slightly more expensive than a regular submit.
+///
+/// ```
+/// let mut scheduler = radix_heap::EdfScheduler::new();
+///
+/// scheduler.submit(30u64, "report");
+/// scheduler.submit(10, "sensor");
+/// scheduler.submit(20, "actuator");
+///
+/// assert_eq!(scheduler.next_due(5), Some((10, "sensor")));
+/// assert_eq!(scheduler.next_due(25), Some((20, "actuator")));
+/// assert_eq!(scheduler.lateness().late(), 1);
+/// assert_eq!(scheduler.lateness().max(), 5);
+/// ```
+#[derive(Clone)]
+pub struct EdfScheduler<D, T> {
+    heap: RadixHeapMap<Reverse<D>, T>,
+    lateness: Lateness<D>,
+}
+
+impl<D, T> EdfScheduler<D, T>
+where
+    D: Radix + Ord + Copy + Default + Add<Output = D> + Sub<Output = D>,
+{
+    /// Create an empty `EdfScheduler`.
+    pub fn new() -> EdfScheduler<D, T> {
+        EdfScheduler {
+            heap: RadixHeapMap::new(),
+            lateness: Lateness::default(),
+        }
+    }
+
+    /// Submits a task to be completed by `deadline`.
+    pub fn submit(&mut self, deadline: D, task: T) {
+        self.heap.raise_top(Reverse(deadline));
+        self.heap.push(Reverse(deadline), task);
+    }
+
+    /// Removes the task with the earliest deadline and returns it along with
+    /// its deadline, or `None` if there are no tasks.
+    ///
+    /// If `now` is past the deadline of the task, the task is counted as
+    /// late.
+    pub fn next_due(&mut self, now: D) -> Option<(D, T)> {
+        let (Reverse(deadline), task) = self.heap.pop()?;
+        self.lateness.record(deadline, now);
+        Some((deadline, task))
+    }
+
+    /// The earliest deadline of any pending task, or `None` if there are no
+    /// tasks.
+    pub fn next_deadline(&mut self) -> Option<D> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        self.heap.constrain();
+        self.heap.top().map(|Reverse(deadline)| deadline)
+    }
+
+    /// Lateness statistics for the tasks dispatched so far.
+    #[inline]
+    pub fn lateness(&self) -> &Lateness<D> {
+        &self.lateness
+    }
+
+    /// Resets the lateness statistics.
+    pub fn reset_lateness(&mut self) {
+        self.lateness = Lateness::default();
+    }
+
+    /// Returns the number of pending tasks.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if there are no pending tasks.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Drops all pending tasks. Lateness statistics are kept.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// Returns an iterator of all pending deadlines and tasks in arbitrary
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = (D, &T)> {
+        self.heap
+            .iter()
+            .map(|(Reverse(deadline), task)| (*deadline, task))
+    }
+}
+
+impl<D, T> Default for EdfScheduler<D, T>
+where
+    D: Radix + Ord + Copy + Default + Add<Output = D> + Sub<Output = D>,
+{
+    fn default() -> EdfScheduler<D, T> {
+        EdfScheduler::new()
+    }
+}
+
+/// Lateness statistics of an [`EdfScheduler`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Lateness<D> {
+    dispatched: usize,
+    late: usize,
+    total: D,
+    max: D,
+}
+
+impl<D: Ord + Copy + Add<Output = D> + Sub<Output = D>> Lateness<D> {
+    fn record(&mut self, deadline: D, now: D) {
+        self.dispatched += 1;
+
+        if now > deadline {
+            let lateness = now - deadline;
+            self.late += 1;
+            self.total = self.total + lateness;
+            self.max = self.max.max(lateness);
+        }
+    }
+
+    /// The number of tasks dispatched.
+    #[inline]
+    pub fn dispatched(&self) -> usize {
+        self.dispatched
+    }
+
+    /// The number of tasks dispatched after their deadline.
+    #[inline]
+    pub fn late(&self) -> usize {
+        self.late
+    }
+
+    /// The sum of the lateness of all late tasks.
+    #[inline]
+    pub fn total(&self) -> D {
+        self.total
+    }
+
+    /// The largest lateness of any task, or zero if no task was late.
+    #[inline]
+    pub fn max(&self) -> D {
+        self.max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EdfScheduler;
+
+    #[test]
+    fn dispatch_order() {
+        let mut scheduler = EdfScheduler::new();
+        scheduler.submit(5u32, 'a');
+        scheduler.submit(3, 'b');
+        scheduler.submit(8, 'c');
+
+        assert_eq!(scheduler.next_deadline(), Some(3));
+        assert_eq!(scheduler.next_due(0), Some((3, 'b')));
+
+        // Earlier than the task that was just dispatched.
+        scheduler.submit(1, 'd');
+
+        assert_eq!(scheduler.next_due(4), Some((1, 'd')));
+        assert_eq!(scheduler.next_due(4), Some((5, 'a')));
+        assert_eq!(scheduler.next_due(10), Some((8, 'c')));
+        assert_eq!(scheduler.next_due(10), None);
+        assert!(scheduler.is_empty());
+
+        let lateness = scheduler.lateness();
+        assert_eq!(lateness.dispatched(), 4);
+        assert_eq!(lateness.late(), 2);
+        assert_eq!(lateness.total(), 5);
+        assert_eq!(lateness.max(), 3);
+    }
+}
@@ -0,0 +1,117 @@
+//! A heap that can be shared between interrupt handlers and the main loop.
+//!
+//! [`StaticRadixHeap`] can be placed in a `static` and accessed through a
+//! [critical section](critical_section), so firmware can share one task queue
+//! between interrupts and the main loop without unsafe code:
+//!
+//! ```
+//! use radix_heap::embedded::StaticRadixHeap;
+//!
+//! static TASKS: StaticRadixHeap<u32, u8> = StaticRadixHeap::new(4);
+//!
+//! // Allocate the buckets before interrupts are enabled.
+//! TASKS.init();
+//!
+//! // In an interrupt handler:
+//! TASKS.with(|heap| heap.push_within_capacity(10, 1)).unwrap();
+//!
+//! // In the main loop:
+//! assert_eq!(TASKS.with(|heap| heap.pop()), Some((10, 1)));
+//! ```
+//!
+//! The target must provide a critical section implementation, as described in
+//! the documentation of the `critical-section` crate.
+
+use crate::{Radix, RadixHeapMap};
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// A [`RadixHeapMap`] with fixed bucket capacities behind a critical section,
+/// for use in a `static`.
+///
+/// The heap is created with
+/// [`with_bucket_capacity`](RadixHeapMap::with_bucket_capacity) on first
+/// access. Since that allocates, call [`init`](StaticRadixHeap::init) before
+/// the heap may be accessed from an interrupt. Afterwards, pushing with
+/// [`push_within_capacity`](RadixHeapMap::push_within_capacity) and popping
+/// never allocate.
+pub struct StaticRadixHeap<K, V> {
+    heap: Mutex<RefCell<Option<RadixHeapMap<K, V>>>>,
+    capacity: usize,
+}
+
+impl<K, V> StaticRadixHeap<K, V> {
+    /// Create a `StaticRadixHeap` where every bucket holds at most `capacity`
+    /// items. Nothing is allocated until the heap is first accessed.
+    pub const fn new(capacity: usize) -> StaticRadixHeap<K, V> {
+        StaticRadixHeap {
+            heap: Mutex::new(RefCell::new(None)),
+            capacity,
+        }
+    }
+
+    /// The maximum number of items in each bucket.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<K: Radix + Ord + Copy, V> StaticRadixHeap<K, V> {
+    /// Allocates the heap if it has not been accessed yet.
+    pub fn init(&self) {
+        self.with(|_| ());
+    }
+
+    /// Calls `f` with exclusive access to the heap inside a critical section.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if called from within `f`.
+    pub fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut RadixHeapMap<K, V>) -> R,
+    {
+        critical_section::with(|cs| {
+            let mut heap = self.heap.borrow_ref_mut(cs);
+            let capacity = self.capacity;
+            f(heap.get_or_insert_with(|| RadixHeapMap::with_bucket_capacity(capacity)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StaticRadixHeap;
+    use std::thread;
+
+    static HEAP: StaticRadixHeap<u32, u32> = StaticRadixHeap::new(16);
+
+    #[test]
+    fn shared() {
+        HEAP.init();
+        assert_eq!(HEAP.with(|heap| heap.bucket_capacity()), Some(16));
+
+        let threads: Vec<_> = (0..4)
+            .map(|t| {
+                thread::spawn(move || {
+                    for i in 0..4 {
+                        HEAP.with(|heap| heap.push_within_capacity(0, t * 4 + i))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let mut values: Vec<_> = std::iter::from_fn(|| HEAP.with(|heap| heap.pop()))
+            .map(|(_, value)| value)
+            .collect();
+        values.sort_unstable();
+
+        assert_eq!(values, (0..16).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{Radix, RadixHeapMap};
+use alloc::vec::Vec;
+
+/// A scheduler that pops from several named heaps in weighted round-robin.
+///
+/// Each class has its own heap, and classes take turns being popped from in
+/// the order they were added. A class with weight `w` gets up to `w` pops per
+/// turn, so a busy class cannot starve the others. Empty classes are skipped.
+///
+/// ```
+/// let mut scheduler = radix_heap::FairScheduler::new();
+/// scheduler.add_class("interactive", 2);
+/// scheduler.add_class("batch", 1);
+///
+/// for i in 0..4 {
+///     scheduler.push(&"interactive", i, 'i');
+///     scheduler.push(&"batch", i, 'b');
+/// }
+///
+/// let mut order = String::new();
+/// while let Some((_, _, value)) = scheduler.pop() {
+///     order.push(value);
+/// }
+/// assert_eq!(order, "iibiibbb");
+/// ```
+#[derive(Clone)]
+pub struct FairScheduler<C, K, V> {
+    classes: Vec<Class<C, K, V>>,
+
+    /// The class whose turn it is.
+    current: usize,
+
+    /// The number of pops the current class has had this turn.
+    served: usize,
+
+    len: usize,
+}
+
+#[derive(Clone)]
+struct Class<C, K, V> {
+    name: C,
+    weight: usize,
+    heap: RadixHeapMap<K, V>,
+}
+
+impl<C: Eq, K: Radix + Ord + Copy, V> FairScheduler<C, K, V> {
+    /// Create a `FairScheduler` without any classes.
+    pub fn new() -> FairScheduler<C, K, V> {
+        FairScheduler {
+            classes: Vec::new(),
+            current: 0,
+            served: 0,
+            len: 0,
+        }
+    }
+
+    /// Adds a class with the given name and weight.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if a class with the same name exists, or if `weight` is zero.
+    pub fn add_class(&mut self, name: C, weight: usize) {
+        assert!(weight > 0, "Weight must be positive");
+        assert!(self.index(&name).is_none(), "Class already exists");
+
+        self.classes.push(Class {
+            name,
+            weight,
+            heap: RadixHeapMap::new(),
+        });
+    }
+
+    /// Pushes a key value pair onto the heap of the given class.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the class does not exist, or if the key is larger than the
+    /// top key of the class.
+    pub fn push(&mut self, class: &C, key: K, value: V) {
+        let index = self.index(class).expect("Class does not exist");
+        self.classes[index].heap.push(key, value);
+        self.len += 1;
+    }
+
+    /// Pops the greatest element of the class whose turn it is, and returns
+    /// it along with the name of the class, or `None` if all classes are
+    /// empty.
+    pub fn pop(&mut self) -> Option<(&C, K, V)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        while self.classes[self.current].heap.is_empty() {
+            self.next_turn();
+        }
+
+        let index = self.current;
+        self.len -= 1;
+        self.served += 1;
+
+        if self.served == self.classes[index].weight {
+            self.next_turn();
+        }
+
+        let class = &mut self.classes[index];
+        let (key, value) = class.heap.pop().expect("Expected non-empty class");
+        Some((&class.name, key, value))
+    }
+
+    /// Returns the names of the classes in the order they take turns.
+    pub fn classes(&self) -> impl Iterator<Item = &C> {
+        self.classes.iter().map(|class| &class.name)
+    }
+
+    /// Returns the number of elements in the given class, or `None` if it
+    /// does not exist.
+    pub fn class_len(&self, class: &C) -> Option<usize> {
+        self.index(class)
+            .map(|index| self.classes[index].heap.len())
+    }
+
+    /// Returns the top key of the given class, or `None` if it does not
+    /// exist or has no top key yet.
+    pub fn class_top(&self, class: &C) -> Option<K> {
+        self.index(class)
+            .and_then(|index| self.classes[index].heap.top())
+    }
+
+    /// Returns the total number of elements in all classes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if all classes are empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn next_turn(&mut self) {
+        self.current = (self.current + 1) % self.classes.len();
+        self.served = 0;
+    }
+
+    fn index(&self, class: &C) -> Option<usize> {
+        self.classes.iter().position(|c| c.name == *class)
+    }
+}
+
+impl<C: Eq, K: Radix + Ord + Copy, V> Default for FairScheduler<C, K, V> {
+    fn default() -> FairScheduler<C, K, V> {
+        FairScheduler::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FairScheduler;
+
+    #[test]
+    fn weighted() {
+        let mut scheduler = FairScheduler::new();
+        scheduler.add_class('a', 3);
+        scheduler.add_class('b', 1);
+        scheduler.add_class('c', 2);
+
+        for i in 0..10u32 {
+            scheduler.push(&'a', i, ());
+        }
+        scheduler.push(&'b', 7, ());
+        scheduler.push(&'c', 1, ());
+
+        assert_eq!(scheduler.class_len(&'a'), Some(10));
+        assert_eq!(scheduler.class_top(&'a'), None);
+        assert_eq!(scheduler.class_len(&'d'), None);
+
+        let mut order = Vec::new();
+        for _ in 0..6 {
+            let (&class, key, _) = scheduler.pop().unwrap();
+            order.push((class, key));
+        }
+
+        assert_eq!(
+            order,
+            vec![('a', 9), ('a', 8), ('a', 7), ('b', 7), ('c', 1), ('a', 6)]
+        );
+        assert_eq!(scheduler.class_top(&'a'), Some(6));
+        assert_eq!(scheduler.len(), 6);
+    }
+}
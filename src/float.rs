@@ -0,0 +1,174 @@
+use crate::Radix;
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Add,
+};
+
+macro_rules! radix_float {
+    ($(#[$attr:meta])* $name:ident, $t:ty, $bits:ty, $key:ident) => {
+        /// Maps the bits of a float to an integer with the same order as
+        /// `total_cmp`, by flipping every bit of negative floats and only the
+        /// sign bit of positive floats.
+        #[inline]
+        pub(crate) fn $key(x: $t) -> $bits {
+            const SIGN: $bits = 1 << (<$bits>::BITS - 1);
+
+            let bits = x.to_bits();
+            if bits & SIGN != 0 {
+                !bits
+            } else {
+                bits | SIGN
+            }
+        }
+
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default)]
+        pub struct $name(pub $t);
+
+        impl Ord for $name {
+            #[inline]
+            fn cmp(&self, other: &$name) -> Ordering {
+                $key(self.0).cmp(&$key(other.0))
+            }
+        }
+
+        impl PartialOrd for $name {
+            #[inline]
+            fn partial_cmp(&self, other: &$name) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl PartialEq for $name {
+            #[inline]
+            fn eq(&self, other: &$name) -> bool {
+                self.0.to_bits() == other.0.to_bits()
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.to_bits().hash(state);
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            #[inline]
+            fn add(self, other: $name) -> $name {
+                $name(self.0 + other.0)
+            }
+        }
+
+        impl From<$t> for $name {
+            #[inline]
+            fn from(x: $t) -> $name {
+                $name(x)
+            }
+        }
+
+        impl Radix for $name {
+            #[inline]
+            fn radix_similarity(&self, other: &$name) -> u32 {
+                $key(self.0).radix_similarity(&$key(other.0))
+            }
+
+            const RADIX_BITS: u32 = <$bits>::RADIX_BITS;
+        }
+    };
+}
+
+radix_float! {
+    /// An `f32` key ordered like [`f32::total_cmp`], without the
+    /// `ordered-float` dependency.
+    ///
+    /// Negative floats sort before positive ones, `-0.0` sorts before `0.0`,
+    /// and NaNs sort after infinity, or before negative infinity if their
+    /// sign bit is set. Keys are equal only if their bits are equal.
+    ///
+    /// ```
+    /// use radix_heap::{RadixF32, RadixHeapMap};
+    ///
+    /// let mut heap = RadixHeapMap::new();
+    /// heap.push(RadixF32(-1.5), 'a');
+    /// heap.push(RadixF32(2.0), 'b');
+    /// heap.push(RadixF32(-8.0), 'c');
+    ///
+    /// assert_eq!(heap.pop(), Some((RadixF32(2.0), 'b')));
+    /// assert_eq!(heap.pop(), Some((RadixF32(-1.5), 'a')));
+    /// assert_eq!(heap.pop(), Some((RadixF32(-8.0), 'c')));
+    /// ```
+    RadixF32, f32, u32, f32_key
+}
+
+radix_float! {
+    /// An `f64` key ordered like [`f64::total_cmp`], without the
+    /// `ordered-float` dependency.
+    ///
+    /// See [`RadixF32`].
+    RadixF64, f64, u64, f64_key
+}
+
+#[cfg(feature = "ordered-float")]
+macro_rules! radix_not_nan_impl {
+    ($t:ty, $bits:ty, $key:ident) => {
+        impl Radix for ordered_float::NotNan<$t> {
+            #[inline]
+            fn radix_similarity(&self, other: &ordered_float::NotNan<$t>) -> u32 {
+                // `-0.0` and `0.0` are equal, so they must have the same key.
+                let key = |x: &ordered_float::NotNan<$t>| $key(x.into_inner() + 0.0);
+                key(self).radix_similarity(&key(other))
+            }
+
+            const RADIX_BITS: u32 = <$bits>::RADIX_BITS;
+        }
+    };
+}
+
+#[cfg(feature = "ordered-float")]
+radix_not_nan_impl!(f32, u32, f32_key);
+
+#[cfg(feature = "ordered-float")]
+radix_not_nan_impl!(f64, u64, f64_key);
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::{RadixF32, RadixF64};
+    use crate::RadixHeapMap;
+
+    #[test]
+    fn total_cmp() {
+        fn prop(a: f64, b: f64) -> bool {
+            RadixF64(a).cmp(&RadixF64(b)) == a.total_cmp(&b)
+                && RadixF32(a as f32).cmp(&RadixF32(b as f32)) == (a as f32).total_cmp(&(b as f32))
+        }
+
+        quickcheck(prop as fn(f64, f64) -> bool);
+        assert!(prop(-0.0, 0.0));
+        assert!(prop(f64::NAN, f64::INFINITY));
+        assert!(prop(-f64::NAN, f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn sort() {
+        fn prop(xs: Vec<f32>) -> bool {
+            let mut xs: Vec<_> = xs.into_iter().map(RadixF32).collect();
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, ())).collect();
+            xs.sort();
+
+            std::iter::from_fn(|| heap.pop())
+                .map(|(x, ())| x)
+                .eq(xs.into_iter().rev())
+        }
+
+        quickcheck(prop as fn(Vec<f32>) -> bool);
+        assert!(prop(vec![-1.0, -2.0, 0.0, -0.0, 3.5, f32::NAN, -f32::NAN]));
+    }
+}
@@ -0,0 +1,392 @@
+//! Uniform grid maps for pathfinding.
+//!
+//! A [`GridMap`] is a rectangular grid of passable and blocked cells, which
+//! can be loaded from the `.map` format of the
+//! [2D Pathfinding Benchmarks](https://movingai.com/benchmarks/grids.html).
+//! Cells are connected to their 4 or 8 neighbors depending on the
+//! [`Connectivity`], and [`GridMap::astar`] finds paths using the matching
+//! heuristic. On 8-connected grids, [`GridMap::jps`] finds paths of the same
+//! cost using Jump Point Search.
+
+use crate::algorithms::astar;
+use std::{error::Error, fmt, str::FromStr};
+
+mod jps;
+
+/// A position on a grid, as `(x, y)`.
+pub type Pos = (u32, u32);
+
+/// The cost of moving to a horizontally or vertically adjacent cell when
+/// using [`Connectivity::Eight`].
+pub const STRAIGHT_COST: u32 = 10;
+
+/// The cost of moving to a diagonally adjacent cell when using
+/// [`Connectivity::Eight`]. This approximates `STRAIGHT_COST * sqrt(2)`.
+pub const DIAGONAL_COST: u32 = 14;
+
+/// Which cells are adjacent to each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Cells are adjacent horizontally and vertically, and each step costs 1.
+    Four,
+
+    /// Cells are adjacent horizontally, vertically and diagonally. Straight
+    /// steps cost [`STRAIGHT_COST`] and diagonal steps cost
+    /// [`DIAGONAL_COST`]. A diagonal step is only allowed if both cells it
+    /// cuts the corner of are passable.
+    Eight,
+}
+
+impl Connectivity {
+    /// The heuristic matching this connectivity: Manhattan distance for
+    /// [`Connectivity::Four`] and octile distance for
+    /// [`Connectivity::Eight`].
+    pub fn heuristic(self, from: Pos, to: Pos) -> u32 {
+        match self {
+            Connectivity::Four => manhattan(from, to),
+            Connectivity::Eight => octile(from, to),
+        }
+    }
+}
+
+/// The Manhattan distance between two positions.
+///
+/// This is the cost of the cheapest path on an open grid using
+/// [`Connectivity::Four`].
+#[inline]
+pub fn manhattan(from: Pos, to: Pos) -> u32 {
+    let (dx, dy) = delta(from, to);
+    dx + dy
+}
+
+/// The octile distance between two positions.
+///
+/// This is the cost of the cheapest path on an open grid using
+/// [`Connectivity::Eight`].
+#[inline]
+pub fn octile(from: Pos, to: Pos) -> u32 {
+    let (dx, dy) = delta(from, to);
+    DIAGONAL_COST * dx.min(dy) + STRAIGHT_COST * (dx.max(dy) - dx.min(dy))
+}
+
+fn delta(from: Pos, to: Pos) -> (u32, u32) {
+    (
+        from.0.max(to.0) - from.0.min(to.0),
+        from.1.max(to.1) - from.1.min(to.1),
+    )
+}
+
+/// A rectangular grid of passable and blocked cells.
+///
+/// ```
+/// use radix_heap::grid::{Connectivity, GridMap};
+///
+/// let map: GridMap = "type octile\nheight 3\nwidth 4\nmap\n\
+///                     ....\n\
+///                     .@@.\n\
+///                     ....\n"
+///     .parse()
+///     .unwrap();
+///
+/// let (cost, path) = map.astar((0, 1), (3, 1), Connectivity::Four).unwrap();
+/// assert_eq!(cost, 5);
+/// assert_eq!(path.len(), 6);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct GridMap {
+    width: u32,
+    height: u32,
+    passable: Vec<bool>,
+}
+
+impl GridMap {
+    /// Create a `GridMap` of the given size where every cell is passable.
+    pub fn new(width: u32, height: u32) -> GridMap {
+        GridMap {
+            width,
+            height,
+            passable: vec![true; width as usize * height as usize],
+        }
+    }
+
+    /// Parses a map in the MovingAI `.map` format.
+    ///
+    /// The cells `.`, `G` and `S` are passable and all other cells are
+    /// blocked.
+    pub fn parse(text: &str) -> Result<GridMap, ParseMapError> {
+        let mut lines = text.lines();
+        let mut header = |name: &'static str| {
+            let line = lines.next().ok_or(ParseMapError("unexpected end of map"))?;
+            let mut words = line.split_whitespace();
+
+            if words.next() != Some(name) {
+                return Err(ParseMapError("unexpected header"));
+            }
+
+            Ok(words.next().unwrap_or(""))
+        };
+
+        header("type")?;
+        let height = header("height")?
+            .parse()
+            .map_err(|_| ParseMapError("invalid height"))?;
+        let width = header("width")?
+            .parse()
+            .map_err(|_| ParseMapError("invalid width"))?;
+        header("map")?;
+
+        // The cells are only stored as their rows are read, so the header
+        // cannot make this allocate more than the text holds.
+        let mut passable = Vec::new();
+
+        for _ in 0..height {
+            let line = lines.next().ok_or(ParseMapError("missing rows"))?;
+
+            if line.len() != width as usize {
+                return Err(ParseMapError("row has the wrong width"));
+            }
+
+            passable.extend(line.bytes().map(|cell| matches!(cell, b'.' | b'G' | b'S')));
+        }
+
+        Ok(GridMap {
+            width,
+            height,
+            passable,
+        })
+    }
+
+    /// The number of columns.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The number of rows.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns true if `pos` is inside the map.
+    #[inline]
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.0 < self.width && pos.1 < self.height
+    }
+
+    /// Returns true if `pos` is inside the map and passable.
+    #[inline]
+    pub fn is_passable(&self, pos: Pos) -> bool {
+        self.contains(pos) && self.passable[self.index(pos)]
+    }
+
+    /// Sets whether `pos` is passable.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `pos` is outside the map.
+    pub fn set_passable(&mut self, pos: Pos, passable: bool) {
+        assert!(self.contains(pos), "Position must be inside the map");
+        let index = self.index(pos);
+        self.passable[index] = passable;
+    }
+
+    /// Returns an iterator of the passable cells adjacent to `pos` along with
+    /// the cost of moving to them.
+    pub fn neighbors(&self, pos: Pos, connectivity: Connectivity) -> Neighbors<'_> {
+        Neighbors {
+            map: self,
+            pos,
+            connectivity,
+            direction: 0,
+        }
+    }
+
+    /// Finds the cheapest path from `from` to `to` using A* with the
+    /// heuristic matching `connectivity`.
+    ///
+    /// Returns the cost and the path including both endpoints, or `None` if
+    /// there is no path.
+    pub fn astar(&self, from: Pos, to: Pos, connectivity: Connectivity) -> Option<(u32, Vec<Pos>)> {
+        if !self.is_passable(from) || !self.is_passable(to) {
+            return None;
+        }
+
+        astar(
+            from,
+            to,
+            |pos| self.neighbors(pos, connectivity),
+            |pos| connectivity.heuristic(pos, to),
+        )
+    }
+
+    #[inline]
+    fn index(&self, pos: Pos) -> usize {
+        pos.1 as usize * self.width as usize + pos.0 as usize
+    }
+}
+
+impl FromStr for GridMap {
+    type Err = ParseMapError;
+
+    fn from_str(text: &str) -> Result<GridMap, ParseMapError> {
+        GridMap::parse(text)
+    }
+}
+
+impl fmt::Debug for GridMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                f.write_str(if self.is_passable((x, y)) { "." } else { "@" })?;
+            }
+
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The steps to each neighbor, with the straight ones first.
+const DIRECTIONS: [(i8, i8); 8] = [
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (0, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+    (1, -1),
+];
+
+/// An iterator over the passable neighbors of a cell in a [`GridMap`].
+#[derive(Clone)]
+pub struct Neighbors<'a> {
+    map: &'a GridMap,
+    pos: Pos,
+    connectivity: Connectivity,
+    direction: usize,
+}
+
+impl<'a> Iterator for Neighbors<'a> {
+    type Item = (Pos, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let count = match self.connectivity {
+            Connectivity::Four => 4,
+            Connectivity::Eight => 8,
+        };
+
+        while self.direction < count {
+            let (dx, dy) = DIRECTIONS[self.direction];
+            self.direction += 1;
+
+            let step = |dx: i8, dy: i8| {
+                let x = self.pos.0.wrapping_add(dx as u32);
+                let y = self.pos.1.wrapping_add(dy as u32);
+                Some((x, y)).filter(|&pos| self.map.is_passable(pos))
+            };
+
+            let neighbor = match step(dx, dy) {
+                Some(neighbor) => neighbor,
+                None => continue,
+            };
+
+            let cost = match self.connectivity {
+                Connectivity::Four => 1,
+                Connectivity::Eight if dx == 0 || dy == 0 => STRAIGHT_COST,
+                Connectivity::Eight if step(dx, 0).is_some() && step(0, dy).is_some() => {
+                    DIAGONAL_COST
+                }
+                Connectivity::Eight => continue,
+            };
+
+            return Some((neighbor, cost));
+        }
+
+        None
+    }
+}
+
+/// An error returned when parsing a [`GridMap`] fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseMapError(&'static str);
+
+impl fmt::Display for ParseMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid map: {}", self.0)
+    }
+}
+
+impl Error for ParseMapError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Connectivity, GridMap, DIAGONAL_COST, STRAIGHT_COST};
+    use crate::algorithms::dijkstra;
+
+    const MAP: &str = include_str!("../benches/den203d.map");
+
+    #[test]
+    fn parse() {
+        let map: GridMap = MAP.parse().unwrap();
+        assert_eq!(map.width(), 93);
+        assert_eq!(map.height(), 77);
+        assert!(map.is_passable((28, 0)));
+        assert!(!map.is_passable((27, 0)));
+        assert!(!map.is_passable((0, 0)));
+
+        assert!("type octile\nheight 2\nwidth 2\nmap\n..\n"
+            .parse::<GridMap>()
+            .is_err());
+    }
+
+    #[test]
+    fn huge_header() {
+        let text = format!("type octile\nheight {0}\nwidth {0}\nmap\n", u32::MAX);
+        assert!(text.parse::<GridMap>().is_err());
+    }
+
+    #[test]
+    fn neighbors() {
+        let mut map = GridMap::new(3, 3);
+        map.set_passable((1, 0), false);
+
+        let mut four: Vec<_> = map.neighbors((1, 1), Connectivity::Four).collect();
+        four.sort();
+        assert_eq!(four, vec![((0, 1), 1), ((1, 2), 1), ((2, 1), 1)]);
+
+        // The diagonals towards the blocked cell cut its corner.
+        let eight: Vec<_> = map.neighbors((0, 1), Connectivity::Eight).collect();
+        assert_eq!(
+            eight,
+            vec![
+                ((1, 1), STRAIGHT_COST),
+                ((0, 2), STRAIGHT_COST),
+                ((0, 0), STRAIGHT_COST),
+                ((1, 2), DIAGONAL_COST)
+            ]
+        );
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let map: GridMap = MAP.parse().unwrap();
+        let from = (28, 0);
+
+        for &connectivity in &[Connectivity::Four, Connectivity::Eight] {
+            let costs = dijkstra(from, |pos| map.neighbors(pos, connectivity));
+            assert!(costs.len() > 1000);
+
+            for (&to, &cost) in costs.iter().step_by(97) {
+                let (astar_cost, path) = map.astar(from, to, connectivity).unwrap();
+                assert_eq!(astar_cost, cost);
+                assert_eq!(path.first(), Some(&from));
+                assert_eq!(path.last(), Some(&to));
+            }
+        }
+
+        assert!(map.astar(from, (0, 0), Connectivity::Four).is_none());
+    }
+}
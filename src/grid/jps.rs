@@ -0,0 +1,268 @@
+use super::{octile, GridMap, Pos};
+use crate::RadixHeapMap;
+use std::{cmp::Reverse, collections::HashMap};
+
+impl GridMap {
+    /// Finds the cheapest path from `from` to `to` using Jump Point Search.
+    ///
+    /// This gives the same cost as [`GridMap::astar`] with
+    /// [`Connectivity::Eight`](super::Connectivity::Eight), but only pushes
+    /// jump points onto the open list: the cells where the straight and
+    /// diagonal lines through the grid have to turn around an obstacle. On
+    /// open maps this makes the open list much smaller.
+    ///
+    /// Returns the cost and the full path including both endpoints, or
+    /// `None` if there is no path.
+    ///
+    /// ```
+    /// use radix_heap::grid::{Connectivity, GridMap};
+    ///
+    /// let mut map = GridMap::new(64, 64);
+    /// for y in 0..60 {
+    ///     map.set_passable((32, y), false);
+    /// }
+    ///
+    /// let (cost, path) = map.jps((0, 0), (63, 0)).unwrap();
+    /// assert_eq!(Some(cost), map.astar((0, 0), (63, 0), Connectivity::Eight).map(|(c, _)| c));
+    /// assert_eq!(path.first(), Some(&(0, 0)));
+    /// assert_eq!(path.last(), Some(&(63, 0)));
+    /// ```
+    pub fn jps(&self, from: Pos, to: Pos) -> Option<(u32, Vec<Pos>)> {
+        if !self.is_passable(from) || !self.is_passable(to) {
+            return None;
+        }
+
+        let mut heap = RadixHeapMap::new();
+        let mut costs = HashMap::new();
+        let mut parents: HashMap<Pos, Pos> = HashMap::new();
+
+        costs.insert(from, 0);
+        heap.push(Reverse(octile(from, to)), (from, 0));
+
+        while let Some((_, (pos, cost))) = heap.pop() {
+            if costs[&pos] < cost {
+                continue;
+            }
+
+            if pos == to {
+                return Some((cost, self.unfold(&parents, from, to)));
+            }
+
+            let directions = match parents.get(&pos) {
+                Some(&parent) => self.pruned(pos, direction(parent, pos)),
+                None => self.pruned(pos, (0, 0)),
+            };
+
+            for direction in directions.iter().flatten() {
+                let next = match self.jump(pos, *direction, to) {
+                    Some(next) => next,
+                    None => continue,
+                };
+
+                let next_cost = cost + octile(pos, next);
+
+                if !matches!(costs.get(&next), Some(&c) if c <= next_cost) {
+                    costs.insert(next, next_cost);
+                    parents.insert(next, pos);
+                    heap.push(Reverse(next_cost + octile(next, to)), (next, next_cost));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn passable_at(&self, (x, y): (i64, i64)) -> bool {
+        x >= 0 && y >= 0 && self.is_passable((x as u32, y as u32))
+    }
+
+    /// The directions to search from `pos` when arriving in `direction`, or
+    /// all directions if `direction` is `(0, 0)`.
+    fn pruned(&self, pos: Pos, (dx, dy): (i64, i64)) -> [Option<(i64, i64)>; 8] {
+        let (x, y) = (i64::from(pos.0), i64::from(pos.1));
+        let open = |dx: i64, dy: i64| self.passable_at((x + dx, y + dy));
+        let mut directions = [None; 8];
+        let mut add = |direction: (i64, i64)| {
+            if let Some(slot) = directions.iter_mut().find(|slot| slot.is_none()) {
+                *slot = Some(direction);
+            }
+        };
+
+        if dx == 0 && dy == 0 {
+            for &(dx, dy) in &[(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                add((dx, dy));
+            }
+
+            for &(dx, dy) in &[(1, 1), (-1, 1), (-1, -1), (1, -1)] {
+                if open(dx, 0) && open(0, dy) {
+                    add((dx, dy));
+                }
+            }
+        } else if dx != 0 && dy != 0 {
+            add((dx, 0));
+            add((0, dy));
+
+            if open(dx, 0) && open(0, dy) {
+                add((dx, dy));
+            }
+        } else {
+            // The two directions perpendicular to the one we arrived in.
+            let (px, py) = (dy, dx);
+
+            if open(dx, dy) {
+                add((dx, dy));
+
+                if open(px, py) {
+                    add((dx + px, dy + py));
+                }
+
+                if open(-px, -py) {
+                    add((dx - px, dy - py));
+                }
+            }
+
+            add((px, py));
+            add((-px, -py));
+        }
+
+        directions
+    }
+
+    /// Moves from `pos` in `direction` until reaching a jump point, returning
+    /// it, or `None` if the line hits an obstacle first.
+    fn jump(&self, pos: Pos, (dx, dy): (i64, i64), goal: Pos) -> Option<Pos> {
+        let (mut x, mut y) = (i64::from(pos.0), i64::from(pos.1));
+        let target = (i64::from(goal.0), i64::from(goal.1));
+        let open = |x: i64, y: i64| self.passable_at((x, y));
+
+        loop {
+            x += dx;
+            y += dy;
+
+            if !open(x, y) {
+                return None;
+            }
+
+            if (x, y) == target {
+                break;
+            }
+
+            if dx != 0 && dy != 0 {
+                let pos = (x as u32, y as u32);
+
+                if self.jump(pos, (dx, 0), goal).is_some()
+                    || self.jump(pos, (0, dy), goal).is_some()
+                {
+                    break;
+                }
+            } else {
+                // The two cells perpendicular to the direction of movement
+                // are forced neighbors if they are open but the cells behind
+                // them are not.
+                let (px, py) = (dy, dx);
+
+                if (open(x + px, y + py) && !open(x + px - dx, y + py - dy))
+                    || (open(x - px, y - py) && !open(x - px - dx, y - py - dy))
+                {
+                    break;
+                }
+            }
+
+            if !open(x + dx, y) || !open(x, y + dy) {
+                return None;
+            }
+        }
+
+        Some((x as u32, y as u32))
+    }
+
+    /// Expands the jump points from `from` to `to` into a full path.
+    fn unfold(&self, parents: &HashMap<Pos, Pos>, from: Pos, to: Pos) -> Vec<Pos> {
+        let mut path = vec![to];
+        let mut pos = to;
+
+        while pos != from {
+            let parent = parents[&pos];
+            let (dx, dy) = direction(pos, parent);
+
+            while pos != parent {
+                pos = (
+                    (i64::from(pos.0) + dx) as u32,
+                    (i64::from(pos.1) + dy) as u32,
+                );
+                path.push(pos);
+            }
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// The unit step from `from` towards `to`.
+fn direction(from: Pos, to: Pos) -> (i64, i64) {
+    (
+        (i64::from(to.0) - i64::from(from.0)).signum(),
+        (i64::from(to.1) - i64::from(from.1)).signum(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::{quickcheck, TestResult};
+    use super::super::{Connectivity, GridMap, Pos};
+    use std::collections::HashSet;
+
+    fn check_path(map: &GridMap, path: &[Pos]) {
+        for step in path.windows(2) {
+            assert!(map
+                .neighbors(step[0], Connectivity::Eight)
+                .any(|(pos, _)| pos == step[1]));
+        }
+    }
+
+    #[test]
+    fn jps_matches_astar() {
+        let map: GridMap = include_str!("../../benches/den203d.map").parse().unwrap();
+        let from = (28, 0);
+
+        for y in (0..map.height()).step_by(3) {
+            for x in (0..map.width()).step_by(5) {
+                let astar = map.astar(from, (x, y), Connectivity::Eight);
+                let jps = map.jps(from, (x, y));
+                assert_eq!(astar.map(|(c, _)| c), jps.as_ref().map(|&(c, _)| c));
+
+                if let Some((_, path)) = jps {
+                    check_path(&map, &path);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn jps_random_maps() {
+        fn prop(blocked: Vec<(u8, u8)>, from: (u8, u8), to: (u8, u8)) -> TestResult {
+            let blocked: HashSet<_> = blocked.into_iter().map(|(x, y)| (x % 16, y % 16)).collect();
+            let from = (u32::from(from.0 % 16), u32::from(from.1 % 16));
+            let to = (u32::from(to.0 % 16), u32::from(to.1 % 16));
+
+            let mut map = GridMap::new(16, 16);
+            for (x, y) in blocked {
+                map.set_passable((u32::from(x), u32::from(y)), false);
+            }
+
+            let astar = map.astar(from, to, Connectivity::Eight);
+            let jps = map.jps(from, to);
+
+            if let Some((_, path)) = &jps {
+                check_path(&map, path);
+            }
+
+            TestResult::from_bool(astar.map(|(c, _)| c) == jps.map(|(c, _)| c))
+        }
+
+        quickcheck(prop as fn(Vec<(u8, u8)>, (u8, u8), (u8, u8)) -> TestResult);
+    }
+}
@@ -0,0 +1,549 @@
+use crate::Radix;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A handle to an entry of a [`RadixIndexHeapMap`], returned when pushing it.
+///
+/// A handle stays valid until its entry is popped or removed. The slot of a
+/// removed entry is reused with a new generation, so using its handle
+/// afterwards finds nothing rather than the next entry in that slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: u32,
+}
+
+#[derive(Clone)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+
+    /// The bucket the entry is in, and its position in that bucket.
+    bucket: usize,
+    pos: usize,
+}
+
+#[derive(Clone)]
+struct Slot<K, V> {
+    generation: u32,
+    entry: Option<Entry<K, V>>,
+}
+
+/// A monotone priority queue where entries can be changed or removed through
+/// [`Handle`]s, implemented using a radix heap.
+///
+/// Like [`RadixHeapMap`](crate::RadixHeapMap), this is a max-heap, and every
+/// pushed key must be no larger than the [`top`](RadixIndexHeapMap::top) key.
+/// The key of an entry can be changed with
+/// [`update_key`](RadixIndexHeapMap::update_key), which moves it between
+/// buckets, so Dijkstra's algorithm can decrease the cost of a node instead of
+/// pushing it again and skipping the stale entries when popping.
+///
+/// Entries live in a table of slots, and buckets hold the slots of their
+/// entries, so moving an entry does not move its value. Entries with equal
+/// keys are popped in arbitrary order.
+///
+/// ```
+/// use radix_heap::RadixIndexHeapMap;
+/// use std::cmp::Reverse;
+///
+/// let mut heap = RadixIndexHeapMap::new();
+/// let a = heap.push(Reverse(10u32), 'a');
+/// heap.push(Reverse(7), 'b');
+///
+/// heap.update_key(a, Reverse(4));
+/// assert_eq!(heap.pop(), Some((Reverse(4), 'a')));
+/// assert_eq!(heap.get(a), None);
+/// assert_eq!(heap.pop(), Some((Reverse(7), 'b')));
+/// ```
+#[derive(Clone)]
+pub struct RadixIndexHeapMap<K, V> {
+    len: usize,
+    top: Option<K>,
+
+    /// The slots of the entries at each radix distance from the top key,
+    /// followed by the slots of entries pushed before a top key was set.
+    buckets: Vec<Vec<usize>>,
+    slots: Vec<Slot<K, V>>,
+    free: Vec<usize>,
+}
+
+impl<K: Radix + Ord + Copy, V> RadixIndexHeapMap<K, V> {
+    /// Create an empty `RadixIndexHeapMap`.
+    pub fn new() -> RadixIndexHeapMap<K, V> {
+        RadixIndexHeapMap {
+            len: 0,
+            top: None,
+            buckets: (0..=K::RADIX_BITS + 1).map(|_| Vec::new()).collect(),
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Create an empty `RadixIndexHeapMap` with the top key set to a specific
+    /// value.
+    pub fn new_at(top: K) -> RadixIndexHeapMap<K, V> {
+        RadixIndexHeapMap {
+            top: Some(top),
+            ..RadixIndexHeapMap::new()
+        }
+    }
+
+    /// Drops all entries from the heap and sets the top key to `None`.
+    ///
+    /// Handles of the dropped entries are no longer valid.
+    pub fn clear(&mut self) {
+        for bucket in &mut self.buckets {
+            for index in bucket.drain(..) {
+                let slot = &mut self.slots[index];
+                slot.entry = None;
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(index);
+            }
+        }
+
+        self.len = 0;
+        self.top = None;
+    }
+
+    /// Pushes a new key value pair onto the heap, and returns a handle to it.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    pub fn push(&mut self, key: K, value: V) -> Handle {
+        self.assert_below_top(key);
+
+        let entry = Entry {
+            key,
+            value,
+            bucket: 0,
+            pos: 0,
+        };
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index].entry = Some(entry);
+                index
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: Some(entry),
+                });
+                self.slots.len() - 1
+            }
+        };
+
+        self.insert(index);
+        self.len += 1;
+
+        Handle {
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+
+    /// Removes the greatest element from the heap and returns it, or `None`
+    /// if empty.
+    ///
+    /// This will set the top key to the extracted key.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.buckets[0].is_empty() {
+            self.constrain();
+        }
+
+        let index = *self.buckets[0].last()?;
+        self.take(index)
+    }
+
+    /// Returns the greatest element without removing it, or `None` if empty.
+    ///
+    /// Since finding it may set the top key to the greatest key, this requires
+    /// mutable access.
+    pub fn peek(&mut self) -> Option<(Handle, &K, &V)> {
+        if self.buckets[0].is_empty() {
+            self.constrain();
+        }
+
+        let index = *self.buckets[0].last()?;
+        let slot = &self.slots[index];
+        let entry = slot.entry.as_ref()?;
+
+        let handle = Handle {
+            index,
+            generation: slot.generation,
+        };
+        Some((handle, &entry.key, &entry.value))
+    }
+
+    /// Returns the key and value of the entry of `handle`, or `None` if it
+    /// was popped or removed.
+    pub fn get(&self, handle: Handle) -> Option<(&K, &V)> {
+        self.entry(handle).map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Returns the value of the entry of `handle` as a mutable reference, or
+    /// `None` if it was popped or removed.
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut V> {
+        match self.slots.get_mut(handle.index) {
+            Some(slot) if slot.generation == handle.generation => {
+                slot.entry.as_mut().map(|entry| &mut entry.value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if the entry of `handle` is still in the heap.
+    #[inline]
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.entry(handle).is_some()
+    }
+
+    /// Changes the key of the entry of `handle`, and returns the old key, or
+    /// `None` if the entry was popped or removed.
+    ///
+    /// With `Reverse` keys, as for Dijkstra's algorithm, this is a decrease
+    /// key operation. Only the bucket of the entry changes.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    pub fn update_key(&mut self, handle: Handle, key: K) -> Option<K> {
+        self.entry(handle)?;
+        self.assert_below_top(key);
+
+        self.unlink(handle.index);
+        let entry = self.slots[handle.index].entry.as_mut()?;
+        let old = core::mem::replace(&mut entry.key, key);
+        self.insert(handle.index);
+
+        Some(old)
+    }
+
+    /// Removes the entry of `handle` and returns it, or `None` if it was
+    /// already popped or removed.
+    pub fn remove(&mut self, handle: Handle) -> Option<(K, V)> {
+        self.entry(handle)?;
+        self.take(handle.index)
+    }
+
+    /// The current top key. All keys pushed onto the heap must be smaller than
+    /// this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.top
+    }
+
+    /// Returns the number of elements in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no elements in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator of all handles, keys and values in the heap in
+    /// arbitrary order.
+    pub fn iter(&self) -> IndexIter<'_, K, V> {
+        IndexIter {
+            slots: self.slots.iter().enumerate(),
+            size: self.len,
+        }
+    }
+
+    fn assert_below_top(&self, key: K) {
+        if let Some(top) = self.top {
+            assert!(key <= top, "Key must be lower or equal to current top key");
+        }
+    }
+
+    fn entry(&self, handle: Handle) -> Option<&Entry<K, V>> {
+        match self.slots.get(handle.index) {
+            Some(slot) if slot.generation == handle.generation => slot.entry.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The bucket of `key` at the current top key.
+    fn bucket_of(&self, key: K) -> usize {
+        match self.top {
+            Some(top) => key.radix_distance(&top) as usize,
+            None => self.buckets.len() - 1,
+        }
+    }
+
+    /// Adds the entry of slot `index` to the bucket of its key.
+    fn insert(&mut self, index: usize) {
+        let key = match &self.slots[index].entry {
+            Some(entry) => entry.key,
+            None => return,
+        };
+
+        let bucket = self.bucket_of(key);
+        let pos = self.buckets[bucket].len();
+        self.buckets[bucket].push(index);
+
+        if let Some(entry) = &mut self.slots[index].entry {
+            entry.bucket = bucket;
+            entry.pos = pos;
+        }
+    }
+
+    /// Removes the entry of slot `index` from its bucket, moving the last
+    /// entry of the bucket into its place.
+    fn unlink(&mut self, index: usize) {
+        let (bucket, pos) = match &self.slots[index].entry {
+            Some(entry) => (entry.bucket, entry.pos),
+            None => return,
+        };
+
+        self.buckets[bucket].swap_remove(pos);
+
+        if let Some(&moved) = self.buckets[bucket].get(pos) {
+            if let Some(entry) = &mut self.slots[moved].entry {
+                entry.pos = pos;
+            }
+        }
+    }
+
+    /// Removes the entry of slot `index` from the heap and frees the slot.
+    fn take(&mut self, index: usize) -> Option<(K, V)> {
+        self.unlink(index);
+
+        let slot = &mut self.slots[index];
+        let entry = slot.entry.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(index);
+        self.len -= 1;
+
+        Some((entry.key, entry.value))
+    }
+
+    /// Sets the top key to the greatest key, and moves the entries of the
+    /// bucket it was in closer to it.
+    fn constrain(&mut self) {
+        let initial = self.buckets.len() - 1;
+
+        let bucket = if self.top.is_some() {
+            match self.buckets[..initial]
+                .iter()
+                .position(|bucket| !bucket.is_empty())
+            {
+                None | Some(0) => return,
+                Some(bucket) => bucket,
+            }
+        } else if !self.buckets[initial].is_empty() {
+            initial
+        } else {
+            return;
+        };
+
+        let repush = core::mem::take(&mut self.buckets[bucket]);
+        self.top = repush
+            .iter()
+            .filter_map(|&index| self.slots[index].entry.as_ref())
+            .map(|entry| entry.key)
+            .max();
+
+        for &index in &repush {
+            self.insert(index);
+        }
+
+        // Keep the allocation of the emptied bucket for reuse.
+        let mut emptied = repush;
+        emptied.clear();
+        if self.buckets[bucket].is_empty() {
+            self.buckets[bucket] = emptied;
+        }
+    }
+}
+
+/// An iterator over the entries of a [`RadixIndexHeapMap`].
+///
+/// This `struct` is created by [`RadixIndexHeapMap::iter`].
+#[derive(Clone)]
+pub struct IndexIter<'a, K, V> {
+    slots: core::iter::Enumerate<core::slice::Iter<'a, Slot<K, V>>>,
+    size: usize,
+}
+
+impl<'a, K, V> Iterator for IndexIter<'a, K, V> {
+    type Item = (Handle, &'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in &mut self.slots {
+            if let Some(entry) = &slot.entry {
+                self.size -= 1;
+
+                let handle = Handle {
+                    index,
+                    generation: slot.generation,
+                };
+                return Some((handle, &entry.key, &entry.value));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IndexIter<'a, K, V> {}
+
+impl<'a, K, V> core::iter::FusedIterator for IndexIter<'a, K, V> {}
+
+impl<K: Radix + Ord + Copy, V> Default for RadixIndexHeapMap<K, V> {
+    fn default() -> RadixIndexHeapMap<K, V> {
+        RadixIndexHeapMap::new()
+    }
+}
+
+impl<K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug> fmt::Debug for RadixIndexHeapMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(|(_, key, value)| (key, value)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::{quickcheck, TestResult};
+    use super::{Handle, RadixIndexHeapMap};
+    use std::cmp::Reverse;
+
+    #[derive(Clone, Copy, Debug)]
+    enum Op {
+        Push(u8),
+        Pop,
+        Update(usize, u8),
+        Remove(usize),
+    }
+
+    impl quickcheck::Arbitrary for Op {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Op {
+            match u8::arbitrary(g) % 4 {
+                0 => Op::Pop,
+                1 => Op::Update(usize::arbitrary(g), u8::arbitrary(g)),
+                2 => Op::Remove(usize::arbitrary(g)),
+                _ => Op::Push(u8::arbitrary(g)),
+            }
+        }
+    }
+
+    #[test]
+    fn matches_model() {
+        fn prop(ops: Vec<Op>) -> TestResult {
+            let mut heap = RadixIndexHeapMap::new();
+            let mut model: Vec<(Handle, u8, usize)> = Vec::new();
+            let mut handles = Vec::new();
+
+            for (id, op) in ops.into_iter().enumerate() {
+                let top = heap.top().unwrap_or(u8::MAX);
+
+                match op {
+                    Op::Push(key) => {
+                        let key = key.min(top);
+                        let handle = heap.push(key, id);
+                        model.push((handle, key, id));
+                        handles.push(handle);
+                    }
+                    Op::Pop => {
+                        let max = model.iter().map(|&(_, key, _)| key).max();
+                        let popped = heap.pop();
+
+                        if popped.map(|(key, _)| key) != max {
+                            return TestResult::failed();
+                        }
+                        if let Some((_, id)) = popped {
+                            model.retain(|&(_, _, i)| i != id);
+                        }
+                    }
+                    Op::Update(i, key) if !handles.is_empty() => {
+                        let handle = handles[i % handles.len()];
+                        let key = key.min(top);
+                        let expected = model.iter_mut().find(|(h, _, _)| *h == handle);
+                        let old = heap.update_key(handle, key);
+
+                        match expected {
+                            Some(entry) if old == Some(entry.1) => entry.1 = key,
+                            None if old.is_none() => {}
+                            _ => return TestResult::failed(),
+                        }
+                    }
+                    Op::Remove(i) if !handles.is_empty() => {
+                        let handle = handles[i % handles.len()];
+                        let expected = model.iter().position(|(h, _, _)| *h == handle);
+                        let removed = heap.remove(handle).map(|(_, id)| id);
+
+                        if removed != expected.map(|i| model.remove(i).2) {
+                            return TestResult::failed();
+                        }
+                    }
+                    _ => {}
+                }
+
+                if heap.len() != model.len()
+                    || model
+                        .iter()
+                        .any(|&(handle, key, id)| heap.get(handle) != Some((&key, &id)))
+                {
+                    return TestResult::failed();
+                }
+            }
+
+            TestResult::passed()
+        }
+
+        quickcheck(prop as fn(Vec<Op>) -> TestResult);
+    }
+
+    #[test]
+    fn dijkstra() {
+        // A path 0 -> 1 -> 2 that is cheaper than the direct edge 0 -> 2.
+        let edges = [vec![(1, 2u32), (2, 10)], vec![(2, 3)], vec![]];
+        let mut handles = [None; 3];
+        let mut dist = [u32::MAX; 3];
+        let mut heap = RadixIndexHeapMap::new();
+
+        dist[0] = 0;
+        handles[0] = Some(heap.push(Reverse(0), 0));
+
+        while let Some((Reverse(d), node)) = heap.pop() {
+            for &(next, cost) in &edges[node] {
+                if d + cost < dist[next] {
+                    dist[next] = d + cost;
+
+                    match handles[next] {
+                        Some(handle) if heap.contains(handle) => {
+                            heap.update_key(handle, Reverse(d + cost));
+                        }
+                        _ => handles[next] = Some(heap.push(Reverse(d + cost), next)),
+                    }
+                }
+            }
+        }
+
+        assert_eq!(dist, [0, 2, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn update_above_top() {
+        let mut heap = RadixIndexHeapMap::new();
+        let a = heap.push(3u8, ());
+        heap.push(5, ());
+        heap.pop();
+        heap.update_key(a, 6);
+    }
+}
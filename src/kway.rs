@@ -0,0 +1,145 @@
+use crate::{Radix, RadixHeapMap};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// An iterator merging streams sorted by decreasing key into one stream
+/// sorted by decreasing key, such as the runs of an external sort.
+///
+/// The heap holds the next entry of every stream. Each pop refills the heap
+/// from the stream the popped entry came from, and as that stream is sorted,
+/// its next key is never larger than the one just popped, so the heap stays
+/// monotone. Merging `n` entries from `k` streams takes `O(n log C)` time,
+/// where `C` is the range of the keys, no matter how large `k` is.
+///
+/// Entries with equal keys may come from their streams in any order.
+///
+/// ```
+/// use radix_heap::KWayMerge;
+///
+/// let runs = vec![vec![(9u32, 'a'), (4, 'b')], vec![(7, 'c')], vec![(8, 'd'), (1, 'e')]];
+/// let merged: String = KWayMerge::new(runs).map(|(_, v)| v).collect();
+/// assert_eq!(merged, "adcbe");
+/// ```
+///
+/// Panics
+/// ------
+/// Iterating panics if a stream yields a key larger than the key before it.
+pub struct KWayMerge<K, V, I> {
+    heap: RadixHeapMap<K, (V, usize)>,
+    streams: Vec<I>,
+}
+
+impl<K, V, I> KWayMerge<K, V, I>
+where
+    K: Radix + Ord + Clone,
+    I: Iterator<Item = (K, V)>,
+{
+    /// Create an iterator merging `streams`, each sorted by decreasing key.
+    pub fn new<S>(streams: S) -> KWayMerge<K, V, I>
+    where
+        S: IntoIterator,
+        S::Item: IntoIterator<IntoIter = I>,
+    {
+        let mut heap = RadixHeapMap::new();
+        let streams: Vec<I> = streams
+            .into_iter()
+            .enumerate()
+            .map(|(i, stream)| {
+                let mut stream = stream.into_iter();
+                if let Some((key, value)) = stream.next() {
+                    heap.push(key, (value, i));
+                }
+                stream
+            })
+            .collect();
+
+        KWayMerge { heap, streams }
+    }
+
+    /// Returns the number of streams being merged, including exhausted ones.
+    #[inline]
+    pub fn streams(&self) -> usize {
+        self.streams.len()
+    }
+}
+
+impl<K, V, I> Iterator for KWayMerge<K, V, I>
+where
+    K: Radix + Ord + Clone,
+    I: Iterator<Item = (K, V)>,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        let (key, (value, i)) = self.heap.pop()?;
+
+        // A stream is only advanced after its entry is popped, so once it
+        // returns `None` it is never polled again.
+        if let Some((next, next_value)) = self.streams[i].next() {
+            if self.heap.try_push(next, (next_value, i)).is_err() {
+                panic!("Stream {} is not sorted by decreasing key", i);
+            }
+        }
+
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.streams.iter().fold(
+            (self.heap.len(), Some(self.heap.len())),
+            |(lo, hi), stream| {
+                let (stream_lo, stream_hi) = stream.size_hint();
+                (
+                    lo.saturating_add(stream_lo),
+                    hi.zip(stream_hi).and_then(|(a, b)| a.checked_add(b)),
+                )
+            },
+        )
+    }
+}
+
+impl<K, V, I> FusedIterator for KWayMerge<K, V, I>
+where
+    K: Radix + Ord + Clone,
+    I: Iterator<Item = (K, V)>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::KWayMerge;
+
+    #[test]
+    fn merges_runs() {
+        fn prop(runs: Vec<Vec<i16>>) -> bool {
+            let runs: Vec<Vec<(i16, usize)>> = runs
+                .into_iter()
+                .enumerate()
+                .map(|(i, mut run)| {
+                    run.sort_unstable_by(|a, b| b.cmp(a));
+                    run.into_iter().map(|k| (k, i)).collect()
+                })
+                .collect();
+
+            let mut expected: Vec<i16> = runs.iter().flatten().map(|&(k, _)| k).collect();
+            expected.sort_unstable_by(|a, b| b.cmp(a));
+
+            let merge = KWayMerge::new(runs);
+            let hint = merge.size_hint();
+            let merged: Vec<i16> = merge.map(|(k, _)| k).collect();
+
+            hint == (expected.len(), Some(expected.len())) && merged == expected
+        }
+
+        quickcheck(prop as fn(Vec<Vec<i16>>) -> bool);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unsorted_stream() {
+        KWayMerge::new(vec![vec![(1u8, ()), (2, ())]]).for_each(drop);
+    }
+}
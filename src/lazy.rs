@@ -0,0 +1,245 @@
+use crate::{Radix, RadixHeapMap};
+use core::fmt;
+
+/// A radix heap that defers redistributing a bucket until it is needed.
+///
+/// When the bucket of the top key runs empty, a [`RadixHeapMap`] finds the
+/// greatest key of the next non-empty bucket and moves every item of that
+/// bucket closer to the top. This heap only takes out the greatest item and
+/// leaves the others where they are. They are moved the next time the bucket
+/// of the top key runs empty, which costs the same as moving them right away,
+/// but is skipped entirely if the heap is cleared or dropped first, such as
+/// when a search stops at its goal.
+///
+/// The price is a check for a deferred bucket on every pop, and a second pass
+/// over its items when they are moved, so unless heaps are often dropped while
+/// full this is slower than a [`RadixHeapMap`]. Pops of equal
+/// keys are in no particular order.
+///
+/// ```
+/// let mut heap = radix_heap::LazyRadixHeapMap::new();
+/// heap.extend(vec![(2u32, 'a'), (9, 'b'), (5, 'c')]);
+///
+/// assert_eq!(heap.pop(), Some((9, 'b')));
+/// heap.push(7, 'd');
+/// assert_eq!(heap.pop(), Some((7, 'd')));
+/// assert_eq!(heap.pop(), Some((5, 'c')));
+/// assert_eq!(heap.pop(), Some((2, 'a')));
+/// ```
+#[derive(Clone)]
+pub struct LazyRadixHeapMap<K, V> {
+    heap: RadixHeapMap<K, V>,
+
+    /// The bucket whose items have not been moved since the top key was
+    /// raised out of it, or 0 if there is none.
+    deferred: usize,
+}
+
+impl<K: Radix + Ord + Clone, V> LazyRadixHeapMap<K, V> {
+    /// Create an empty `LazyRadixHeapMap`.
+    pub fn new() -> LazyRadixHeapMap<K, V> {
+        LazyRadixHeapMap {
+            heap: RadixHeapMap::new(),
+            deferred: 0,
+        }
+    }
+
+    /// Create an empty `LazyRadixHeapMap` with the top key set to a specific
+    /// value.
+    pub fn new_at(top: K) -> LazyRadixHeapMap<K, V> {
+        LazyRadixHeapMap {
+            heap: RadixHeapMap::new_at(top),
+            deferred: 0,
+        }
+    }
+
+    /// Pushes a new key value pair onto the heap.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        self.heap.push(key, value);
+    }
+
+    /// Removes the greatest element from the heap and returns it, or `None` if
+    /// empty. This sets the top key to the extracted key.
+    ///
+    /// If the bucket of the top key is empty, any deferred bucket is moved
+    /// first, and the greatest item is then taken out of the next non-empty
+    /// bucket, deferring the rest of it.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.heap.buckets[0].is_empty() && self.heap.top.is_some() {
+            return self.pop_deferring();
+        }
+
+        self.heap.pop()
+    }
+
+    /// Returns the greatest key without removing it, or `None` if empty.
+    pub fn peek_key(&mut self) -> Option<K> {
+        if !self.heap.buckets[0].is_empty() || self.heap.top.is_none() {
+            return self.heap.peek_key();
+        }
+
+        self.settle();
+        self.heap
+            .buckets
+            .iter()
+            .find(|bucket| !bucket.is_empty())
+            .and_then(|bucket| bucket.iter().map(|(key, _)| key).max())
+            .cloned()
+    }
+
+    /// The current top key. All keys pushed onto the heap must be smaller
+    /// than this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.heap.top()
+    }
+
+    /// Returns the number of elements in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if there are no elements in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Drops all items from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.deferred = 0;
+    }
+
+    /// Returns the underlying heap, with the items of the deferred bucket
+    /// moved.
+    pub fn into_map(mut self) -> RadixHeapMap<K, V> {
+        self.settle();
+        self.heap
+    }
+
+    /// Moves the items of the deferred bucket to the buckets of their
+    /// distances to the top key.
+    fn settle(&mut self) {
+        let index = core::mem::replace(&mut self.deferred, 0);
+        if index == 0 {
+            return;
+        }
+
+        let heap = &mut self.heap;
+        let top = heap.top.as_ref().expect("Deferred bucket without top key");
+        let (buckets, rest) = heap.buckets.split_at_mut(index);
+        let mut repush = core::mem::take(&mut rest[0]);
+
+        // Items pushed since the bucket was deferred are at the same distance
+        // as before, so only the deferred items, which are closer, move down.
+        let moved = repush.len();
+        for (key, value) in repush.drain(..) {
+            match key.radix_distance(top) as usize {
+                distance if distance == index => rest[0].push((key, value)),
+                distance => buckets[distance].push((key, value)),
+            }
+        }
+
+        heap.stats
+            .record_redistribute(moved, &heap.buckets[..=index]);
+    }
+
+    /// Pops the greatest item of the first non-empty bucket, deferring the
+    /// others, when the bucket of the top key is empty.
+    fn pop_deferring(&mut self) -> Option<(K, V)> {
+        self.settle();
+
+        let heap = &mut self.heap;
+        let index = heap.buckets.iter().position(|bucket| !bucket.is_empty())?;
+        if index == 0 {
+            return heap.pop();
+        }
+
+        let bucket = &mut heap.buckets[index];
+
+        let max = (0..bucket.len())
+            .max_by(|&a, &b| bucket[a].0.cmp(&bucket[b].0))
+            .expect("Expected non-empty bucket");
+        let (key, value) = bucket.swap_remove(max);
+
+        self.deferred = index;
+        heap.top = Some(key.clone());
+        heap.len -= 1;
+        heap.stats.record_pop();
+        heap.hooks.pop(&key);
+        Some((key, value))
+    }
+}
+
+impl<K: Radix + Ord + Clone, V> Default for LazyRadixHeapMap<K, V> {
+    fn default() -> LazyRadixHeapMap<K, V> {
+        LazyRadixHeapMap::new()
+    }
+}
+
+impl<K: Radix + Ord + Clone, V> Extend<(K, V)> for LazyRadixHeapMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.heap.extend(iter);
+    }
+}
+
+impl<K: Radix + Ord + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for LazyRadixHeapMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.heap.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::LazyRadixHeapMap;
+    use crate::RadixHeapMap;
+
+    #[test]
+    fn matches_radix_heap() {
+        fn prop(initial: Vec<i16>, ops: Vec<Option<u8>>) -> bool {
+            let mut lazy = LazyRadixHeapMap::new();
+            let mut heap = RadixHeapMap::new();
+            lazy.extend(initial.iter().map(|&k| (k, ())));
+            heap.extend(initial.iter().map(|&k| (k, ())));
+
+            for op in ops {
+                match (op, heap.top()) {
+                    (Some(step), Some(top)) => {
+                        let key = top.saturating_sub(step as i16);
+                        lazy.push(key, ());
+                        heap.push(key, ());
+                    }
+                    (Some(_), None) => {}
+                    (None, _) => {
+                        if lazy.peek_key() != heap.peek_key() || lazy.pop() != heap.pop() {
+                            return false;
+                        }
+                    }
+                }
+
+                if lazy.len() != heap.len() || lazy.top() != heap.top() {
+                    return false;
+                }
+            }
+
+            let mut settled = lazy.clone().into_map();
+            let expected: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+
+            std::iter::from_fn(|| lazy.pop()).eq(expected.iter().copied())
+                && std::iter::from_fn(|| settled.pop()).eq(expected)
+        }
+
+        quickcheck(prop as fn(Vec<i16>, Vec<Option<u8>>) -> bool);
+    }
+}
@@ -1,11 +1,184 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
-use std::{
-    cmp::Reverse, default::Default, fmt, iter::FromIterator, iter::FusedIterator, num::Wrapping,
+extern crate alloc;
+
+use alloc::{collections::TryReserveError, vec::Vec};
+use core::{
+    cmp::Reverse,
+    default::Default,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FromIterator,
+    iter::FusedIterator,
+    num::Wrapping,
+    ops::{Add, Bound, Deref, DerefMut, RangeBounds, Sub},
 };
 
-type Bucket<K, V> = Vec<(K, V)>;
+mod aging;
+#[cfg(feature = "algorithms")]
+pub mod algorithms;
+#[cfg(any(feature = "quickcheck", feature = "proptest"))]
+mod arbitrary;
+mod bounded;
+mod bucket;
+mod by;
+mod bytes;
+mod bytewise;
+mod compact;
+#[cfg(feature = "compare")]
+pub mod compare;
+#[cfg(feature = "concurrent")]
+mod concurrent;
+mod counted;
+mod deadline;
+#[cfg(feature = "std")]
+mod dedup;
+mod diagnostics;
+mod dial;
+#[cfg(feature = "dimacs")]
+pub mod dimacs;
+mod drain;
+mod dump;
+mod edf;
+#[cfg(feature = "embedded")]
+pub mod embedded;
+mod fair;
+mod float;
+#[cfg(feature = "grid")]
+pub mod grid;
+mod indexed;
+mod kway;
+mod lazy;
+mod median;
+mod min;
+mod monotone;
+mod observer;
+pub mod radix;
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+mod relaxed;
+mod reorder;
+mod retry;
+#[cfg(feature = "std")]
+mod scaled;
+mod seen;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod serial;
+mod set;
+mod stamped;
+mod stats;
+#[cfg(feature = "std")]
+mod sync;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(kani)]
+mod verification;
+
+pub use aging::AgingHeap;
+pub use bounded::BoundedRadixHeapMap;
+pub use by::RadixHeapBy;
+pub use bytes::BoundedBytes;
+pub use bytewise::{ByteRadixHeapMap, RadixBytes};
+pub use compact::{CompactIter, CompactKey, CompactRadixHeapMap};
+#[cfg(feature = "concurrent")]
+pub use concurrent::ConcurrentRadixHeap;
+pub use counted::Counted;
+pub use deadline::{AdvanceTo, DeadlineQueue, PastDeadline};
+#[cfg(feature = "std")]
+pub use dedup::DedupRadixHeapMap;
+pub use dial::{BucketKey, BucketQueue};
+pub use drain::{Drain, DrainBudget, DrainSorted, DrainUntil, PopTies};
+pub use edf::{EdfScheduler, Lateness};
+pub use fair::FairScheduler;
+pub use float::{RadixF32, RadixF64};
+pub use indexed::{Handle, IndexIter, RadixIndexHeapMap};
+pub use kway::KWayMerge;
+pub use lazy::LazyRadixHeapMap;
+pub use median::RunningMedian;
+pub use min::{MinIter, RadixMinHeapMap};
+pub use monotone::MonotonePriorityQueue;
+#[cfg(feature = "observer")]
+pub use observer::Event;
+/// Derives [`Radix`](trait@Radix) for a struct, comparing fields in
+/// declaration order like a tuple.
+///
+/// This matches the order of `#[derive(PartialOrd, Ord)]`, so a key can
+/// derive all three. Every field must implement `Radix`, and `RADIX_BITS` is
+/// the sum of theirs. Enums are only supported if they have exactly one
+/// variant.
+///
+/// ```
+/// use radix_heap::{Radix, RadixHeapMap};
+///
+/// #[derive(Radix, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Cost {
+///     hops: u16,
+///     dist: u32,
+/// }
+///
+/// assert_eq!(Cost::RADIX_BITS, 48);
+///
+/// let mut heap = RadixHeapMap::new();
+/// heap.push(Cost { hops: 2, dist: 10 }, 'a');
+/// heap.push(Cost { hops: 2, dist: 30 }, 'b');
+/// heap.push(Cost { hops: 1, dist: 50 }, 'c');
+/// assert_eq!(heap.pop().map(|(_, v)| v), Some('b'));
+/// ```
+///
+/// Enums with more than one variant are rejected:
+///
+/// ```compile_fail
+/// #[derive(radix_heap::Radix)]
+/// enum Key {
+///     A(u8),
+///     B(u8),
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use radix_heap_derive::Radix;
+#[cfg(feature = "rayon")]
+pub use rayon_impl::{ParIter, ParValues};
+pub use relaxed::RelaxedRadixHeapMap;
+pub use reorder::{Emit, LateEvent, ReorderBuffer};
+pub use retry::RetryQueue;
+#[cfg(feature = "std")]
+pub use scaled::{OnOverflow, Rounding, Scale, ScaleError, ScaledKey};
+pub use serial::Serial;
+pub use set::RadixHeapSet;
+pub use stamped::{Stamped, Stamper};
+#[cfg(feature = "stats")]
+pub use stats::Stats;
+#[cfg(feature = "std")]
+pub use sync::{SyncRadixQueue, WouldBlock};
+
+use bucket::{Bucket, BucketIntoIter};
+
+/// Rounds a non-negative float to the nearest integer, with ties rounding up.
+/// `f64::round` is not available without `std`.
+fn round_usize(x: f64) -> usize {
+    let floor = x as usize;
+
+    if x - floor as f64 >= 0.5 {
+        floor + 1
+    } else {
+        floor
+    }
+}
+
+/// Rounds a non-negative float up to an integer. `f64::ceil` is not available
+/// without `std`.
+pub(crate) fn ceil_usize(x: f64) -> usize {
+    let floor = x as usize;
+
+    if (floor as f64) < x {
+        floor + 1
+    } else {
+        floor
+    }
+}
 
 /// A montone priority queue implemented using a radix heap.
 ///
@@ -13,11 +186,14 @@ type Bucket<K, V> = Vec<(K, V)>;
 ///
 /// See the [module documentation](index.html) for more information.
 ///
+/// Keys only need to be `Clone`. They are moved, not cloned, as they move
+/// between buckets, while methods returning a key by value, such as
+/// [`top`](RadixHeapMap::top), clone it.
+///
 /// It is a logic error for a key to be modified in such a way that the
 /// item's ordering relative to any other item, as determined by the `Ord`
 /// trait, changes while it is in the heap. This is normally only possible
 /// through `Cell`, `RefCell`, global state, I/O, or unsafe code.
-#[derive(Clone)]
 pub struct RadixHeapMap<K, V> {
     len: usize,
 
@@ -32,9 +208,32 @@ pub struct RadixHeapMap<K, V> {
 
     /// The initial entries before a top key is found.
     initial: Bucket<K, V>,
+
+    /// Whether to reset the top key when the last item is popped.
+    auto_rebase: bool,
+
+    /// The maximum number of items in each bucket for
+    /// [`push_within_capacity`](RadixHeapMap::push_within_capacity).
+    bucket_capacity: Option<usize>,
+
+    /// The smallest and largest keys pushed since the heap was last cleared,
+    /// which are empty unless the `extremes` feature is enabled.
+    seen: seen::Seen<K>,
+
+    /// Operation counters, which are empty unless the `stats` feature is
+    /// enabled.
+    stats: stats::Stats,
+
+    /// Warnings about poorly matched key encodings, which are only emitted if
+    /// the `log` feature is enabled.
+    diagnostics: diagnostics::Diagnostics,
+
+    /// The observer, which is only called if the `observer` feature is
+    /// enabled.
+    hooks: observer::Hooks<K>,
 }
 
-impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
+impl<K: Radix + Ord + Clone, V> RadixHeapMap<K, V> {
     /// Create an empty `RadixHeapMap`
     pub fn new() -> RadixHeapMap<K, V> {
         RadixHeapMap {
@@ -42,6 +241,12 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
             top: None,
             buckets: (0..=K::RADIX_BITS).map(|_| Bucket::default()).collect(),
             initial: Bucket::default(),
+            auto_rebase: false,
+            bucket_capacity: None,
+            seen: seen::Seen::default(),
+            stats: stats::Stats::default(),
+            diagnostics: diagnostics::Diagnostics::default(),
+            hooks: observer::Hooks::default(),
         }
     }
 
@@ -56,13 +261,174 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
             top: Some(top),
             buckets: (0..=K::RADIX_BITS).map(|_| Bucket::default()).collect(),
             initial: Bucket::default(),
+            auto_rebase: false,
+            bucket_capacity: None,
+            seen: seen::Seen::default(),
+            stats: stats::Stats::default(),
+            diagnostics: diagnostics::Diagnostics::default(),
+            hooks: observer::Hooks::default(),
+        }
+    }
+
+    /// Create an empty `RadixHeapMap` with memory for at least `capacity`
+    /// items allocated up front.
+    ///
+    /// Without a top key, pushed items are kept in a single initial bucket
+    /// until the first pop, so the memory is allocated for that bucket.
+    pub fn with_capacity(capacity: usize) -> RadixHeapMap<K, V> {
+        let mut heap = RadixHeapMap::new();
+        heap.initial.reserve_exact(capacity);
+        heap
+    }
+
+    /// Create an empty `RadixHeapMap` with the top key set to a specific
+    /// value and memory for `capacity` items allocated up front.
+    ///
+    /// Where pushed items land depends on their keys, so the memory is split
+    /// evenly across the buckets, with any remainder going to the buckets
+    /// closest to the top key. To make sure any `n` items can be pushed
+    /// without allocating, use [`reserve`](RadixHeapMap::reserve) instead.
+    pub fn new_at_with_capacity(top: K, capacity: usize) -> RadixHeapMap<K, V> {
+        let mut heap = RadixHeapMap::new_at(top);
+        let count = heap.buckets.len();
+
+        for (i, bucket) in heap.buckets.iter_mut().enumerate() {
+            let extra = if i < capacity % count { 1 } else { 0 };
+            bucket.reserve_exact(capacity / count + extra);
+        }
+
+        heap
+    }
+
+    /// Create a `RadixHeapMap` with the top key set to `top`, holding the
+    /// items of `iter`.
+    ///
+    /// Unlike collecting into a heap without a top key, which keeps the items
+    /// aside until the first pop sorts them into buckets, this puts every
+    /// item into its final bucket as it is pushed.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if any key is larger than `top`.
+    ///
+    /// ```
+    /// let heap = radix_heap::RadixHeapMap::from_iter_at(8u8, vec![(8, 'a'), (1, 'b')]);
+    ///
+    /// assert_eq!(heap.top(), Some(8));
+    /// assert_eq!(heap.bucket(4), &[(1, 'b')]);
+    /// ```
+    pub fn from_iter_at<I>(top: K, iter: I) -> RadixHeapMap<K, V>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut heap = RadixHeapMap::new_at(top);
+        heap.extend(iter);
+        heap
+    }
+
+    /// Create a `RadixHeapMap` from pairs with keys in descending order,
+    /// setting the top key to the first key.
+    ///
+    /// Every pair goes straight into its final bucket, so the first pop does
+    /// not redistribute anything. See
+    /// [`extend_sorted`](RadixHeapMap::extend_sorted).
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::from_sorted_iter(vec![(9, 'a'), (4, 'b')]);
+    ///
+    /// assert_eq!(heap.top(), Some(9));
+    /// assert_eq!(heap.pop(), Some((9, 'a')));
+    /// ```
+    ///
+    /// Panics
+    /// ------
+    /// Panics in debug builds if the keys are not in descending order.
+    pub fn from_sorted_iter<I>(iter: I) -> RadixHeapMap<K, V>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut heap = RadixHeapMap::new();
+        heap.extend_sorted(iter);
+        heap
+    }
+
+    /// Create a `RadixHeapMap` from a `Vec` of pairs in any order, setting
+    /// the top key to the largest key.
+    ///
+    /// This finds the largest key in one pass over the pairs, and moves each
+    /// pair into its final bucket in a second pass. Collecting into a heap
+    /// instead keeps the pairs aside until the first pop, which then moves
+    /// all of them once more.
+    ///
+    /// ```
+    /// let heap = radix_heap::RadixHeapMap::from_vec(vec![(3u8, 'a'), (8, 'b'), (1, 'c')]);
+    ///
+    /// assert_eq!(heap.top(), Some(8));
+    /// assert_eq!(heap.bucket(0), &[(8, 'b')]);
+    /// assert_eq!(heap.bucket(4), &[(3, 'a'), (1, 'c')]);
+    /// ```
+    pub fn from_vec(vec: Vec<(K, V)>) -> RadixHeapMap<K, V> {
+        let top = match vec.iter().map(|(key, _)| key).max() {
+            Some(top) => top.clone(),
+            None => return RadixHeapMap::new(),
+        };
+
+        let mut heap = RadixHeapMap::new_at(top);
+        for (key, value) in vec {
+            heap.push_unchecked(key, value);
+        }
+        heap
+    }
+
+    /// Create an empty `RadixHeapMap` where every bucket holds at most
+    /// `capacity` items, with memory for all of them allocated up front.
+    ///
+    /// Items pushed with
+    /// [`push_within_capacity`](RadixHeapMap::push_within_capacity) are
+    /// rejected instead of exceeding the capacity of their bucket. As long as
+    /// only that method is used to push, neither pushing nor popping ever
+    /// allocates, which makes the heap usable where allocation is not allowed,
+    /// such as in interrupt handlers. Note that this allocates memory for
+    /// `capacity` items in each of the `K::RADIX_BITS + 2` buckets.
+    ///
+    /// The limit is not carried over to heaps created by
+    /// [`partition_by`](RadixHeapMap::partition_by) or
+    /// [`map_values`](RadixHeapMap::map_values).
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::with_bucket_capacity(1);
+    ///
+    /// assert!(heap.push_within_capacity(5u8, 'a').is_ok());
+    /// assert!(heap.push_within_capacity(4, 'b').is_err());
+    ///
+    /// assert_eq!(heap.pop(), Some((5, 'a')));
+    /// assert!(heap.push_within_capacity(4, 'b').is_ok());
+    /// assert!(heap.push_within_capacity(3, 'c').is_ok());
+    /// assert!(heap.push_within_capacity(2, 'd').is_err());
+    /// ```
+    pub fn with_bucket_capacity(capacity: usize) -> RadixHeapMap<K, V> {
+        let mut heap = RadixHeapMap::new();
+        heap.bucket_capacity = Some(capacity);
+
+        for bucket in core::iter::once(&mut heap.initial).chain(&mut heap.buckets) {
+            bucket.reserve_exact(capacity);
         }
+
+        heap
+    }
+
+    /// The maximum number of items in each bucket, if the heap was created
+    /// with [`with_bucket_capacity`](RadixHeapMap::with_bucket_capacity).
+    #[inline]
+    pub fn bucket_capacity(&self) -> Option<usize> {
+        self.bucket_capacity
     }
 
     /// Drops all items from the `RadixHeapMap` and sets the top key to `None`.
     pub fn clear(&mut self) {
         self.len = 0;
         self.top = None;
+        self.seen = seen::Seen::default();
         self.initial.clear();
 
         for bucket in &mut self.buckets {
@@ -70,6 +436,42 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
         }
     }
 
+    /// Returns an empty heap with the same top key and settings as this one,
+    /// and buckets with the same capacities.
+    ///
+    /// This suits pools of heaps cloned from a template, since the clone does
+    /// not allocate again until it grows past the template. The elements,
+    /// the keys seen and the [`stats`](RadixHeapMap::stats) are not cloned.
+    ///
+    /// ```
+    /// let mut template = radix_heap::RadixHeapMap::new_at(100u32);
+    /// template.reserve(16);
+    /// template.push(42, 'a');
+    ///
+    /// let mut heap = template.clone_empty();
+    /// assert!(heap.is_empty());
+    /// assert_eq!(heap.top(), Some(100));
+    /// assert!(heap.push_within_capacity(7, 'b').is_ok());
+    /// ```
+    pub fn clone_empty(&self) -> RadixHeapMap<K, V> {
+        RadixHeapMap {
+            len: 0,
+            top: self.top.clone(),
+            buckets: self
+                .buckets
+                .iter()
+                .map(|bucket| Bucket::with_capacity(bucket.capacity()))
+                .collect(),
+            initial: Bucket::with_capacity(self.initial.capacity()),
+            auto_rebase: self.auto_rebase,
+            bucket_capacity: self.bucket_capacity,
+            seen: seen::Seen::default(),
+            stats: stats::Stats::default(),
+            diagnostics: diagnostics::Diagnostics::default(),
+            hooks: self.hooks.clone(),
+        }
+    }
+
     /// Drop all items from the `RadixHeapMap` and sets the top key to a
     /// specific value.
     ///
@@ -80,9 +482,117 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
         self.top = Some(top);
     }
 
+    /// Sets whether the top key is reset to `None` when the last item is
+    /// popped, which is off by default.
+    ///
+    /// When a heap empties between bursts of work, the top key otherwise stays
+    /// at the last popped key, so pushing a larger key in the next burst
+    /// panics. With auto-rebase enabled, any key can be pushed onto a heap
+    /// that has drained empty.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.set_auto_rebase(true);
+    ///
+    /// heap.push(2, 'a');
+    /// assert_eq!(heap.pop(), Some((2, 'a')));
+    /// assert_eq!(heap.top(), None);
+    ///
+    /// heap.push(5, 'b');
+    /// assert_eq!(heap.pop(), Some((5, 'b')));
+    /// ```
+    pub fn set_auto_rebase(&mut self, auto_rebase: bool) {
+        self.auto_rebase = auto_rebase;
+    }
+
+    /// Returns true if the top key is reset when the last item is popped. See
+    /// [`set_auto_rebase`](RadixHeapMap::set_auto_rebase).
+    #[inline]
+    pub fn auto_rebase(&self) -> bool {
+        self.auto_rebase
+    }
+
+    /// Operation counters accumulated since the heap was created or
+    /// [`reset_stats`](RadixHeapMap::reset_stats) was called.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(1, 'a'), (5, 'b')]);
+    /// heap.pop();
+    ///
+    /// assert_eq!(heap.stats().pushes(), 2);
+    /// assert_eq!(heap.stats().pops(), 1);
+    /// assert_eq!(heap.stats().redistributed(), 2);
+    /// ```
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// Resets all operation counters to zero.
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&mut self) {
+        self.stats = Stats::default();
+    }
+
+    /// Sets the fraction of the heap that a single constrain may move between
+    /// buckets before a warning is logged, which is 0.5 by default.
+    ///
+    /// With the `log` feature enabled, a warning is logged the first time a
+    /// constrain moves more than this fraction of a large heap, and the first
+    /// time the vast majority of pushes onto a large heap are equal to the top
+    /// key. Both are signs that the key encoding is poorly matched to the
+    /// workload, so most items end up in the same few buckets.
+    #[cfg(feature = "log")]
+    pub fn set_constrain_warning(&mut self, fraction: f64) {
+        self.diagnostics.constrain_fraction = Some(fraction);
+    }
+
+    /// Sets a function to be called on every push, pop and constrain, which
+    /// replaces any previous observer.
+    ///
+    /// This can be used for custom accounting, shadow-checking invariants or
+    /// visualization. The observer is shared between clones of the heap.
+    ///
+    /// ```
+    /// use radix_heap::{Event, RadixHeapMap};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let pops = Arc::new(AtomicUsize::new(0));
+    /// let counter = pops.clone();
+    ///
+    /// let mut heap = RadixHeapMap::new();
+    /// heap.set_observer(move |event| {
+    ///     if let Event::Pop { .. } = event {
+    ///         counter.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// });
+    ///
+    /// heap.push(1, 'a');
+    /// heap.pop();
+    /// assert_eq!(pops.load(Ordering::Relaxed), 1);
+    /// ```
+    #[cfg(feature = "observer")]
+    pub fn set_observer<F>(&mut self, observer: F)
+    where
+        F: Fn(Event<K>) + Send + Sync + 'static,
+    {
+        self.hooks.set(Some(alloc::sync::Arc::new(observer)));
+    }
+
+    /// Removes the observer set by
+    /// [`set_observer`](RadixHeapMap::set_observer).
+    #[cfg(feature = "observer")]
+    pub fn clear_observer(&mut self) {
+        self.hooks.set(None);
+    }
+
     /// Sets the top value to the current maximum key value in the heap
     pub fn constrain(&mut self) {
-        let (buckets, repush) = if self.top.is_some() {
+        let initial = self.top.is_none();
+        let (buckets, repush) = if !initial {
             let index = self.buckets.iter().position(|bucket| !bucket.is_empty());
 
             match index {
@@ -98,17 +608,53 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
             return;
         };
 
-        let top = *repush
+        let top = repush
             .iter()
             .map(|(k, _)| k)
             .max()
-            .expect("Expected non-empty bucket");
+            .expect("Expected non-empty bucket")
+            .clone();
 
+        let moved = repush.len();
+        repush
+            .drain(..)
+            .for_each(|(key, value)| buckets[key.radix_distance(&top) as usize].push((key, value)));
+        self.stats.record_constrain();
+        self.stats.record_redistribute(moved, buckets);
+        self.hooks.constrain(&top, moved);
         self.top = Some(top);
 
+        if !initial {
+            self.diagnostics.record_constrain(moved, self.len);
+        }
+    }
+
+    /// Raises the top key to `top`, which must be larger than the current top.
+    ///
+    /// Buckets closer to the old top than the old top is to the new one all
+    /// end up at the same distance, so they are moved wholesale. Only the
+    /// bucket at exactly that distance needs to be redistributed.
+    pub(crate) fn raise_top(&mut self, top: K) {
+        let index = match &self.top {
+            Some(old) if *old < top => old.radix_distance(&top) as usize,
+            _ => return,
+        };
+
+        let (buckets, rest) = self.buckets.split_at_mut(index);
+        let bucket = &mut rest[0];
+        let mut repush = core::mem::take(bucket);
+
+        for lower in buckets.iter_mut() {
+            bucket.append(lower);
+        }
+
+        let moved = repush.len();
         repush
             .drain(..)
             .for_each(|(key, value)| buckets[key.radix_distance(&top) as usize].push((key, value)));
+        self.stats
+            .record_redistribute(moved, &self.buckets[..=index]);
+        self.top = Some(top);
     }
 
     /// Pushes a new key value pair onto the heap.
@@ -118,495 +664,2658 @@ impl<K: Radix + Ord + Copy, V> RadixHeapMap<K, V> {
     /// Panics if the key is larger than the current top key.
     #[inline]
     pub fn push(&mut self, key: K, value: V) {
-        let bucket = if let Some(top) = self.top {
-            assert!(key <= top, "Key must be lower or equal to current top key");
-            &mut self.buckets[key.radix_distance(&top) as usize]
-        } else {
-            &mut self.initial
-        };
+        if let Some(top) = &self.top {
+            assert!(key <= *top, "Key must be lower or equal to current top key");
+        }
 
-        bucket.push((key, value));
-        self.len += 1;
+        self.push_unchecked(key, value);
     }
 
-    /// Remove the greatest element from the heap and returns it, or `None` if
-    /// empty.
+    /// Pushes a new key value pair onto the heap, or returns it in an error
+    /// if the key is larger than the current top key.
     ///
-    /// If there is a tie between multiple elements, the last inserted element
-    /// will be popped first.
+    /// This suits code where the monotone order depends on the data, such as
+    /// A* with a heuristic that may be inconsistent, so the rejected pair can
+    /// be handled elsewhere instead of panicking.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.push(5, 'a');
+    /// heap.pop();
     ///
-    /// This will set the top key to the extracted key.
+    /// assert_eq!(heap.try_push(3, 'b'), Ok(()));
+    ///
+    /// let error = heap.try_push(7, 'c').unwrap_err();
+    /// assert_eq!((error.key, error.value), (7, 'c'));
+    /// ```
     #[inline]
-    pub fn pop(&mut self) -> Option<(K, V)> {
-        let ret = self.buckets[0].pop().or_else(|| {
-            self.constrain();
-            self.buckets[0].pop()
-        });
-
-        if ret.is_some() {
-            self.len -= 1;
+    pub fn try_push(&mut self, key: K, value: V) -> Result<(), MonotonicityError<K, V>> {
+        match &self.top {
+            Some(top) if key > *top => Err(MonotonicityError { key, value }),
+            _ => {
+                self.push_unchecked(key, value);
+                Ok(())
+            }
         }
-
-        ret
-    }
-
-    /// Returns the number of elements in the heap
-    #[inline]
-    pub fn len(&self) -> usize {
-        self.len
-    }
-
-    /// Returns true if there is no elements in the heap
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
     }
 
-    /// The current top value. All keys pushed onto the heap must be smaller than this value.
-    #[inline]
-    pub fn top(&self) -> Option<K> {
-        self.top
-    }
+    /// Moves all items from `other` into the heap, leaving `other` empty.
+    ///
+    /// If both heaps have a top key and the top key of `other` is no larger
+    /// than this one, the buckets of `other` are moved wholesale, and only the
+    /// one bucket whose distance changes is redistributed. Otherwise the items
+    /// of `other` are pushed one at a time. If this heap has no top key yet,
+    /// all items are moved into its initial bucket.
+    ///
+    /// The top key of the heap does not change, and `other` is left as after
+    /// [`clear`](RadixHeapMap::clear).
+    ///
+    /// ```
+    /// let mut a = radix_heap::RadixHeapMap::new();
+    /// a.extend(vec![(9, 'a'), (4, 'b')]);
+    /// a.pop();
+    ///
+    /// let mut b = radix_heap::RadixHeapMap::new();
+    /// b.extend(vec![(7, 'c'), (2, 'd')]);
+    ///
+    /// a.append(&mut b);
+    /// assert!(b.is_empty());
+    /// assert_eq!(a.pop(), Some((7, 'c')));
+    /// assert_eq!(a.len(), 2);
+    /// ```
+    ///
+    /// Panics
+    /// ------
+    /// Panics if a key in `other` is larger than the current top key, in
+    /// which case neither heap is modified.
+    pub fn append(&mut self, other: &mut RadixHeapMap<K, V>) {
+        match (&self.top, &other.top) {
+            (Some(top), Some(other_top)) if other_top <= top => {
+                // Raising the top key of `other` puts each of its items at the
+                // same distance it has from this top key.
+                other.raise_top(top.clone());
 
-    /// Discards as much additional capacity as possible.
-    pub fn shrink_to_fit(&mut self) {
-        self.initial.shrink_to_fit();
+                for (bucket, moved) in self.buckets.iter_mut().zip(&mut other.buckets) {
+                    bucket.append(moved);
+                }
+            }
+            (Some(top), _) => {
+                assert!(
+                    other.iter().all(|(key, _)| key <= top),
+                    "Key must be lower or equal to current top key"
+                );
 
-        for bucket in &mut self.buckets {
-            bucket.shrink_to_fit();
-        }
-    }
+                for bucket in core::iter::once(&mut other.initial).chain(&mut other.buckets) {
+                    for (key, value) in bucket.drain(..) {
+                        self.push_unchecked(key, value);
+                    }
+                }
 
-    /// Returns an iterator of all key-value pairs in the RadixHeapMap in arbitrary order
-    pub fn iter(&self) -> Iter<K, V> {
-        Iter {
-            cur_bucket: self.initial.iter(),
-            buckets: self.buckets.iter(),
-            size: self.len,
+                other.clear();
+                return;
+            }
+            (None, _) => {
+                for bucket in core::iter::once(&mut other.initial).chain(&mut other.buckets) {
+                    self.initial.append(bucket);
+                }
+            }
         }
-    }
-
-    /// Returns an iterator of all keys in the RadixHeapMap in arbitrary order
-    pub fn keys(&self) -> Keys<K, V> {
-        Keys(self.iter())
-    }
 
-    /// Returns an iterator of all values in the RadixHeapMap in arbitrary order
-    pub fn values(&self) -> Values<K, V> {
-        Values(self.iter())
-    }
-}
+        self.len += other.len;
+        self.seen.merge(core::mem::take(&mut other.seen));
 
-impl<K: Radix + Ord + Copy, V> Default for RadixHeapMap<K, V> {
-    fn default() -> RadixHeapMap<K, V> {
-        RadixHeapMap::new()
+        other.clear();
     }
-}
 
-impl<K: Radix + Ord + Copy, V> FromIterator<(K, V)> for RadixHeapMap<K, V> {
-    fn from_iter<I>(iter: I) -> RadixHeapMap<K, V>
+    /// Pushes pairs with keys in descending order, such as a replayed log of
+    /// popped events.
+    ///
+    /// Only the first key is checked against the top key, and the order of
+    /// the rest is only checked in debug builds. If the heap is empty and has
+    /// no top key, the top key is set to the first key, so that every pair
+    /// goes straight into its final bucket instead of being redistributed by
+    /// the first pop.
+    ///
+    /// It is a logic error for the keys not to be in descending order.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend_sorted(vec![(9, 'a'), (7, 'b'), (7, 'c'), (2, 'd')]);
+    ///
+    /// assert_eq!(heap.top(), Some(9));
+    /// assert_eq!(heap.pop(), Some((9, 'a')));
+    /// assert_eq!(heap.pop(), Some((7, 'c')));
+    /// ```
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the first key is larger than the current top key, or in
+    /// debug builds if the keys are not in descending order.
+    pub fn extend_sorted<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = (K, V)>,
     {
-        let mut heap = RadixHeapMap::new();
+        let mut iter = iter.into_iter();
 
-        for (k, v) in iter {
-            heap.push(k, v);
+        let (key, value) = match iter.next() {
+            Some(first) => first,
+            None => return,
+        };
+
+        if self.top.is_none() && self.len == 0 {
+            self.top = Some(key.clone());
         }
 
-        heap
+        #[cfg(debug_assertions)]
+        let mut previous = key.clone();
+        self.push(key, value);
+
+        for (key, value) in iter {
+            #[cfg(debug_assertions)]
+            {
+                assert!(key <= previous, "Keys must be in descending order");
+                previous = key.clone();
+            }
+
+            self.push_unchecked(key, value);
+        }
     }
-}
 
-impl<K: Radix + Ord + Copy, V> Extend<(K, V)> for RadixHeapMap<K, V> {
-    fn extend<I>(&mut self, iter: I)
+    /// Pushes every pair of `iter`, or none of them if any key is larger than
+    /// the top key.
+    ///
+    /// Pairs are buffered until the whole iterator has been checked, so on
+    /// error the heap is unchanged. The error holds the first rejected pair.
+    /// The pairs before it are dropped, and the iterator is not consumed any
+    /// further. If the heap has no top key, every pair is accepted and nothing
+    /// is buffered.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(10);
+    ///
+    /// let error = heap.try_extend(vec![(3, 'a'), (12, 'b'), (5, 'c')]).unwrap_err();
+    /// assert_eq!((error.key, error.value), (12, 'b'));
+    /// assert!(heap.is_empty());
+    ///
+    /// assert!(heap.try_extend(vec![(3, 'a'), (5, 'c')]).is_ok());
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), MonotonicityError<K, V>>
     where
         I: IntoIterator<Item = (K, V)>,
     {
-        for (k, v) in iter {
-            self.push(k, v);
+        let top = match &self.top {
+            Some(top) => top,
+            None => {
+                self.extend(iter);
+                return Ok(());
+            }
+        };
+
+        let mut accepted = Vec::new();
+
+        for (key, value) in iter {
+            if key > *top {
+                return Err(MonotonicityError { key, value });
+            }
+
+            accepted.push((key, value));
+        }
+
+        for (key, value) in accepted {
+            self.push_unchecked(key, value);
         }
+
+        Ok(())
     }
-}
 
-impl<'a, K: Radix + Ord + Copy + 'a, V: Copy + 'a> Extend<&'a (K, V)> for RadixHeapMap<K, V> {
-    fn extend<I>(&mut self, iter: I)
-    where
-        I: IntoIterator<Item = &'a (K, V)>,
-    {
-        for &(k, v) in iter {
-            self.push(k, v);
+    /// Pushes a key value pair without checking it against the top key.
+    #[inline]
+    fn push_unchecked(&mut self, key: K, value: V) {
+        self.seen.record(&key);
+
+        let bucket = if let Some(top) = &self.top {
+            let distance = key.radix_distance(top);
+            self.diagnostics.record_push(distance);
+            self.hooks.push(&key, Some(distance));
+            &mut self.buckets[distance as usize]
+        } else {
+            self.hooks.push(&key, None);
+            &mut self.initial
+        };
+
+        bucket.push((key, value));
+        self.len += 1;
+        self.stats.record_push(bucket.len());
+    }
+
+    /// Pushes a new key value pair onto the heap, or returns it in an error
+    /// if its bucket is full.
+    ///
+    /// A bucket is full once it holds as many items as the capacity given to
+    /// [`with_bucket_capacity`](RadixHeapMap::with_bucket_capacity), or as
+    /// many items as it has memory allocated for if the heap has no capacity
+    /// limit. Either way, this never allocates.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    pub fn push_within_capacity(&mut self, key: K, value: V) -> Result<(), CapacityError<K, V>> {
+        let bucket = match &self.top {
+            Some(top) => &self.buckets[key.radix_distance(top) as usize],
+            None => &self.initial,
+        };
+
+        if bucket.len() >= self.bucket_capacity.unwrap_or_else(|| bucket.capacity()) {
+            return Err(CapacityError { key, value });
         }
+
+        self.push(key, value);
+        Ok(())
     }
-}
 
-impl<K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug> fmt::Debug for RadixHeapMap<K, V> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_list().entries(self.iter()).finish()
+    /// Pushes a new key value pair onto the heap, or returns it in an error
+    /// if memory for it could not be allocated.
+    ///
+    /// Unlike [`push`](RadixHeapMap::push), which aborts when the allocator
+    /// fails, this reports the failure so the caller can shed load instead.
+    /// Popping can still allocate when items are moved between buckets, which
+    /// [`try_reserve`](RadixHeapMap::try_reserve) can rule out up front.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    pub fn try_push_alloc(&mut self, key: K, value: V) -> Result<(), TryPushError<K, V>> {
+        let bucket = match &self.top {
+            Some(top) => &mut self.buckets[key.radix_distance(top) as usize],
+            None => &mut self.initial,
+        };
+
+        if let Err(error) = bucket::try_reserve(bucket, 1) {
+            return Err(TryPushError { key, value, error });
+        }
+
+        self.push(key, value);
+        Ok(())
     }
-}
 
-/// An owning iterator over key-value pairs in a RadixHeapMap.
-#[derive(Clone)]
-pub struct IntoIter<K, V> {
-    cur_bucket: std::vec::IntoIter<(K, V)>,
-    buckets: std::vec::IntoIter<Bucket<K, V>>,
-    size: usize,
-}
+    /// Remove the greatest element from the heap and returns it, or `None` if
+    /// empty.
+    ///
+    /// If there is a tie between multiple elements, the last inserted element
+    /// will be popped first.
+    ///
+    /// This will set the top key to the extracted key, or to `None` if the
+    /// heap is now empty and [auto-rebase](RadixHeapMap::set_auto_rebase) is
+    /// enabled.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let ret = self.buckets[0].pop().or_else(|| {
+            self.constrain();
+            self.buckets[0].pop()
+        });
 
-impl<K, V> Iterator for IntoIter<K, V> {
-    type Item = (K, V);
+        if let Some((key, _)) = &ret {
+            self.len -= 1;
+            self.stats.record_pop();
+            self.hooks.pop(key);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if let pair @ Some(_) = self.cur_bucket.next() {
-                self.size -= 1;
-                return pair;
-            } else {
-                self.cur_bucket = self.buckets.next()?.into_iter();
+            if self.len == 0 && self.auto_rebase {
+                self.top = None;
             }
         }
+
+        ret
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.size, Some(self.size))
+    /// Returns the greatest element without removing it, or `None` if empty.
+    ///
+    /// This is the element [`pop`](RadixHeapMap::pop) would return. Since
+    /// finding it may set the top key to the greatest key, this requires
+    /// mutable access.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 'a'), (8, 'b'), (5, 'c')]);
+    ///
+    /// assert_eq!(heap.peek(), Some((&8, &'b')));
+    /// assert_eq!(heap.peek_key(), Some(8));
+    /// assert_eq!(heap.peek_value(), Some(&'b'));
+    /// assert_eq!(heap.pop(), Some((8, 'b')));
+    /// ```
+    pub fn peek(&mut self) -> Option<(&K, &V)> {
+        if self.buckets[0].is_empty() {
+            self.constrain();
+        }
+
+        self.buckets[0].last().map(|(key, value)| (key, value))
     }
 
-    fn for_each<F>(self, mut f: F)
-    where
-        F: FnMut(Self::Item),
-    {
-        self.cur_bucket.for_each(&mut f);
-        self.buckets.for_each(|b| b.into_iter().for_each(&mut f));
+    /// Returns the greatest key without removing it, or `None` if empty.
+    ///
+    /// See [`peek`](RadixHeapMap::peek).
+    pub fn peek_key(&mut self) -> Option<K> {
+        self.peek().map(|(key, _)| key.clone())
     }
-}
 
-impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+    /// Returns the value of the greatest element without removing it, or
+    /// `None` if empty.
+    ///
+    /// See [`peek`](RadixHeapMap::peek).
+    pub fn peek_value(&mut self) -> Option<&V> {
+        self.peek().map(|(_, value)| value)
+    }
 
-impl<K, V> FusedIterator for IntoIter<K, V> {}
+    /// Returns all elements tied for the greatest key without removing them,
+    /// or an empty slice if the heap is empty.
+    ///
+    /// These are the elements in bucket 0 after constraining. They are popped
+    /// from the end of the slice, so the last element is the one
+    /// [`peek`](RadixHeapMap::peek) returns. To pop another one first, use
+    /// [`bucket_mut`](RadixHeapMap::bucket_mut) to swap it to the end.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 'a'), (8, 'b'), (8, 'c')]);
+    ///
+    /// assert_eq!(heap.peek_bucket(), &[(8, 'b'), (8, 'c')]);
+    ///
+    /// let best = heap.peek_bucket().iter().position(|&(_, v)| v == 'b').unwrap();
+    /// let last = heap.bucket(0).len() - 1;
+    /// heap.bucket_mut(0).swap(best, last);
+    /// assert_eq!(heap.pop(), Some((8, 'b')));
+    /// ```
+    pub fn peek_bucket(&mut self) -> &[(K, V)] {
+        if self.buckets[0].is_empty() {
+            self.constrain();
+        }
 
-/// An iterator over key-value pairs in a RadixHeapMap.
-#[derive(Clone)]
-pub struct Iter<'a, K, V> {
-    cur_bucket: std::slice::Iter<'a, (K, V)>,
-    buckets: std::slice::Iter<'a, Bucket<K, V>>,
-    size: usize,
-}
+        &self.buckets[0]
+    }
 
-impl<'a, K, V> Iterator for Iter<'a, K, V> {
-    type Item = &'a (K, V);
+    /// Returns a guard giving mutable access to the value of the greatest
+    /// element, or `None` if empty.
+    ///
+    /// The key cannot be changed, since that could move the element to
+    /// another bucket. The element can be removed with [`PeekMut::pop`].
+    /// Like [`peek`](RadixHeapMap::peek), this may set the top key to the
+    /// greatest key.
+    ///
+    /// ```
+    /// use radix_heap::PeekMut;
+    ///
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 1), (8, 2)]);
+    ///
+    /// if let Some(mut top) = heap.peek_mut() {
+    ///     *top += 10;
+    /// }
+    /// assert_eq!(heap.peek(), Some((&8, &12)));
+    ///
+    /// let top = heap.peek_mut().unwrap();
+    /// assert_eq!(PeekMut::pop(top), (8, 12));
+    /// assert_eq!(heap.len(), 1);
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, K, V>> {
+        if self.is_empty() {
+            return None;
+        }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if let pair @ Some(_) = self.cur_bucket.next() {
-                self.size -= 1;
-                return pair;
-            } else {
-                self.cur_bucket = self.buckets.next()?.iter();
-            }
+        if self.buckets[0].is_empty() {
+            self.constrain();
         }
-    }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.size, Some(self.size))
+        Some(PeekMut { heap: self })
     }
 
-    fn for_each<F>(self, mut f: F)
+    /// Removes the greatest element and returns it if `f` returns true for
+    /// it. Returns `None` if empty or if `f` returns false, in which case the
+    /// heap keeps all its elements.
+    ///
+    /// Like [`peek`](RadixHeapMap::peek), this may set the top key to the
+    /// greatest key even if nothing is popped.
+    ///
+    /// ```
+    /// use std::cmp::Reverse;
+    ///
+    /// let mut timers = radix_heap::RadixHeapMap::new();
+    /// timers.extend(vec![(Reverse(10), "a"), (Reverse(20), "b")]);
+    ///
+    /// let now = 15;
+    /// let due = |&Reverse(deadline): &_, _: &_| deadline <= now;
+    /// assert_eq!(timers.pop_if(due), Some((Reverse(10), "a")));
+    /// assert_eq!(timers.pop_if(due), None);
+    /// assert_eq!(timers.len(), 1);
+    /// ```
+    pub fn pop_if<F>(&mut self, f: F) -> Option<(K, V)>
     where
-        F: FnMut(Self::Item),
+        F: FnOnce(&K, &V) -> bool,
     {
-        self.cur_bucket.for_each(&mut f);
-        self.buckets.for_each(|b| b.iter().for_each(&mut f));
+        let (key, value) = self.peek()?;
+
+        if f(key, value) {
+            self.pop()
+        } else {
+            None
+        }
     }
-}
 
-impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+    /// Returns true if the heap contains an element with key `key`.
+    ///
+    /// Only the bucket at the radix distance of `key` from the top key is
+    /// searched, or the initial bucket if there is no top key yet.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(100u32);
+    /// heap.extend(vec![(3, 'a'), (8, 'b')]);
+    ///
+    /// assert!(heap.contains_key(&8));
+    /// assert!(!heap.contains_key(&5));
+    /// ```
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.bucket_of(key)
+            .iter()
+            .any(|bucket| bucket.iter().any(|(k, _)| k == key))
+    }
 
-impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
+    /// Returns the value of an element with key `key`, or `None` if there is
+    /// no such element. Of several elements with the key, this is the one
+    /// that would be popped first.
+    ///
+    /// Like [`contains_key`](RadixHeapMap::contains_key), this only searches
+    /// one bucket.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.bucket_of(key)?
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
 
-/// An iterator over keys in a RadixHeapMap.
-#[derive(Clone)]
-pub struct Keys<'a, K, V>(Iter<'a, K, V>);
+    /// Returns an iterator of the values of all elements with key `key`, in
+    /// arbitrary order.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 'a'), (8, 'b'), (3, 'c')]);
+    ///
+    /// assert_eq!(heap.get(&3), Some(&'c'));
+    /// assert_eq!(heap.get_all(&3).collect::<Vec<_>>(), vec![&'a', &'c']);
+    /// ```
+    pub fn get_all<'a>(&'a self, key: &'a K) -> GetAll<'a, K, V> {
+        GetAll {
+            bucket: self.bucket_of(key).unwrap_or(&[]).iter(),
+            key,
+        }
+    }
 
-impl<'a, K, V> Iterator for Keys<'a, K, V> {
-    type Item = &'a K;
+    /// Removes an element with key `key` and returns it, or `None` if there
+    /// is no such element. Of several elements with the key, this removes the
+    /// one that would be popped first.
+    ///
+    /// Like [`contains_key`](RadixHeapMap::contains_key), this only searches
+    /// one bucket. The top key is left unchanged.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 'a'), (8, 'b'), (3, 'c')]);
+    ///
+    /// assert_eq!(heap.remove_key(&3), Some((3, 'c')));
+    /// assert_eq!(heap.remove_key(&5), None);
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    pub fn remove_key(&mut self, key: &K) -> Option<(K, V)> {
+        let bucket = match &self.top {
+            Some(top) if key > top => return None,
+            Some(top) => &mut self.buckets[key.radix_distance(top) as usize],
+            None => &mut self.initial,
+        };
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().map(|(k, _)| k)
+        // Keep the order of the bucket, so that ties are still popped last
+        // inserted first.
+        let index = bucket.iter().rposition(|(k, _)| k == key)?;
+        let item = bucket.remove(index);
+        self.len -= 1;
+
+        if self.len == 0 && self.auto_rebase {
+            self.top = None;
+        }
+
+        Some(item)
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.0.size_hint()
+    /// References to all elements, sorted by key.
+    fn sorted_entries(&self) -> Vec<&(K, V)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        entries
     }
 
-    fn for_each<F>(self, mut f: F)
+    /// The bucket an element with key `key` would be in, or `None` if the key
+    /// is larger than the top key.
+    fn bucket_of(&self, key: &K) -> Option<&[(K, V)]> {
+        match &self.top {
+            Some(top) if key > top => None,
+            Some(top) => Some(&self.buckets[key.radix_distance(top) as usize]),
+            None => Some(&self.initial),
+        }
+    }
+
+    /// Removes the first element in iteration order for which `f` returns
+    /// true and returns it, or `None` if there is no such element.
+    ///
+    /// This stops at the first match, so it is cheaper than
+    /// [`partition_by`](RadixHeapMap::partition_by) for an occasional
+    /// cancellation, though it still scans the heap in the worst case. The top
+    /// key is left unchanged.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, "a"), (8, "cancelled"), (5, "c")]);
+    ///
+    /// assert_eq!(heap.remove_where(|_, &v| v == "cancelled"), Some((8, "cancelled")));
+    /// assert_eq!(heap.remove_where(|_, &v| v == "cancelled"), None);
+    /// assert_eq!(heap.pop(), Some((5, "c")));
+    /// ```
+    pub fn remove_where<F>(&mut self, mut f: F) -> Option<(K, V)>
     where
-        F: FnMut(Self::Item),
+        F: FnMut(&K, &V) -> bool,
     {
-        self.0.for_each(|(k, _)| f(k))
-    }
-}
+        let (bucket, index) = core::iter::once(&mut self.initial)
+            .chain(&mut self.buckets)
+            .find_map(|bucket| {
+                let index = bucket.iter().position(|(key, value)| f(key, value))?;
+                Some((bucket, index))
+            })?;
 
-impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+        // Keep the order of the bucket, so that ties are still popped last
+        // inserted first.
+        let item = bucket.remove(index);
+        self.len -= 1;
 
-impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+        if self.len == 0 && self.auto_rebase {
+            self.top = None;
+        }
 
-/// An iterator over values in a RadixHeapMap.
-#[derive(Clone)]
-pub struct Values<'a, K, V>(Iter<'a, K, V>);
+        Some(item)
+    }
 
-impl<'a, K, V> Iterator for Values<'a, K, V> {
-    type Item = &'a V;
+    /// Keeps only the elements for which `f` returns true, removing the rest
+    /// in place.
+    ///
+    /// `f` is called once for each element, in iteration order, and can
+    /// modify the values it is given. Elements kept in the same bucket stay in
+    /// the same order, so ties are still popped last inserted first. The top
+    /// key is left unchanged.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 1), (8, 2), (5, 3)]);
+    ///
+    /// heap.retain(|&key, value| {
+    ///     *value *= 10;
+    ///     key != 8
+    /// });
+    ///
+    /// assert_eq!(heap.pop(), Some((5, 30)));
+    /// assert_eq!(heap.len(), 1);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        for bucket in core::iter::once(&mut self.initial).chain(&mut self.buckets) {
+            let mut kept = 0;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().map(|(_, v)| v)
-    }
+            for i in 0..bucket.len() {
+                let (key, value) = &mut bucket[i];
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.0.size_hint()
+                if f(key, value) {
+                    bucket.swap(kept, i);
+                    kept += 1;
+                }
+            }
+
+            self.len -= bucket.len() - kept;
+            bucket.truncate(kept);
+        }
+
+        if self.len == 0 && self.auto_rebase {
+            self.top = None;
+        }
     }
 
-    fn for_each<F>(self, mut f: F)
+    /// Removes all elements with keys in `range` and returns them in order of
+    /// decreasing key.
+    ///
+    /// Keys in a bucket further from the top key are always smaller, so only
+    /// the buckets at the radix distances of the ends of the range are
+    /// filtered. Buckets between them are taken whole, and the rest are not
+    /// looked at. The top key is left unchanged.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(100u32);
+    /// heap.extend(vec![(90, 'a'), (40, 'b'), (60, 'c'), (3, 'd')]);
+    ///
+    /// assert_eq!(heap.pop_range(30..70), vec![(60, 'c'), (40, 'b')]);
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    pub fn pop_range<R>(&mut self, range: R) -> Vec<(K, V)>
     where
-        F: FnMut(Self::Item),
+        R: RangeBounds<K>,
     {
-        self.0.for_each(|(_, v)| f(v))
-    }
-}
+        let mut removed = Vec::new();
+        let filter = |bucket: &mut Bucket<K, V>, removed: &mut Vec<(K, V)>| {
+            let kept: Vec<_> = bucket
+                .drain(..)
+                .filter_map(|item| {
+                    if range.contains(&item.0) {
+                        removed.push(item);
+                        None
+                    } else {
+                        Some(item)
+                    }
+                })
+                .collect();
+            bucket.extend(kept);
+        };
 
-impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+        filter(&mut self.initial, &mut removed);
 
-impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
+        if let Some(top) = &self.top {
+            let near = match range.end_bound() {
+                Bound::Included(end) | Bound::Excluded(end) if end < top => end.radix_distance(top),
+                _ => 0,
+            };
+            let far = match range.start_bound() {
+                Bound::Included(start) | Bound::Excluded(start) => {
+                    start.min(top).radix_distance(top)
+                }
+                Bound::Unbounded => K::RADIX_BITS,
+            };
 
-impl<K: Radix + Ord + Copy, V> IntoIterator for RadixHeapMap<K, V> {
-    type Item = (K, V);
-    type IntoIter = IntoIter<K, V>;
+            for distance in near..=far {
+                let bucket = &mut self.buckets[distance as usize];
 
-    fn into_iter(self) -> Self::IntoIter {
-        IntoIter {
-            cur_bucket: self.initial.into_iter(),
-            buckets: self.buckets.into_iter(),
-            size: self.len,
+                if near < distance && distance < far {
+                    bucket::append(&mut removed, bucket);
+                } else {
+                    filter(bucket, &mut removed);
+                }
+            }
         }
-    }
-}
 
-impl<'a, K: Radix + Ord + Copy, V> IntoIterator for &'a RadixHeapMap<K, V> {
-    type Item = &'a (K, V);
-    type IntoIter = Iter<'a, K, V>;
+        self.len -= removed.len();
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+        if self.len == 0 && self.auto_rebase {
+            self.top = None;
+        }
+
+        removed.sort_by(|(a, _), (b, _)| b.cmp(a));
+        removed
     }
-}
 
-/// A number that can be compared using radix distance
-pub trait Radix {
-    /// The number of high bits in a row that this and `other` has in common
+    /// Moves all elements with keys greater than or equal to `key` into a new
+    /// heap and returns it, leaving the smaller elements in this heap.
     ///
-    /// Eg. the radix similarity of 001001 and 000001 is 2 because they share
-    /// the 2 high bits.
-    fn radix_similarity(&self, other: &Self) -> u32;
+    /// Both heaps keep the top key of this heap. Buckets closer to the top key
+    /// than `key` only hold larger keys, and buckets further away only hold
+    /// smaller keys, so those are moved or kept whole. Only the bucket at the
+    /// radix distance of `key` is split.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(100u32);
+    /// heap.extend(vec![(90, 'a'), (40, 'b'), (60, 'c'), (3, 'd')]);
+    ///
+    /// let mut above = heap.split_off_at(50);
+    /// assert_eq!(above.pop(), Some((90, 'a')));
+    /// assert_eq!(above.pop(), Some((60, 'c')));
+    /// assert_eq!(above.pop(), None);
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    pub fn split_off_at(&mut self, key: K) -> RadixHeapMap<K, V> {
+        let mut other = RadixHeapMap {
+            top: self.top.clone(),
+            auto_rebase: self.auto_rebase,
+            seen: self.seen.clone(),
+            hooks: self.hooks.clone(),
+            ..RadixHeapMap::new()
+        };
 
-    /// Opposite of `radix_similarity`. If `radix_distance` returns 0, then `radix_similarity`
-    /// returns `radix_bits` and vice versa.
-    fn radix_distance(&self, other: &Self) -> u32 {
-        Self::RADIX_BITS - self.radix_similarity(other)
-    }
+        let split = |bucket: &mut Bucket<K, V>, other: &mut Bucket<K, V>| {
+            let kept: Vec<_> = bucket
+                .drain(..)
+                .filter_map(|item| {
+                    if item.0 >= key {
+                        other.push(item);
+                        None
+                    } else {
+                        Some(item)
+                    }
+                })
+                .collect();
+            bucket.extend(kept);
+        };
 
-    /// The value returned by `radix_similarty` if all bits are equal
-    const RADIX_BITS: u32;
-}
+        split(&mut self.initial, &mut other.initial);
 
-macro_rules! radix_wrapper_impl {
-    ($t:ident) => {
-        impl<T: Radix> Radix for $t<T> {
-            #[inline]
-            fn radix_similarity(&self, other: &$t<T>) -> u32 {
-                self.0.radix_similarity(&other.0)
+        // No key in the buckets is larger than the top key, so nothing moves
+        // if `key` is.
+        if let Some(top) = self.top.as_ref().filter(|&top| key <= *top) {
+            let distance = key.radix_distance(top) as usize;
+
+            for (bucket, moved) in self
+                .buckets
+                .iter_mut()
+                .zip(&mut other.buckets)
+                .take(distance)
+            {
+                core::mem::swap(bucket, moved);
             }
 
-            const RADIX_BITS: u32 = T::RADIX_BITS;
+            if let (Some(bucket), Some(moved)) = (
+                self.buckets.get_mut(distance),
+                other.buckets.get_mut(distance),
+            ) {
+                split(bucket, moved);
+            }
         }
-    };
-}
 
-radix_wrapper_impl!(Reverse);
-radix_wrapper_impl!(Wrapping);
+        other.len = other.initial.len()
+            + other
+                .buckets
+                .iter()
+                .map(|bucket| bucket.len())
+                .sum::<usize>();
+        self.len -= other.len;
 
-macro_rules! radix_int_impl {
-    ($t:ty) => {
-        impl Radix for $t {
-            #[inline]
-            fn radix_similarity(&self, other: &$t) -> u32 {
-                (self ^ other).leading_zeros()
+        for heap in [&mut *self, &mut other] {
+            if heap.len == 0 && heap.auto_rebase {
+                heap.top = None;
             }
-
-            const RADIX_BITS: u32 = (std::mem::size_of::<$t>() * 8) as u32;
         }
-    };
-}
 
-radix_int_impl!(i8);
-radix_int_impl!(i16);
-radix_int_impl!(i32);
-radix_int_impl!(i64);
-radix_int_impl!(i128);
-radix_int_impl!(isize);
+        other
+    }
 
-radix_int_impl!(u8);
-radix_int_impl!(u16);
-radix_int_impl!(u32);
-radix_int_impl!(u64);
-radix_int_impl!(u128);
-radix_int_impl!(usize);
+    /// Splits the heap into two, where the first contains the elements for
+    /// which `f` returns true and the second contains the rest.
+    ///
+    /// Both heaps keep the top key of this heap, so elements stay in the same
+    /// buckets and no keys need to be compared.
+    ///
+    /// ```
+    /// let heap: radix_heap::RadixHeapMap<u32, char> =
+    ///     vec![(3, 'a'), (8, 'b'), (5, 'c')].into_iter().collect();
+    ///
+    /// let (mut now, mut later) = heap.partition_by(|&key, _| key > 4);
+    /// assert_eq!(now.pop(), Some((8, 'b')));
+    /// assert_eq!(now.pop(), Some((5, 'c')));
+    /// assert_eq!(later.pop(), Some((3, 'a')));
+    /// ```
+    pub fn partition_by<F>(self, mut f: F) -> (RadixHeapMap<K, V>, RadixHeapMap<K, V>)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut split = |bucket: Bucket<K, V>| -> (Bucket<K, V>, Bucket<K, V>) {
+            bucket.into_iter().partition(|(key, value)| f(key, value))
+        };
 
-#[cfg(feature = "ordered-float")]
-macro_rules! radix_float_impl {
-    ($t:ty, $bits:ty, $wrapper:path) => {
-        impl Radix for $wrapper {
-            #[inline]
-            fn radix_similarity(&self, other: &$wrapper) -> u32 {
-                let self_bits: $bits = self.to_bits();
-                let other_bits: $bits = other.to_bits();
-                self_bits.radix_similarity(&other_bits)
+        let (initial, rest) = split(self.initial);
+        let (buckets, rest_buckets): (Vec<_>, Vec<_>) = self.buckets.into_iter().map(split).unzip();
+
+        let (top, auto_rebase, seen, hooks) = (self.top, self.auto_rebase, self.seen, self.hooks);
+        let heap = |initial: Bucket<K, V>, buckets: Vec<Bucket<K, V>>| {
+            let len = initial.len() + buckets.iter().map(|bucket| bucket.len()).sum::<usize>();
+
+            RadixHeapMap {
+                len,
+                top: if len == 0 && auto_rebase {
+                    None
+                } else {
+                    top.clone()
+                },
+                buckets,
+                initial,
+                auto_rebase,
+                bucket_capacity: None,
+                seen: seen.clone(),
+                stats: stats::Stats::default(),
+                diagnostics: diagnostics::Diagnostics::default(),
+                hooks: hooks.clone(),
             }
+        };
+
+        (heap(initial, buckets), heap(rest, rest_buckets))
+    }
+
+    /// Applies `f` to every key in the heap, including the top key.
+    ///
+    /// `f` must be monotone, so that `a <= b` implies `f(a) <= f(b)`, such as
+    /// adding or subtracting a constant. It is a logic error otherwise.
+    ///
+    /// Buckets where every key keeps its radix distance to the top key are
+    /// left in place, so a constant offset that does not carry across the
+    /// bits of a bucket only rewrites the keys.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(100u32, 'a'), (120, 'b'), (90, 'c')]);
+    /// assert_eq!(heap.pop(), Some((120, 'b')));
+    ///
+    /// heap.map_keys(|key| key - 80);
+    /// assert_eq!(heap.top(), Some(40));
+    /// assert_eq!(heap.pop(), Some((20, 'a')));
+    /// assert_eq!(heap.pop(), Some((10, 'c')));
+    /// ```
+    pub fn map_keys<F>(&mut self, mut f: F)
+    where
+        F: FnMut(K) -> K,
+    {
+        let initial = core::mem::take(&mut self.initial);
+        self.initial = initial
+            .into_iter()
+            .map(|(key, value)| (f(key), value))
+            .collect();
+
+        self.seen = core::mem::take(&mut self.seen).map(&mut f);
 
-            const RADIX_BITS: u32 = <$bits>::RADIX_BITS;
+        let top = match self.top.take() {
+            Some(top) => f(top),
+            None => return,
+        };
+
+        let mut repush = Vec::new();
+
+        for (distance, bucket) in self.buckets.iter_mut().enumerate() {
+            let mut moved = false;
+
+            *bucket = core::mem::take(bucket)
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = f(key);
+                    moved |= key.radix_distance(&top) as usize != distance;
+                    (key, value)
+                })
+                .collect();
+
+            if moved {
+                let (stay, rest): (Bucket<K, V>, Bucket<K, V>) = core::mem::take(bucket)
+                    .into_iter()
+                    .partition(|(key, _)| key.radix_distance(&top) as usize == distance);
+
+                *bucket = stay;
+                repush.extend(rest);
+            }
         }
-    };
-}
 
-#[cfg(feature = "ordered-float")]
-radix_float_impl!(f32, u32, ordered_float::NotNan<f32>);
+        for (key, value) in repush {
+            self.buckets[key.radix_distance(&top) as usize].push((key, value));
+        }
 
-#[cfg(feature = "ordered-float")]
-radix_float_impl!(f64, u64, ordered_float::NotNan<f64>);
+        self.top = Some(top);
+    }
 
-impl Radix for () {
-    #[inline]
-    fn radix_similarity(&self, _: &()) -> u32 {
-        0
+    /// Adds `delta` to every key in the heap and to the top key, such as when
+    /// aging priorities.
+    ///
+    /// This is [`map_keys`](RadixHeapMap::map_keys) with a constant offset, so
+    /// buckets where the addition does not carry past the bits that differ
+    /// from the top key are kept intact. Negative offsets can be used with
+    /// signed keys.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(100u64);
+    /// heap.extend(vec![(98, 'a'), (60, 'b')]);
+    ///
+    /// heap.shift_keys(1000);
+    /// assert_eq!(heap.top(), Some(1100));
+    /// assert_eq!(heap.pop(), Some((1098, 'a')));
+    /// ```
+    pub fn shift_keys(&mut self, delta: K)
+    where
+        K: Add<Output = K>,
+    {
+        self.map_keys(|key| key + delta.clone());
     }
-    const RADIX_BITS: u32 = 0;
-}
 
-macro_rules! radix_tuple_impl {
-    ($(
-        $Tuple:ident {
-            $(($idx:tt) -> $T:ident)+
+    /// Subtracts `delta` from every key in the heap and from the top key, such
+    /// as when re-anchoring timestamps to a new epoch.
+    ///
+    /// See [`shift_keys`](RadixHeapMap::shift_keys).
+    pub fn shift_keys_down(&mut self, delta: K)
+    where
+        K: Sub<Output = K>,
+    {
+        self.map_keys(|key| key - delta.clone());
+    }
+
+    /// Converts every value in the heap using `f`.
+    ///
+    /// Keys stay in the same buckets, so nothing is compared or moved between
+    /// buckets. When `U` has the same size and alignment as `V`, the buckets
+    /// can reuse their allocations.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(2, 'a'), (7, 'b')]);
+    ///
+    /// let mut heap = heap.map_values(|c| c.to_ascii_uppercase() as u32);
+    /// assert_eq!(heap.pop(), Some((7, 'B' as u32)));
+    /// ```
+    pub fn map_values<U, F>(self, mut f: F) -> RadixHeapMap<K, U>
+    where
+        F: FnMut(V) -> U,
+    {
+        let mut convert = |bucket: Bucket<K, V>| -> Bucket<K, U> {
+            bucket
+                .into_iter()
+                .map(|(key, value)| (key, f(value)))
+                .collect()
+        };
+
+        RadixHeapMap {
+            len: self.len,
+            top: self.top,
+            initial: convert(self.initial),
+            buckets: self.buckets.into_iter().map(&mut convert).collect(),
+            auto_rebase: self.auto_rebase,
+            bucket_capacity: None,
+            seen: self.seen,
+            stats: self.stats,
+            diagnostics: self.diagnostics,
+            hooks: self.hooks,
         }
-    )+) => {
-        $(
-            impl<$($T:Radix),+> Radix for ($($T,)+) {
-                #[inline]
-                fn radix_similarity(&self, other: &($($T,)+)) -> u32 {
-                    let similarity = 0;
+    }
 
-                    $(
-                        let s = self.$idx.radix_similarity(&other.$idx);
-                        let similarity = similarity + s;
-                        if s < <$T as Radix>::RADIX_BITS { return similarity }
-                    )+
+    /// Modifies every value in the heap using `f`, without changing the value
+    /// type.
+    pub fn map_values_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut V),
+    {
+        self.initial
+            .iter_mut()
+            .chain(self.buckets.iter_mut().flatten())
+            .for_each(|(_, value)| f(value));
+    }
 
-                    return similarity;
-                }
-                const RADIX_BITS: u32 = 0 $(+<$T as Radix>::RADIX_BITS)+;
-            }
-        )+
+    /// Returns the number of elements with a radix distance of at most
+    /// `distance` from the top key, in `O(buckets)` time.
+    ///
+    /// Elements pushed before a top key was set have no distance yet and are
+    /// not counted. Calling [`constrain`](RadixHeapMap::constrain) first sets
+    /// the top key and counts all elements.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(16u32);
+    /// heap.extend(vec![(16, ()), (15, ()), (12, ()), (3, ())]);
+    ///
+    /// assert_eq!(heap.count_within(0), 1);
+    /// assert_eq!(heap.count_within(5), 4);
+    /// ```
+    pub fn count_within(&self, distance: u32) -> usize {
+        self.buckets
+            .iter()
+            .take(
+                (distance as usize)
+                    .saturating_add(1)
+                    .min(self.buckets.len()),
+            )
+            .map(|bucket| bucket.len())
+            .sum()
     }
-}
 
-radix_tuple_impl! {
-    Tuple1 {
-        (0) -> A
+    /// Returns a key near the `p` quantile of the keys in the heap, where 0 is
+    /// the smallest key and 1 is the largest, or `None` if empty.
+    ///
+    /// This takes `O(buckets)` time. The returned key is from the same bucket
+    /// as the exact quantile, so it is within the same radix distance of the
+    /// top key. Elements pushed before a top key was set are all in one
+    /// bucket, so any of them may be returned.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `p` is not between 0 and 1.
+    pub fn quantile_key(&self, p: f64) -> Option<K> {
+        assert!((0.0..=1.0).contains(&p), "Quantile must be between 0 and 1");
+
+        if self.len == 0 {
+            return None;
+        }
+
+        // The number of keys larger than the quantile.
+        let mut rank = self.len - 1 - round_usize(p * (self.len - 1) as f64);
+
+        for bucket in core::iter::once(&self.initial).chain(&self.buckets) {
+            if rank < bucket.len() {
+                return bucket.first().map(|(key, _)| key.clone());
+            }
+
+            rank -= bucket.len();
+        }
+
+        None
     }
-    Tuple2 {
-        (0) -> A
-        (1) -> B
+
+    /// Returns the number of elements in the heap
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
     }
-    Tuple3 {
-        (0) -> A
-        (1) -> B
-        (2) -> C
+
+    /// Returns true if there is no elements in the heap
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
-    Tuple4 {
-        (0) -> A
-        (1) -> B
-        (2) -> C
-        (3) -> D
+
+    /// The current top value. All keys pushed onto the heap must be smaller than this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.top.clone()
     }
-    Tuple5 {
-        (0) -> A
-        (1) -> B
-        (2) -> C
-        (3) -> D
-        (4) -> E
+
+    /// The largest key that could still be popped, or `None` if there is no
+    /// bound yet. This is the same as [`top`](RadixHeapMap::top).
+    ///
+    /// Keys are popped in decreasing order and pushed keys can be no larger
+    /// than the top key, so no key larger than the watermark will ever be
+    /// popped again. With `Reverse` timestamps as keys, this is the event-time
+    /// watermark of a stream: no event before it will be emitted.
+    ///
+    /// ```
+    /// use std::cmp::Reverse;
+    ///
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(Reverse(12), 'a'), (Reverse(10), 'b')]);
+    /// assert_eq!(heap.watermark(), None);
+    ///
+    /// heap.pop();
+    /// assert_eq!(heap.watermark(), Some(Reverse(10)));
+    /// ```
+    #[inline]
+    pub fn watermark(&self) -> Option<K> {
+        self.top()
     }
-    Tuple6 {
-        (0) -> A
-        (1) -> B
-        (2) -> C
-        (3) -> D
-        (4) -> E
-        (5) -> F
+
+    /// Returns true if the [`watermark`](RadixHeapMap::watermark) differs
+    /// from `reported`, the watermark last emitted downstream, and sets
+    /// `reported` to it.
+    ///
+    /// Start with `reported` set to `None`, so the first call returns true if
+    /// there is a watermark.
+    ///
+    /// ```
+    /// use std::cmp::Reverse;
+    ///
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(Reverse(0));
+    /// heap.extend(vec![(Reverse(4), 'a'), (Reverse(4), 'b'), (Reverse(9), 'c')]);
+    /// let mut reported = None;
+    ///
+    /// assert!(heap.watermark_changed(&mut reported));
+    /// heap.pop();
+    /// assert!(heap.watermark_changed(&mut reported));
+    /// assert_eq!(reported, Some(Reverse(4)));
+    /// heap.pop();
+    /// assert!(!heap.watermark_changed(&mut reported));
+    /// ```
+    pub fn watermark_changed(&self, reported: &mut Option<K>) -> bool {
+        let changed = *reported != self.top;
+        if changed {
+            reported.clone_from(&self.top);
+        }
+        changed
     }
-    Tuple7 {
-        (0) -> A
-        (1) -> B
-        (2) -> C
-        (3) -> D
-        (4) -> E
-        (5) -> F
-        (6) -> G
+
+    /// The largest key pushed since the heap was created or last cleared, or
+    /// `None` if no keys were pushed.
+    ///
+    /// Together with [`min_key_seen`](RadixHeapMap::min_key_seen), this can
+    /// be used to check assumed bounds on the keys, or to pick a top key for
+    /// [`new_at`](RadixHeapMap::new_at) or
+    /// [`clear_to`](RadixHeapMap::clear_to) in a later run. The keys are only
+    /// tracked with the `extremes` feature enabled, as it costs every push a
+    /// clone and comparison of the key.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(5, 'a'), (9, 'b'), (2, 'c')]);
+    /// heap.pop();
+    ///
+    /// assert_eq!(heap.max_key_seen(), Some(9));
+    /// assert_eq!(heap.min_key_seen(), Some(2));
+    ///
+    /// heap.clear();
+    /// assert_eq!(heap.max_key_seen(), None);
+    /// ```
+    #[cfg(feature = "extremes")]
+    #[inline]
+    pub fn max_key_seen(&self) -> Option<K> {
+        self.seen.max()
     }
-    Tuple8 {
-        (0) -> A
-        (1) -> B
-        (2) -> C
-        (3) -> D
-        (4) -> E
-        (5) -> F
-        (6) -> G
-        (7) -> H
+
+    /// The smallest key pushed since the heap was created or last cleared, or
+    /// `None` if no keys were pushed.
+    ///
+    /// See [`max_key_seen`](RadixHeapMap::max_key_seen).
+    #[cfg(feature = "extremes")]
+    #[inline]
+    pub fn min_key_seen(&self) -> Option<K> {
+        self.seen.min()
     }
-    Tuple9 {
-        (0) -> A
-        (1) -> B
-        (2) -> C
-        (3) -> D
-        (4) -> E
-        (5) -> F
-        (6) -> G
-        (7) -> H
-        (8) -> I
+
+    /// Reserves capacity so that every bucket can hold at least `capacity`
+    /// items without reallocating.
+    ///
+    /// If `capacity` is at least the number of items in the heap,
+    /// [`try_pop_no_alloc`](RadixHeapMap::try_pop_no_alloc) is guaranteed to
+    /// succeed. Note that this reserves memory for `capacity` items in each of
+    /// the `K::RADIX_BITS + 1` buckets.
+    pub fn reserve_buckets(&mut self, capacity: usize) {
+        for bucket in core::iter::once(&mut self.initial).chain(&mut self.buckets) {
+            bucket.reserve(capacity.saturating_sub(bucket.len()));
+        }
     }
-    Tuple10 {
-        (0) -> A
-        (1) -> B
-        (2) -> C
-        (3) -> D
-        (4) -> E
-        (5) -> F
-        (6) -> G
-        (7) -> H
-        (8) -> I
-        (9) -> J
+
+    /// Reserves capacity for at least `additional` more items in every
+    /// bucket.
+    ///
+    /// Afterwards, the next `additional` pushes do not allocate whatever their
+    /// keys. Note that this reserves memory in each of the
+    /// `K::RADIX_BITS + 1` buckets. See
+    /// [`try_reserve`](RadixHeapMap::try_reserve) for a fallible version.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the new capacity of a bucket overflows `usize`.
+    pub fn reserve(&mut self, additional: usize) {
+        for bucket in core::iter::once(&mut self.initial).chain(&mut self.buckets) {
+            bucket.reserve(additional);
+        }
     }
-    Tuple11 {
-        (0) -> A
-        (1) -> B
-        (2) -> C
-        (3) -> D
-        (4) -> E
-        (5) -> F
-        (6) -> G
-        (7) -> H
-        (8) -> I
-        (9) -> J
-        (10) -> K
+
+    /// Tries to reserve capacity for at least `additional` more items in
+    /// every bucket, or returns an error if the allocator fails.
+    ///
+    /// Afterwards, the next `additional` pushes do not allocate whatever their
+    /// keys. If `additional` is also at least the number of items in the heap,
+    /// popping does not allocate either. Note that this reserves memory in
+    /// each of the `K::RADIX_BITS + 1` buckets.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::<u32, ()>::new();
+    /// assert!(heap.try_reserve(16).is_ok());
+    /// assert!(heap.try_reserve(usize::MAX).is_err());
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        for bucket in core::iter::once(&mut self.initial).chain(&mut self.buckets) {
+            bucket::try_reserve(bucket, additional)?;
+        }
+
+        Ok(())
     }
-    Tuple12 {
+
+    /// Like [`pop`](RadixHeapMap::pop), but returns an error instead of
+    /// popping if redistributing items could allocate.
+    ///
+    /// When a pop finds the bucket of the top key empty, the items of the
+    /// closest non-empty bucket are moved into the empty buckets below it. This
+    /// fails if any of those buckets has less capacity than the number of
+    /// items being moved, which can be avoided up front with
+    /// [`reserve_buckets`](RadixHeapMap::reserve_buckets).
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend((0..8).map(|k| (k, k)));
+    /// assert!(heap.try_pop_no_alloc().is_err());
+    ///
+    /// heap.reserve_buckets(heap.len());
+    /// assert_eq!(heap.try_pop_no_alloc(), Ok(Some((7, 7))));
+    /// ```
+    pub fn try_pop_no_alloc(&mut self) -> Result<Option<(K, V)>, WouldAllocate> {
+        if self.buckets[0].is_empty() {
+            let (moved, targets) = if self.top.is_some() {
+                match self.buckets.iter().position(|bucket| !bucket.is_empty()) {
+                    Some(index) => (self.buckets[index].len(), index),
+                    None => (0, 0),
+                }
+            } else {
+                (self.initial.len(), self.buckets.len())
+            };
+
+            if self.buckets[..targets]
+                .iter()
+                .any(|bucket| bucket.capacity() < moved)
+            {
+                return Err(WouldAllocate);
+            }
+        }
+
+        Ok(self.pop())
+    }
+
+    /// Discards as much additional capacity as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.initial.shrink_to_fit();
+
+        for bucket in &mut self.buckets {
+            bucket.shrink_to_fit();
+        }
+    }
+
+    /// Shrinks the capacity of every bucket to at most `min_capacity` items,
+    /// or to the number of items in it if that is larger.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        for bucket in core::iter::once(&mut self.initial).chain(&mut self.buckets) {
+            bucket::shrink_to(bucket, min_capacity);
+        }
+    }
+
+    /// Moves the items of every bucket into a new allocation of exactly the
+    /// right size, and frees the allocations of empty buckets.
+    ///
+    /// Unlike [`shrink_to_fit`](RadixHeapMap::shrink_to_fit), which asks the
+    /// allocator to shrink each allocation in place, this releases every old
+    /// allocation. After many pops have left large allocations scattered
+    /// across mostly empty buckets, this gives the allocator a chance to
+    /// reclaim them. Items stay in the same buckets in the same order.
+    pub fn compact(&mut self) {
+        for bucket in core::iter::once(&mut self.initial).chain(&mut self.buckets) {
+            bucket::compact(bucket);
+        }
+    }
+
+    /// Returns an iterator of all key-value pairs in the RadixHeapMap in arbitrary order
+    pub fn iter(&self) -> Iter<K, V> {
+        Iter {
+            cur_bucket: self.initial.iter(),
+            buckets: self.buckets.iter(),
+            size: self.len,
+        }
+    }
+
+    /// Returns an iterator over the entries of both heaps in order of
+    /// decreasing key, without modifying either.
+    ///
+    /// Each bucket is only sorted once the iterator reaches it, so taking the
+    /// first few entries is cheap. Entries with equal keys in both heaps are
+    /// yielded from this heap first.
+    ///
+    /// ```
+    /// let a: radix_heap::RadixHeapMap<u32, char> = vec![(4, 'a'), (9, 'b')].into_iter().collect();
+    /// let b: radix_heap::RadixHeapMap<u32, char> = vec![(7, 'c'), (4, 'd')].into_iter().collect();
+    ///
+    /// let merged: Vec<_> = a.merge_sorted(&b).map(|&(_, v)| v).collect();
+    /// assert_eq!(merged, vec!['b', 'c', 'a', 'd']);
+    /// ```
+    pub fn merge_sorted<'a>(&'a self, other: &'a RadixHeapMap<K, V>) -> MergeSorted<'a, K, V> {
+        MergeSorted {
+            left: self.sorted().peekable(),
+            right: other.sorted().peekable(),
+            size: self.len + other.len,
+        }
+    }
+
+    /// Copies the entries of the heap into a `Vec` in arbitrary order,
+    /// without modifying the heap.
+    pub fn to_vec(&self) -> Vec<(K, V)>
+    where
+        V: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Copies the entries of the heap into a `Vec` in order of decreasing key,
+    /// without modifying the heap.
+    ///
+    /// Only each bucket is sorted, rather than all entries at once.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 'a'), (8, 'b'), (5, 'c')]);
+    ///
+    /// assert_eq!(heap.to_sorted_vec(), vec![(8, 'b'), (5, 'c'), (3, 'a')]);
+    /// assert_eq!(heap.len(), 3);
+    /// ```
+    pub fn to_sorted_vec(&self) -> Vec<(K, V)>
+    where
+        V: Clone,
+    {
+        let mut sorted = Vec::with_capacity(self.len);
+        sorted.extend(self.sorted().cloned());
+        sorted
+    }
+
+    /// Returns an iterator popping the entries of the heap in order of
+    /// decreasing key, consuming the heap.
+    ///
+    /// Entries are popped lazily, so taking the first few entries only sorts
+    /// the buckets they are in. Entries with equal keys are yielded in the
+    /// order [`pop`](RadixHeapMap::pop) would return them.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 'a'), (8, 'b'), (5, 'c')]);
+    ///
+    /// let first: Vec<_> = heap.into_iter_sorted().take(2).collect();
+    /// assert_eq!(first, vec![(8, 'b'), (5, 'c')]);
+    /// ```
+    pub fn into_iter_sorted(self) -> IntoIterSorted<K, V> {
+        IntoIterSorted { heap: self }
+    }
+
+    /// Consumes the heap and returns its entries in a `Vec` in order of
+    /// decreasing key.
+    ///
+    /// Unlike `BinaryHeap::into_sorted_vec`, which sorts in ascending order,
+    /// this uses the same order as [`into_iter_sorted`](RadixHeapMap::into_iter_sorted)
+    /// and [`to_sorted_vec`](RadixHeapMap::to_sorted_vec).
+    ///
+    /// ```
+    /// let heap: radix_heap::RadixHeapMap<u8, ()> = vec![(3, ()), (8, ()), (5, ())].into_iter().collect();
+    ///
+    /// let keys: Vec<_> = heap.into_sorted_vec().into_iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec![8, 5, 3]);
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<(K, V)> {
+        let mut sorted = Vec::with_capacity(self.len);
+        sorted.extend(self.into_iter_sorted());
+        sorted
+    }
+
+    /// Returns a wrapper whose `Debug` output lists the entries in order of
+    /// decreasing key.
+    ///
+    /// The `Debug` output of the heap itself lists entries in bucket order,
+    /// which depends on the history of pushes and pops. This only depends on
+    /// the entries, except that entries with equal keys keep their relative
+    /// order within a bucket, which makes it suitable for snapshot tests.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 'a'), (8, 'b'), (5, 'c')]);
+    ///
+    /// assert_eq!(format!("{:?}", heap.fmt_sorted()), "[(8, 'b'), (5, 'c'), (3, 'a')]");
+    /// ```
+    pub fn fmt_sorted(&self) -> FmtSorted<'_, K, V> {
+        FmtSorted(self)
+    }
+
+    fn sorted(&self) -> Sorted<'_, K, V> {
+        Sorted {
+            cur_bucket: sort_bucket(&self.initial),
+            buckets: self.buckets.iter(),
+        }
+    }
+
+    /// Returns an iterator over the non-empty buckets as slices, along with
+    /// their radix distance from the top key, in order of increasing
+    /// distance.
+    ///
+    /// Like [`bucket`](RadixHeapMap::bucket), this does not include items
+    /// pushed before a top key was set. Call
+    /// [`constrain`](RadixHeapMap::constrain) first to move them into buckets.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(8u8);
+    /// heap.extend(vec![(8, 'a'), (1, 'b'), (2, 'c')]);
+    ///
+    /// let chunks: Vec<_> = heap.bucket_chunks().collect();
+    /// assert_eq!(chunks, vec![(0, &[(8, 'a')][..]), (4, &[(1, 'b'), (2, 'c')][..])]);
+    /// ```
+    pub fn bucket_chunks(&self) -> BucketChunks<'_, K, V> {
+        BucketChunks(self.buckets.iter().enumerate())
+    }
+
+    /// Returns an iterator over the number of items in every bucket, in order
+    /// of increasing radix distance from the top key, including empty buckets.
+    ///
+    /// This shows how the keys are spread across buckets, such as when tuning
+    /// the key encoding. Like [`bucket`](RadixHeapMap::bucket), this does not
+    /// count items pushed before a top key was set.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(8u8);
+    /// heap.extend(vec![(8, 'a'), (1, 'b'), (2, 'c')]);
+    ///
+    /// let lens: Vec<_> = heap.bucket_lens().collect();
+    /// assert_eq!(lens, vec![1, 0, 0, 0, 2, 0, 0, 0, 0]);
+    /// ```
+    pub fn bucket_lens(&self) -> BucketLens<'_, K, V> {
+        BucketLens(self.buckets.iter())
+    }
+
+    /// The items at radix distance `distance` from the top key.
+    ///
+    /// Items in bucket 0 are equal to the top key and are popped from the end
+    /// of the slice. Items in other buckets are in no particular order.
+    ///
+    /// Items pushed before a top key was set are not in any bucket until the
+    /// next [`constrain`](RadixHeapMap::constrain) or pop.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `distance` is larger than `K::RADIX_BITS`.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(8u8);
+    /// heap.extend(vec![(8, 'a'), (1, 'b'), (2, 'c')]);
+    ///
+    /// assert_eq!(heap.bucket(0), &[(8, 'a')]);
+    /// assert_eq!(heap.bucket(4), &[(1, 'b'), (2, 'c')]);
+    /// ```
+    #[inline]
+    pub fn bucket(&self, distance: u32) -> &[(K, V)] {
+        &self.buckets[distance as usize]
+    }
+
+    /// The items at radix distance `distance` from the top key, as a mutable
+    /// slice.
+    ///
+    /// Values can be changed freely. It is a logic error to change a key such
+    /// that its radix distance to the top key changes, or such that it becomes
+    /// larger than the top key.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `distance` is larger than `K::RADIX_BITS`.
+    #[inline]
+    pub fn bucket_mut(&mut self, distance: u32) -> &mut [(K, V)] {
+        &mut self.buckets[distance as usize]
+    }
+
+    /// Removes and returns all items at radix distance `distance` from the top
+    /// key.
+    ///
+    /// The top key is left unchanged. The removed items can be pushed back
+    /// individually, such as after pruning some of them.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `distance` is larger than `K::RADIX_BITS`.
+    pub fn take_bucket(&mut self, distance: u32) -> Vec<(K, V)> {
+        let bucket = core::mem::take(&mut self.buckets[distance as usize]);
+        self.len -= bucket.len();
+        bucket::into_vec(bucket)
+    }
+
+    /// Returns a cursor at the start of the heap, for iterating while the heap
+    /// is modified.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new_at(10);
+    /// heap.extend(vec![(4, 'a'), (9, 'b')]);
+    ///
+    /// let mut cursor = heap.cursor();
+    /// let mut seen = 0;
+    /// while let Some(&(key, _)) = cursor.next(&heap) {
+    ///     seen += 1;
+    ///     if key > 1 {
+    ///         heap.push(key / 2, 'c');
+    ///     }
+    /// }
+    /// assert!(seen >= 2);
+    /// ```
+    pub fn cursor(&self) -> Cursor {
+        Cursor::default()
+    }
+
+    /// Returns an iterator of all keys in the RadixHeapMap in arbitrary order
+    pub fn keys(&self) -> Keys<K, V> {
+        Keys(self.iter())
+    }
+
+    /// Returns an iterator of all values in the RadixHeapMap in arbitrary order
+    pub fn values(&self) -> Values<K, V> {
+        Values(self.iter())
+    }
+
+    /// Returns an iterator of all keys and mutable values in the RadixHeapMap
+    /// in arbitrary order.
+    ///
+    /// Keys cannot be changed, since that could move them to another bucket.
+    ///
+    /// ```
+    /// let mut heap = radix_heap::RadixHeapMap::new();
+    /// heap.extend(vec![(3, 1), (8, 2)]);
+    ///
+    /// for (&key, value) in heap.iter_mut() {
+    ///     *value += key;
+    /// }
+    /// assert_eq!(heap.pop(), Some((8, 10)));
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            cur_bucket: self.initial.iter_mut(),
+            buckets: self.buckets.iter_mut(),
+            size: self.len,
+        }
+    }
+
+    /// Returns an iterator of all values in the RadixHeapMap in arbitrary
+    /// order, as mutable references.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut(self.iter_mut())
+    }
+}
+
+/// The error returned by [`RadixHeapMap::push_within_capacity`] when the
+/// bucket of the key is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CapacityError<K, V> {
+    /// The key of the rejected item.
+    pub key: K,
+
+    /// The value of the rejected item.
+    pub value: V,
+}
+
+impl<K: fmt::Debug, V> fmt::Display for CapacityError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the bucket of key {:?} is full", self.key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug, V: fmt::Debug> std::error::Error for CapacityError<K, V> {}
+
+/// The error returned by [`RadixHeapMap::try_push`] and
+/// [`RadixHeapMap::try_extend`] when a key is larger than the top key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MonotonicityError<K, V> {
+    /// The key of the rejected item.
+    pub key: K,
+
+    /// The value of the rejected item.
+    pub value: V,
+}
+
+impl<K: fmt::Debug, V> fmt::Display for MonotonicityError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "key {:?} is larger than the top key", self.key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug, V: fmt::Debug> std::error::Error for MonotonicityError<K, V> {}
+
+/// The error returned by [`RadixHeapMap::try_push_alloc`] when memory for the
+/// item could not be allocated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TryPushError<K, V> {
+    /// The key of the rejected item.
+    pub key: K,
+
+    /// The value of the rejected item.
+    pub value: V,
+
+    /// The allocation error.
+    pub error: TryReserveError,
+}
+
+impl<K: fmt::Debug, V> fmt::Display for TryPushError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "could not allocate memory for key {:?}: {}",
+            self.key, self.error
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug, V: fmt::Debug> std::error::Error for TryPushError<K, V> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The error returned by [`RadixHeapMap::try_pop_no_alloc`] when popping
+/// could allocate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WouldAllocate;
+
+impl fmt::Display for WouldAllocate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("popping would allocate")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WouldAllocate {}
+
+impl<K: Clone, V: Clone> Clone for RadixHeapMap<K, V> {
+    fn clone(&self) -> RadixHeapMap<K, V> {
+        RadixHeapMap {
+            len: self.len,
+            top: self.top.clone(),
+            buckets: self.buckets.clone(),
+            initial: self.initial.clone(),
+            auto_rebase: self.auto_rebase,
+            bucket_capacity: self.bucket_capacity,
+            seen: self.seen.clone(),
+            stats: self.stats,
+            diagnostics: self.diagnostics,
+            hooks: self.hooks.clone(),
+        }
+    }
+
+    /// Clones each bucket of `source` into the same bucket of this heap,
+    /// reusing its allocation.
+    fn clone_from(&mut self, source: &RadixHeapMap<K, V>) {
+        self.len = source.len;
+        self.top.clone_from(&source.top);
+        self.buckets.clone_from(&source.buckets);
+        self.initial.clone_from(&source.initial);
+        self.auto_rebase = source.auto_rebase;
+        self.bucket_capacity = source.bucket_capacity;
+        self.seen.clone_from(&source.seen);
+        self.stats = source.stats;
+        self.diagnostics = source.diagnostics;
+        self.hooks.clone_from(&source.hooks);
+    }
+}
+
+impl<K: Radix + Ord + Clone, V> Default for RadixHeapMap<K, V> {
+    fn default() -> RadixHeapMap<K, V> {
+        RadixHeapMap::new()
+    }
+}
+
+impl<K: Radix + Ord + Clone, V> FromIterator<(K, V)> for RadixHeapMap<K, V> {
+    fn from_iter<I>(iter: I) -> RadixHeapMap<K, V>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut heap = RadixHeapMap::new();
+
+        for (k, v) in iter {
+            heap.push(k, v);
+        }
+
+        heap
+    }
+}
+
+impl<K: Radix + Ord + Clone, V> Extend<(K, V)> for RadixHeapMap<K, V> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        for (k, v) in iter {
+            self.push(k, v);
+        }
+    }
+}
+
+impl<'a, K: Radix + Ord + Copy + 'a, V: Copy + 'a> Extend<&'a (K, V)> for RadixHeapMap<K, V> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a (K, V)>,
+    {
+        for &(k, v) in iter {
+            self.push(k, v);
+        }
+    }
+}
+
+impl<K: Radix + Ord + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for RadixHeapMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Heaps are equal if they hold the same key-value pairs, in any order and
+/// regardless of their top keys or which buckets the pairs are in.
+///
+/// ```
+/// let mut a = radix_heap::RadixHeapMap::new();
+/// a.extend(vec![(9, 'a'), (4, 'b'), (4, 'c')]);
+/// a.pop();
+///
+/// let b: radix_heap::RadixHeapMap<_, _> = vec![(4, 'c'), (4, 'b')].into_iter().collect();
+/// assert_eq!(a, b);
+/// ```
+impl<K: Radix + Ord + Clone, V: PartialEq> PartialEq for RadixHeapMap<K, V> {
+    fn eq(&self, other: &RadixHeapMap<K, V>) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        let (ours, theirs) = (self.sorted_entries(), other.sorted_entries());
+        if !ours
+            .iter()
+            .map(|(k, _)| k)
+            .eq(theirs.iter().map(|(k, _)| k))
+        {
+            return false;
+        }
+
+        // Values with equal keys are in no particular order, so they are
+        // matched up pairwise.
+        let mut start = 0;
+        while start < ours.len() {
+            let key = &ours[start].0;
+            let end = start + ours[start..].iter().take_while(|(k, _)| k == key).count();
+            let mut matched = alloc::vec![false; end - start];
+
+            for (_, value) in &ours[start..end] {
+                let found = theirs[start..end]
+                    .iter()
+                    .zip(&mut matched)
+                    .find(|((_, v), matched)| !**matched && v == value);
+
+                match found {
+                    Some((_, matched)) => *matched = true,
+                    None => return false,
+                }
+            }
+
+            start = end;
+        }
+
+        true
+    }
+}
+
+impl<K: Radix + Ord + Clone, V: Eq> Eq for RadixHeapMap<K, V> {}
+
+/// Hashes the keys in sorted order, so that equal heaps hash equally. Values
+/// are not hashed, since values with equal keys are in no particular order.
+impl<K: Radix + Ord + Clone + Hash, V> Hash for RadixHeapMap<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+
+        for (key, _) in self.sorted_entries() {
+            key.hash(state);
+        }
+    }
+}
+
+/// A guard giving mutable access to the value of the greatest element of a
+/// RadixHeapMap.
+///
+/// This `struct` is created by [`RadixHeapMap::peek_mut`].
+pub struct PeekMut<'a, K: Radix + Ord + Clone, V> {
+    heap: &'a mut RadixHeapMap<K, V>,
+}
+
+impl<'a, K: Radix + Ord + Clone, V> PeekMut<'a, K, V> {
+    /// The key of the greatest element.
+    #[inline]
+    pub fn key(this: &PeekMut<'a, K, V>) -> K {
+        this.entry().0.clone()
+    }
+
+    /// Removes the greatest element from the heap and returns it.
+    pub fn pop(this: PeekMut<'a, K, V>) -> (K, V) {
+        // The element is the last one in bucket 0, so this cannot constrain.
+        this.heap.pop().unwrap()
+    }
+
+    fn entry(&self) -> &(K, V) {
+        self.heap.buckets[0].last().unwrap()
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone, V> Deref for PeekMut<'a, K, V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.entry().1
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone, V> DerefMut for PeekMut<'a, K, V> {
+    fn deref_mut(&mut self) -> &mut V {
+        &mut self.heap.buckets[0].last_mut().unwrap().1
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for PeekMut<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PeekMut").field(self.entry()).finish()
+    }
+}
+
+/// An owning iterator over the entries of a RadixHeapMap in order of
+/// decreasing key.
+///
+/// This `struct` is created by [`RadixHeapMap::into_iter_sorted`].
+#[derive(Clone)]
+pub struct IntoIterSorted<K, V> {
+    heap: RadixHeapMap<K, V>,
+}
+
+impl<K: Radix + Ord + Clone, V> Iterator for IntoIterSorted<K, V> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<K: Radix + Ord + Clone, V> ExactSizeIterator for IntoIterSorted<K, V> {}
+
+impl<K: Radix + Ord + Clone, V> FusedIterator for IntoIterSorted<K, V> {}
+
+/// An owning iterator over key-value pairs in a RadixHeapMap.
+#[derive(Clone)]
+pub struct IntoIter<K, V> {
+    cur_bucket: BucketIntoIter<K, V>,
+    buckets: alloc::vec::IntoIter<Bucket<K, V>>,
+    size: usize,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let pair @ Some(_) = self.cur_bucket.next() {
+                self.size -= 1;
+                return pair;
+            } else {
+                self.cur_bucket = self.buckets.next()?.into_iter();
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+
+    fn for_each<F>(self, mut f: F)
+    where
+        F: FnMut(Self::Item),
+    {
+        self.cur_bucket.for_each(&mut f);
+        self.buckets.for_each(|b| b.into_iter().for_each(&mut f));
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let acc = self.cur_bucket.fold(init, &mut f);
+        self.buckets
+            .fold(acc, |acc, b| b.into_iter().fold(acc, &mut f))
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        loop {
+            let len = self.cur_bucket.len();
+
+            if let pair @ Some(_) = self.cur_bucket.nth(n) {
+                self.size -= n + 1;
+                return pair;
+            } else {
+                n -= len;
+                self.size -= len;
+                self.cur_bucket = self.buckets.next()?.into_iter();
+            }
+        }
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.size
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        let cur_bucket = self.cur_bucket;
+        self.buckets
+            .rev()
+            .find_map(|mut b| b.pop())
+            .or_else(|| cur_bucket.last())
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+/// An iterator over key-value pairs in a RadixHeapMap.
+#[derive(Clone)]
+pub struct Iter<'a, K, V> {
+    cur_bucket: core::slice::Iter<'a, (K, V)>,
+    buckets: core::slice::Iter<'a, Bucket<K, V>>,
+    size: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = &'a (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let pair @ Some(_) = self.cur_bucket.next() {
+                self.size -= 1;
+                return pair;
+            } else {
+                self.cur_bucket = self.buckets.next()?.iter();
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+
+    fn for_each<F>(self, mut f: F)
+    where
+        F: FnMut(Self::Item),
+    {
+        self.cur_bucket.for_each(&mut f);
+        self.buckets.for_each(|b| b.iter().for_each(&mut f));
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let acc = self.cur_bucket.fold(init, &mut f);
+        self.buckets.fold(acc, |acc, b| b.iter().fold(acc, &mut f))
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        loop {
+            let len = self.cur_bucket.len();
+
+            if let pair @ Some(_) = self.cur_bucket.nth(n) {
+                self.size -= n + 1;
+                return pair;
+            } else {
+                n -= len;
+                self.size -= len;
+                self.cur_bucket = self.buckets.next()?.iter();
+            }
+        }
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.size
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        let cur_bucket = self.cur_bucket;
+        self.buckets
+            .rev()
+            .find_map(|b| b.last())
+            .or_else(|| cur_bucket.last())
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
+
+/// An iterator over keys and mutable values in a RadixHeapMap.
+///
+/// This `struct` is created by [`RadixHeapMap::iter_mut`].
+pub struct IterMut<'a, K, V> {
+    cur_bucket: core::slice::IterMut<'a, (K, V)>,
+    buckets: core::slice::IterMut<'a, Bucket<K, V>>,
+    size: usize,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, value)) = self.cur_bucket.next() {
+                self.size -= 1;
+                return Some((key, value));
+            } else {
+                self.cur_bucket = self.buckets.next()?.iter_mut();
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.size
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for IterMut<'a, K, V> {}
+
+/// A position in a RadixHeapMap that stays valid while the heap is modified.
+///
+/// The cursor walks the heap bucket by bucket, like [`RadixHeapMap::iter`],
+/// but does not borrow the heap between steps. Every item in the heap for the
+/// whole walk is visited exactly once if the heap is only pushed to, while
+/// items pushed during the walk may or may not be visited. Pops and other
+/// changes that move items between buckets may cause items to be skipped or
+/// visited twice, but never cause a panic.
+///
+/// This `struct` is created by [`RadixHeapMap::cursor`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    /// The bucket, where 0 is the initial bucket.
+    bucket: usize,
+    index: usize,
+}
+
+impl Cursor {
+    /// Returns the next item of `heap` and advances the cursor, or `None` if
+    /// the cursor is past the end.
+    pub fn next<'a, K, V>(&mut self, heap: &'a RadixHeapMap<K, V>) -> Option<&'a (K, V)> {
+        loop {
+            let bucket = match self.bucket {
+                0 => &heap.initial,
+                bucket => heap.buckets.get(bucket - 1)?,
+            };
+
+            if let Some(item) = bucket.get(self.index) {
+                self.index += 1;
+                return Some(item);
+            }
+
+            self.bucket += 1;
+            self.index = 0;
+        }
+    }
+}
+
+/// An iterator over keys in a RadixHeapMap.
+#[derive(Clone)]
+pub struct Keys<'a, K, V>(Iter<'a, K, V>);
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn for_each<F>(self, mut f: F)
+    where
+        F: FnMut(Self::Item),
+    {
+        self.0.for_each(|(k, _)| f(k))
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.0.fold(init, |acc, (k, _)| f(acc, k))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n).map(|(k, _)| k)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.0.count()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.0.last().map(|(k, _)| k)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+
+/// An iterator over values in a RadixHeapMap.
+#[derive(Clone)]
+pub struct Values<'a, K, V>(Iter<'a, K, V>);
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn for_each<F>(self, mut f: F)
+    where
+        F: FnMut(Self::Item),
+    {
+        self.0.for_each(|(_, v)| f(v))
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.0.fold(init, |acc, (_, v)| f(acc, v))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n).map(|(_, v)| v)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.0.count()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.0.last().map(|(_, v)| v)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
+
+/// An iterator over mutable values in a RadixHeapMap.
+///
+/// This `struct` is created by [`RadixHeapMap::values_mut`].
+pub struct ValuesMut<'a, K, V>(IterMut<'a, K, V>);
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.0.count()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for ValuesMut<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for ValuesMut<'a, K, V> {}
+
+/// An iterator over the values of the elements with a given key in a
+/// RadixHeapMap.
+///
+/// This `struct` is created by [`RadixHeapMap::get_all`].
+#[derive(Clone)]
+pub struct GetAll<'a, K, V> {
+    bucket: core::slice::Iter<'a, (K, V)>,
+    key: &'a K,
+}
+
+impl<'a, K: PartialEq, V> Iterator for GetAll<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.key;
+        self.bucket.find(|(k, _)| k == key).map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.bucket.size_hint().1)
+    }
+}
+
+impl<'a, K: PartialEq, V> FusedIterator for GetAll<'a, K, V> {}
+
+/// An iterator over the non-empty buckets of a RadixHeapMap.
+///
+/// This `struct` is created by [`RadixHeapMap::bucket_chunks`].
+#[derive(Clone)]
+pub struct BucketChunks<'a, K, V>(core::iter::Enumerate<core::slice::Iter<'a, Bucket<K, V>>>);
+
+impl<'a, K, V> Iterator for BucketChunks<'a, K, V> {
+    type Item = (u32, &'a [(K, V)]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .find(|(_, bucket)| !bucket.is_empty())
+            .map(|(distance, bucket)| (distance as u32, &bucket[..]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.0.size_hint().1)
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for BucketChunks<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .rfind(|(_, bucket)| !bucket.is_empty())
+            .map(|(distance, bucket)| (distance as u32, &bucket[..]))
+    }
+}
+
+impl<'a, K, V> FusedIterator for BucketChunks<'a, K, V> {}
+
+/// An iterator over the number of items in each bucket of a RadixHeapMap.
+///
+/// This `struct` is created by [`RadixHeapMap::bucket_lens`].
+#[derive(Clone)]
+pub struct BucketLens<'a, K, V>(core::slice::Iter<'a, Bucket<K, V>>);
+
+impl<'a, K, V> Iterator for BucketLens<'a, K, V> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|bucket| bucket.len())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for BucketLens<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|bucket| bucket.len())
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for BucketLens<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for BucketLens<'a, K, V> {}
+
+/// The entries of a RadixHeapMap in order of decreasing key.
+///
+/// Keys in a bucket closer to the top key are always larger than keys in a
+/// bucket further away, so only each bucket needs to be sorted. When there is
+/// no top key yet, all entries are in the initial bucket.
+#[derive(Clone)]
+struct Sorted<'a, K, V> {
+    cur_bucket: alloc::vec::IntoIter<&'a (K, V)>,
+    buckets: core::slice::Iter<'a, Bucket<K, V>>,
+}
+
+fn sort_bucket<K: Ord, V>(bucket: &Bucket<K, V>) -> alloc::vec::IntoIter<&(K, V)> {
+    let mut bucket: Vec<_> = bucket.iter().collect();
+    bucket.sort_by(|(a, _), (b, _)| b.cmp(a));
+    bucket.into_iter()
+}
+
+impl<'a, K: Ord, V> Iterator for Sorted<'a, K, V> {
+    type Item = &'a (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let pair @ Some(_) = self.cur_bucket.next() {
+                return pair;
+            }
+
+            self.cur_bucket = sort_bucket(self.buckets.next()?);
+        }
+    }
+}
+
+/// A wrapper formatting the entries of a RadixHeapMap in order of decreasing
+/// key.
+///
+/// This `struct` is created by [`RadixHeapMap::fmt_sorted`].
+pub struct FmtSorted<'a, K, V>(&'a RadixHeapMap<K, V>);
+
+impl<'a, K: Radix + Ord + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for FmtSorted<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.0.sorted()).finish()
+    }
+}
+
+/// An iterator over the entries of two RadixHeapMaps in order of decreasing
+/// key.
+///
+/// This `struct` is created by [`RadixHeapMap::merge_sorted`].
+#[derive(Clone)]
+pub struct MergeSorted<'a, K: Ord, V> {
+    left: core::iter::Peekable<Sorted<'a, K, V>>,
+    right: core::iter::Peekable<Sorted<'a, K, V>>,
+    size: usize,
+}
+
+impl<'a, K: Ord, V> Iterator for MergeSorted<'a, K, V> {
+    type Item = &'a (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let right = match (self.left.peek(), self.right.peek()) {
+            (Some((a, _)), Some((b, _))) => b > a,
+            (left, _) => left.is_none(),
+        };
+
+        let pair = if right {
+            self.right.next()
+        } else {
+            self.left.next()
+        };
+
+        if pair.is_some() {
+            self.size -= 1;
+        }
+
+        pair
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+}
+
+impl<'a, K: Ord, V> ExactSizeIterator for MergeSorted<'a, K, V> {}
+
+impl<'a, K: Ord, V> FusedIterator for MergeSorted<'a, K, V> {}
+
+impl<K: Radix + Ord + Clone, V> From<Vec<(K, V)>> for RadixHeapMap<K, V> {
+    /// See [`RadixHeapMap::from_vec`].
+    fn from(vec: Vec<(K, V)>) -> RadixHeapMap<K, V> {
+        RadixHeapMap::from_vec(vec)
+    }
+}
+
+impl<K: Radix + Ord + Clone, V> IntoIterator for RadixHeapMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            cur_bucket: self.initial.into_iter(),
+            buckets: self.buckets.into_iter(),
+            size: self.len,
+        }
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone, V> IntoIterator for &'a RadixHeapMap<K, V> {
+    type Item = &'a (K, V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone, V> IntoIterator for &'a mut RadixHeapMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// A number that can be compared using radix distance
+pub trait Radix {
+    /// The number of high bits in a row that this and `other` has in common
+    ///
+    /// Eg. the radix similarity of 001001 and 000001 is 2 because they share
+    /// the 2 high bits.
+    fn radix_similarity(&self, other: &Self) -> u32;
+
+    /// Opposite of `radix_similarity`. If `radix_distance` returns 0, then `radix_similarity`
+    /// returns `radix_bits` and vice versa.
+    fn radix_distance(&self, other: &Self) -> u32 {
+        Self::RADIX_BITS - self.radix_similarity(other)
+    }
+
+    /// The value returned by `radix_similarty` if all bits are equal
+    const RADIX_BITS: u32;
+}
+
+macro_rules! radix_wrapper_impl {
+    ($t:ident) => {
+        impl<T: Radix> Radix for $t<T> {
+            #[inline]
+            fn radix_similarity(&self, other: &$t<T>) -> u32 {
+                self.0.radix_similarity(&other.0)
+            }
+
+            const RADIX_BITS: u32 = T::RADIX_BITS;
+        }
+    };
+}
+
+radix_wrapper_impl!(Reverse);
+radix_wrapper_impl!(Wrapping);
+radix_wrapper_impl!(Serial);
+
+macro_rules! radix_int_impl {
+    ($t:ty) => {
+        impl Radix for $t {
+            #[inline]
+            fn radix_similarity(&self, other: &$t) -> u32 {
+                (self ^ other).leading_zeros()
+            }
+
+            const RADIX_BITS: u32 = (core::mem::size_of::<$t>() * 8) as u32;
+        }
+    };
+}
+
+radix_int_impl!(i8);
+radix_int_impl!(i16);
+radix_int_impl!(i32);
+radix_int_impl!(i64);
+radix_int_impl!(i128);
+radix_int_impl!(isize);
+
+radix_int_impl!(u8);
+radix_int_impl!(u16);
+radix_int_impl!(u32);
+radix_int_impl!(u64);
+radix_int_impl!(u128);
+radix_int_impl!(usize);
+
+macro_rules! radix_nonzero_impl {
+    ($t:ty) => {
+        impl Radix for $t {
+            #[inline]
+            fn radix_similarity(&self, other: &$t) -> u32 {
+                self.get().radix_similarity(&other.get())
+            }
+
+            const RADIX_BITS: u32 = (core::mem::size_of::<$t>() * 8) as u32;
+        }
+    };
+}
+
+radix_nonzero_impl!(core::num::NonZeroI8);
+radix_nonzero_impl!(core::num::NonZeroI16);
+radix_nonzero_impl!(core::num::NonZeroI32);
+radix_nonzero_impl!(core::num::NonZeroI64);
+radix_nonzero_impl!(core::num::NonZeroI128);
+radix_nonzero_impl!(core::num::NonZeroIsize);
+
+radix_nonzero_impl!(core::num::NonZeroU8);
+radix_nonzero_impl!(core::num::NonZeroU16);
+radix_nonzero_impl!(core::num::NonZeroU32);
+radix_nonzero_impl!(core::num::NonZeroU64);
+radix_nonzero_impl!(core::num::NonZeroU128);
+radix_nonzero_impl!(core::num::NonZeroUsize);
+
+impl Radix for char {
+    #[inline]
+    fn radix_similarity(&self, other: &char) -> u32 {
+        (*self as u32).radix_similarity(&(*other as u32))
+    }
+
+    const RADIX_BITS: u32 = u32::RADIX_BITS;
+}
+
+impl Radix for bool {
+    #[inline]
+    fn radix_similarity(&self, other: &bool) -> u32 {
+        (self == other) as u32
+    }
+
+    const RADIX_BITS: u32 = 1;
+}
+
+/// Options compare with `None` below every `Some`, as a leading bit that is 0
+/// for `None` and 1 for `Some`, followed by the bits of the value, which are
+/// all 0 for `None`. `RADIX_BITS` is one more than that of `T`.
+impl<T: Radix> Radix for Option<T> {
+    #[inline]
+    fn radix_similarity(&self, other: &Option<T>) -> u32 {
+        match (self, other) {
+            (Some(a), Some(b)) => 1 + a.radix_similarity(b),
+            (None, None) => Self::RADIX_BITS,
+            _ => 0,
+        }
+    }
+
+    const RADIX_BITS: u32 = T::RADIX_BITS + 1;
+}
+
+/// Durations compare by seconds, then by nanoseconds, like a tuple.
+impl Radix for core::time::Duration {
+    #[inline]
+    fn radix_similarity(&self, other: &core::time::Duration) -> u32 {
+        let key = |d: &core::time::Duration| (d.as_secs(), d.subsec_nanos());
+        key(self).radix_similarity(&key(other))
+    }
+
+    const RADIX_BITS: u32 = u64::RADIX_BITS + u32::RADIX_BITS;
+}
+
+impl Radix for () {
+    #[inline]
+    fn radix_similarity(&self, _: &()) -> u32 {
+        0
+    }
+    const RADIX_BITS: u32 = 0;
+}
+
+macro_rules! radix_tuple_impl {
+    ($(
+        $Tuple:ident {
+            $(($idx:tt) -> $T:ident)+
+        }
+    )+) => {
+        $(
+            impl<$($T:Radix),+> Radix for ($($T,)+) {
+                #[inline]
+                fn radix_similarity(&self, other: &($($T,)+)) -> u32 {
+                    let similarity = 0;
+
+                    $(
+                        let s = self.$idx.radix_similarity(&other.$idx);
+                        let similarity = similarity + s;
+                        if s < <$T as Radix>::RADIX_BITS { return similarity }
+                    )+
+
+                    return similarity;
+                }
+                const RADIX_BITS: u32 = 0 $(+<$T as Radix>::RADIX_BITS)+;
+            }
+        )+
+    }
+}
+
+radix_tuple_impl! {
+    Tuple1 {
+        (0) -> A
+    }
+    Tuple2 {
+        (0) -> A
+        (1) -> B
+    }
+    Tuple3 {
+        (0) -> A
+        (1) -> B
+        (2) -> C
+    }
+    Tuple4 {
+        (0) -> A
+        (1) -> B
+        (2) -> C
+        (3) -> D
+    }
+    Tuple5 {
+        (0) -> A
+        (1) -> B
+        (2) -> C
+        (3) -> D
+        (4) -> E
+    }
+    Tuple6 {
+        (0) -> A
+        (1) -> B
+        (2) -> C
+        (3) -> D
+        (4) -> E
+        (5) -> F
+    }
+    Tuple7 {
+        (0) -> A
+        (1) -> B
+        (2) -> C
+        (3) -> D
+        (4) -> E
+        (5) -> F
+        (6) -> G
+    }
+    Tuple8 {
+        (0) -> A
+        (1) -> B
+        (2) -> C
+        (3) -> D
+        (4) -> E
+        (5) -> F
+        (6) -> G
+        (7) -> H
+    }
+    Tuple9 {
+        (0) -> A
+        (1) -> B
+        (2) -> C
+        (3) -> D
+        (4) -> E
+        (5) -> F
+        (6) -> G
+        (7) -> H
+        (8) -> I
+    }
+    Tuple10 {
+        (0) -> A
+        (1) -> B
+        (2) -> C
+        (3) -> D
+        (4) -> E
+        (5) -> F
+        (6) -> G
+        (7) -> H
+        (8) -> I
+        (9) -> J
+    }
+    Tuple11 {
+        (0) -> A
+        (1) -> B
+        (2) -> C
+        (3) -> D
+        (4) -> E
+        (5) -> F
+        (6) -> G
+        (7) -> H
+        (8) -> I
+        (9) -> J
+        (10) -> K
+    }
+    Tuple12 {
         (0) -> A
         (1) -> B
         (2) -> C
@@ -620,138 +3329,907 @@ radix_tuple_impl! {
         (10) -> K
         (11) -> L
     }
-}
+}
+
+/// Arrays compare element by element, like tuples. A `[u8; N]` key orders
+/// like the big-endian integer of those bytes.
+impl<T: Radix, const N: usize> Radix for [T; N] {
+    #[inline]
+    fn radix_similarity(&self, other: &[T; N]) -> u32 {
+        let mut similarity = 0;
+
+        for (a, b) in self.iter().zip(other) {
+            let s = a.radix_similarity(b);
+            similarity += s;
+            if s < T::RADIX_BITS {
+                break;
+            }
+        }
+
+        similarity
+    }
+
+    const RADIX_BITS: u32 = T::RADIX_BITS * N as u32;
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::{quickcheck, TestResult};
+    use super::bucket;
+    use super::PeekMut;
+    use super::Radix;
+    use super::RadixHeapMap;
+    use std::{
+        cmp::Reverse,
+        collections::BinaryHeap,
+        num::{NonZeroI16, NonZeroI64, NonZeroU64, NonZeroU8},
+        time::Duration,
+    };
+
+    #[test]
+    fn radix_dist() {
+        assert!(4u32.radix_distance(&2) == 3);
+        assert!(3u32.radix_distance(&2) == 1);
+        assert!(2u32.radix_distance(&2) == 0);
+        assert!(1u32.radix_distance(&2) == 2);
+        assert!(0u32.radix_distance(&2) == 2);
+    }
+
+    #[test]
+    fn clear() {
+        let mut heap = RadixHeapMap::new();
+        heap.push(0u32, 'a');
+        heap.clear();
+        assert!(heap.pop().is_none());
+    }
+
+    #[test]
+    fn push_pop() {
+        let mut heap = RadixHeapMap::new();
+        heap.push(0u32, 'a');
+        heap.push(3, 'b');
+        heap.push(2, 'c');
+
+        assert!(heap.len() == 3);
+        assert!(!heap.is_empty());
+
+        assert!(heap.pop() == Some((3, 'b')));
+        assert!(heap.pop() == Some((2, 'c')));
+        assert!(heap.pop() == Some((0, 'a')));
+        assert!(heap.pop() == None);
+
+        assert!(heap.len() == 0);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn eq_multiset() {
+        use std::{
+            collections::hash_map::DefaultHasher,
+            hash::{Hash, Hasher},
+        };
+
+        fn hash(heap: &RadixHeapMap<u8, u8>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            heap.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        fn prop(xs: Vec<(u8, u8)>, pops: usize, rotate: usize) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().copied().collect();
+            for _ in 0..pops % (xs.len() + 1) {
+                heap.pop();
+            }
+
+            let mut rest: Vec<_> = heap.iter().copied().collect();
+            if !rest.is_empty() {
+                let len = rest.len();
+                rest.rotate_left(rotate % len);
+            }
+
+            let other: RadixHeapMap<_, _> = rest.iter().copied().collect();
+
+            if let Some(first) = rest.first_mut() {
+                first.1 = first.1.wrapping_add(1);
+            }
+            let changed: RadixHeapMap<_, _> = rest.into_iter().collect();
+
+            heap == other && hash(&heap) == hash(&other) && (heap.is_empty() || heap != changed)
+        }
+
+        quickcheck(prop as fn(Vec<(u8, u8)>, usize, usize) -> bool);
+    }
+
+    #[test]
+    fn clone_from() {
+        fn prop(xs: Vec<u16>, ys: Vec<u16>, pops: usize) -> bool {
+            let mut source: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, x)).collect();
+            for _ in 0..pops % (xs.len() + 1) {
+                source.pop();
+            }
+
+            let mut heap: RadixHeapMap<_, _> = ys.iter().map(|&y| (y, y)).collect();
+            heap.pop();
+            let capacities: Vec<_> = heap.buckets.iter().map(|b| b.capacity()).collect();
+
+            heap.clone_from(&source);
+
+            let empty = source.clone_empty();
+
+            heap == source
+                && heap.top() == source.top()
+                && heap.buckets == source.buckets
+                && heap
+                    .buckets
+                    .iter()
+                    .zip(&capacities)
+                    .all(|(bucket, &capacity)| bucket.capacity() >= capacity)
+                && empty.is_empty()
+                && empty.top() == source.top()
+                && empty
+                    .buckets
+                    .iter()
+                    .zip(&source.buckets)
+                    .all(|(a, b)| a.capacity() >= b.capacity())
+        }
+
+        quickcheck(prop as fn(Vec<u16>, Vec<u16>, usize) -> bool);
+    }
+
+    #[test]
+    fn clone_keys() {
+        #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Boxed(Box<u32>);
+
+        impl Radix for Boxed {
+            fn radix_similarity(&self, other: &Boxed) -> u32 {
+                self.0.radix_similarity(&other.0)
+            }
+
+            const RADIX_BITS: u32 = u32::RADIX_BITS;
+        }
+
+        fn prop(mut xs: Vec<u32>) -> bool {
+            let mut heap: RadixHeapMap<_, _> =
+                xs.iter().map(|&x| (Boxed(Box::new(x)), x)).collect();
+            xs.sort_unstable();
+
+            #[cfg(feature = "extremes")]
+            assert_eq!(heap.max_key_seen().map(|key| *key.0), xs.last().copied());
+
+            std::iter::from_fn(|| heap.pop())
+                .map(|(key, x)| {
+                    assert_eq!(*key.0, x);
+                    x
+                })
+                .eq(xs.into_iter().rev())
+        }
+
+        quickcheck(prop as fn(Vec<u32>) -> bool);
+    }
+
+    #[test]
+    fn auto_rebase() {
+        let mut heap = RadixHeapMap::new();
+        heap.set_auto_rebase(true);
+
+        for burst in &[[3u32, 1, 2], [9, 8, 7], [4, 6, 5]] {
+            heap.extend(burst.iter().map(|&k| (k, ())));
+            assert_eq!(heap.pop(), Some((*burst.iter().max().unwrap(), ())));
+            assert!(heap.top().is_some());
+
+            while heap.pop().is_some() {}
+            assert!(heap.top().is_none());
+        }
+
+        heap.clear();
+        assert!(heap.auto_rebase());
+    }
+
+    #[test]
+    fn rev_push_pop() {
+        let mut heap = RadixHeapMap::new();
+        heap.push(Reverse(0), 'a');
+        heap.push(Reverse(3), 'b');
+        heap.push(Reverse(2), 'c');
+
+        assert!(heap.len() == 3);
+        assert!(!heap.is_empty());
+
+        assert!(heap.pop() == Some((Reverse(0), 'a')));
+        assert!(heap.pop() == Some((Reverse(2), 'c')));
+        assert!(heap.pop() == Some((Reverse(3), 'b')));
+        assert!(heap.pop() == None);
+
+        assert!(heap.len() == 0);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_pop_panic() {
+        let mut heap = RadixHeapMap::new();
+        heap.push(0u32, 'a');
+        heap.push(3, 'b');
+
+        assert!(heap.pop() == Some((3, 'b')));
+        heap.push(4, 'd');
+    }
+
+    #[test]
+    fn sort() {
+        fn prop<T: Ord + Radix + Copy>(mut xs: Vec<T>) -> bool {
+            let mut heap: RadixHeapMap<_, _> =
+                xs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+
+            xs.sort();
+
+            while xs.pop() == heap.pop().map(|(k, _)| k) {
+                if xs.is_empty() {
+                    return true;
+                }
+            }
+
+            false
+        }
+
+        quickcheck(prop as fn(Vec<()>) -> bool);
+        quickcheck(prop as fn(Vec<u32>) -> bool);
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+        quickcheck(prop as fn(Vec<(u32, i32)>) -> bool);
+        quickcheck(prop as fn(Vec<u8>) -> bool);
+        quickcheck(prop as fn(Vec<i16>) -> bool);
+        quickcheck(prop as fn(Vec<(i64, usize)>) -> bool);
+        quickcheck(prop as fn(Vec<i128>) -> bool);
+        quickcheck(prop as fn(Vec<u128>) -> bool);
+        quickcheck(prop as fn(Vec<NonZeroU8>) -> bool);
+        quickcheck(prop as fn(Vec<NonZeroU64>) -> bool);
+        quickcheck(prop as fn(Vec<char>) -> bool);
+        quickcheck(prop as fn(Vec<bool>) -> bool);
+        quickcheck(prop as fn(Vec<(bool, u8)>) -> bool);
+        quickcheck(prop as fn(Vec<Option<u8>>) -> bool);
+        quickcheck(prop as fn(Vec<(Option<i16>, Option<bool>)>) -> bool);
+        quickcheck(prop as fn(Vec<Duration>) -> bool);
+        quickcheck(prop as fn(Vec<[u8; 4]>) -> bool);
+        quickcheck(prop as fn(Vec<[i8; 3]>) -> bool);
+        quickcheck(prop as fn(Vec<[(bool, u16); 2]>) -> bool);
+        quickcheck(prop as fn(Vec<[u32; 0]>) -> bool);
+
+        let nonzero_i16 = |xs: Vec<i16>| prop(xs.into_iter().filter_map(NonZeroI16::new).collect());
+        let nonzero_i64 = |xs: Vec<i64>| prop(xs.into_iter().filter_map(NonZeroI64::new).collect());
+        quickcheck(nonzero_i16 as fn(Vec<i16>) -> bool);
+        quickcheck(nonzero_i64 as fn(Vec<i64>) -> bool);
+    }
+
+    #[cfg(feature = "ordered-float")]
+    #[test]
+    fn sort_float() {
+        fn prop(xs: Vec<f32>) -> TestResult {
+            if xs.iter().any(|x| x.is_nan()) {
+                return TestResult::discard();
+            }
+
+            let mut xs: Vec<_> = xs
+                .into_iter()
+                .map(|x| ordered_float::NotNan::new(x).unwrap())
+                .collect();
+            xs.sort();
+
+            let mut heap: RadixHeapMap<_, _> =
+                xs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+
+            while xs.pop() == heap.pop().map(|(k, _)| k) {
+                if xs.is_empty() {
+                    return TestResult::passed();
+                }
+            }
+
+            return TestResult::failed();
+        }
+
+        quickcheck(prop as fn(Vec<f32>) -> TestResult);
+    }
+
+    #[test]
+    fn partition_by() {
+        fn prop(xs: Vec<u32>, pops: usize) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&k| (k, k % 3)).collect();
+            let mut xs = xs;
+            xs.sort();
+
+            for _ in 0..pops % (xs.len() + 1) {
+                heap.pop();
+                xs.pop();
+            }
+
+            let top = heap.top();
+            let (mut zero, mut rest) = heap.partition_by(|_, &v| v == 0);
+
+            if zero.top() != top || rest.top() != top {
+                return false;
+            }
+
+            while let Some(x) = xs.pop() {
+                let heap = if x % 3 == 0 { &mut zero } else { &mut rest };
+
+                if heap.pop() != Some((x, x % 3)) {
+                    return false;
+                }
+            }
+
+            zero.is_empty() && rest.is_empty()
+        }
+
+        quickcheck(prop as fn(Vec<u32>, usize) -> bool);
+    }
+
+    #[test]
+    fn map_keys() {
+        fn prop(xs: Vec<i32>, pops: usize, offset: i32) -> bool {
+            let offset = offset >> 2;
+            let xs: Vec<_> = xs.into_iter().map(|x| x >> 2).collect();
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&k| (k, ())).collect();
+            let mut xs = xs;
+            xs.sort();
+
+            for _ in 0..pops % (xs.len() + 1) {
+                heap.pop();
+                xs.pop();
+            }
+
+            let top = heap.top();
+            heap.map_keys(|k| k + offset);
+
+            if heap.top() != top.map(|k| k + offset) {
+                return false;
+            }
+
+            while let Some(x) = xs.pop() {
+                if heap.pop() != Some((x + offset, ())) {
+                    return false;
+                }
+            }
+
+            heap.is_empty()
+        }
+
+        quickcheck(prop as fn(Vec<i32>, usize, i32) -> bool);
+    }
+
+    #[test]
+    fn map_values() {
+        let mut heap = RadixHeapMap::new();
+        heap.extend(vec![(5u32, 1u8), (9, 2), (3, 3)]);
+        assert_eq!(heap.pop(), Some((9, 2)));
+        heap.push(1, 4);
+
+        heap.map_values_in_place(|v| *v *= 10);
+        let mut heap = heap.map_values(i64::from);
+        assert_eq!(heap.top(), Some(9));
+        assert_eq!(heap.len(), 3);
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, vec![(5, 10), (3, 30), (1, 40)]);
+    }
+
+    #[test]
+    fn merge_sorted() {
+        fn prop(xs: Vec<i16>, ys: Vec<i16>, pops: usize) -> bool {
+            let mut a: RadixHeapMap<_, _> = xs.iter().map(|&k| (k, ())).collect();
+            let b: RadixHeapMap<_, _> = ys.iter().map(|&k| (k, ())).collect();
+
+            let mut expected: Vec<_> = xs.into_iter().chain(ys).collect();
+            expected.sort();
+
+            // Popping the largest elements of both heaps leaves the rest.
+            for _ in 0..pops % 4 {
+                if let Some((k, ())) = a.pop() {
+                    let index = expected.iter().rposition(|&x| x == k).unwrap();
+                    expected.remove(index);
+                }
+            }
+
+            let merged = a.merge_sorted(&b);
+            merged.len() == expected.len()
+                && merged.map(|&(k, ())| k).eq(expected.into_iter().rev())
+        }
+
+        quickcheck(prop as fn(Vec<i16>, Vec<i16>, usize) -> bool);
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn stats() {
+        let mut heap = RadixHeapMap::new();
+        heap.extend(vec![(8u32, ()), (1, ()), (2, ()), (3, ())]);
+        heap.pop();
+        heap.pop();
+
+        let stats = heap.stats();
+        assert_eq!((stats.pushes(), stats.pops()), (4, 2));
+        assert_eq!(stats.constrains(), 2);
+        assert_eq!(stats.redistributed(), 4 + 3);
+        assert_eq!(stats.max_bucket_len(), 4);
+
+        heap.reset_stats();
+        assert_eq!(heap.stats().pushes(), 0);
+    }
+
+    #[test]
+    fn quantile_key() {
+        fn prop(xs: Vec<u32>, p: u8) -> TestResult {
+            if xs.is_empty() {
+                return TestResult::discard();
+            }
+
+            let p = f64::from(p) / 255.0;
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&k| (k, ())).collect();
+            heap.constrain();
+
+            let top = heap.top().unwrap();
+            let mut xs = xs;
+            xs.sort();
+
+            let exact = xs[((p * (xs.len() - 1) as f64).round()) as usize];
+            let approximate = heap.quantile_key(p).unwrap();
+            let distance = exact.radix_distance(&top);
+
+            TestResult::from_bool(
+                approximate.radix_distance(&top) == distance
+                    && heap.count_within(distance) >= xs.iter().filter(|&&x| x >= exact).count(),
+            )
+        }
+
+        quickcheck(prop as fn(Vec<u32>, u8) -> TestResult);
+
+        let mut heap: RadixHeapMap<_, _> = vec![(3u64, ()), (9, ())].into_iter().collect();
+        heap.constrain();
+        assert_eq!(heap.count_within(u32::MAX), 2);
+    }
+
+    #[test]
+    fn shift_keys() {
+        let mut heap = RadixHeapMap::new();
+        heap.extend(vec![(1000u32, 'a'), (1100, 'b'), (1023, 'c'), (1024, 'd')]);
+        assert_eq!(heap.pop(), Some((1100, 'b')));
+
+        heap.shift_keys_down(1000);
+        assert_eq!(heap.top(), Some(100));
+        heap.shift_keys(1);
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, vec![(25, 'd'), (24, 'c'), (1, 'a')]);
+    }
+
+    #[test]
+    fn try_pop_no_alloc() {
+        fn prop(xs: Vec<u16>) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&k| (k, ())).collect();
+            heap.reserve_buckets(xs.len());
+
+            let capacities: Vec<_> = heap
+                .buckets
+                .iter()
+                .map(|bucket| bucket.capacity())
+                .collect();
+            let mut xs = xs;
+            xs.sort();
+
+            while let Some(x) = xs.pop() {
+                if heap.try_pop_no_alloc() != Ok(Some((x, ()))) {
+                    return false;
+                }
+            }
+
+            heap.buckets
+                .iter()
+                .map(|bucket| bucket.capacity())
+                .eq(capacities)
+        }
+
+        quickcheck(prop as fn(Vec<u16>) -> bool);
+    }
+
+    #[test]
+    fn cursor() {
+        fn prop(xs: Vec<u32>, pops: usize) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&k| (k, false)).collect();
+
+            for _ in 0..pops % (xs.len() + 1) {
+                heap.pop();
+            }
+
+            let mut expected: Vec<_> = heap.keys().copied().collect();
+            let mut visited = Vec::new();
+            let mut cursor = heap.cursor();
+
+            while let Some(&(key, pushed)) = cursor.next(&heap) {
+                if !pushed {
+                    visited.push(key);
+                }
+
+                if key > 0 && heap.len() < 2 * xs.len() {
+                    heap.push(key - 1, true);
+                }
+            }
+
+            expected.sort();
+            visited.sort();
+            expected == visited
+        }
+
+        quickcheck(prop as fn(Vec<u32>, usize) -> bool);
+    }
+
+    #[test]
+    fn take_bucket() {
+        let mut heap = RadixHeapMap::new_at(100u32);
+        heap.extend((0..=100).map(|k| (k, k)));
+        assert_eq!(heap.bucket(7).len(), 64);
+
+        for (_, value) in heap.bucket_mut(7) {
+            *value *= 2;
+        }
+
+        let mut bucket = heap.take_bucket(7);
+        assert_eq!(heap.len(), 37);
+        assert!(heap.bucket(7).is_empty());
+
+        bucket.retain(|&(key, _)| key % 2 == 0);
+        heap.extend(bucket);
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped.len(), 37 + 32);
+        assert_eq!(popped.last(), Some(&(0, 0)));
+        assert_eq!(popped[37], (62, 124));
+    }
+
+    #[test]
+    fn compact() {
+        let mut heap = RadixHeapMap::new();
+        heap.extend((0..1000u32).map(|k| (k, ())));
+
+        for _ in 0..990 {
+            heap.pop();
+        }
+
+        heap.compact();
+        let inline = bucket::INLINE_ENTRIES;
+        assert!(heap
+            .buckets
+            .iter()
+            .all(|b| b.capacity() == b.len().max(inline)));
+        assert_eq!(heap.initial.capacity(), inline);
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).map(|(k, _)| k).collect();
+        assert_eq!(popped, (0..10).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn push_within_capacity() {
+        fn prop(ops: Vec<Option<u16>>, capacity: u8) -> bool {
+            let capacity = capacity as usize % 8 + 1;
+            let mut heap = RadixHeapMap::with_bucket_capacity(capacity);
+            let mut model = BinaryHeap::new();
+            let capacities = |heap: &RadixHeapMap<u16, ()>| {
+                std::iter::once(&heap.initial)
+                    .chain(&heap.buckets)
+                    .map(|bucket| bucket.capacity())
+                    .collect::<Vec<_>>()
+            };
+            let before = capacities(&heap);
+
+            for op in ops {
+                match op {
+                    Some(key) => {
+                        let key = heap.top().map_or(key, |top| top.min(key));
+                        if heap.push_within_capacity(key, ()).is_ok() {
+                            model.push(key);
+                        }
+                    }
+                    None => {
+                        if heap.pop().map(|(key, ())| key) != model.pop() {
+                            return false;
+                        }
+                    }
+                }
+            }
+
+            capacities(&heap) == before
+        }
+
+        quickcheck(prop as fn(Vec<Option<u16>>, u8) -> bool);
+    }
+
+    #[test]
+    fn peek() {
+        fn prop(xs: Vec<(u32, u8)>) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.into_iter().collect();
+
+            loop {
+                let peeked = heap.peek().map(|(&key, &value)| (key, value));
+
+                if peeked.map(|(key, _)| key) != heap.peek_key()
+                    || peeked.map(|(_, value)| value) != heap.peek_value().copied()
+                    || peeked != heap.pop()
+                {
+                    return false;
+                }
+
+                if peeked.is_none() {
+                    return true;
+                }
+            }
+        }
+
+        quickcheck(prop as fn(Vec<(u32, u8)>) -> bool);
+    }
 
-#[cfg(test)]
-mod tests {
-    extern crate quickcheck;
+    #[test]
+    fn into_sorted_vec() {
+        fn prop(xs: Vec<(i32, u8)>) -> bool {
+            let heap: RadixHeapMap<_, _> = xs.iter().copied().collect();
+            let mut expected: Vec<_> = xs.iter().map(|&(k, _)| k).collect();
+            expected.sort_unstable_by_key(|&k| Reverse(k));
 
-    use self::quickcheck::{quickcheck, TestResult};
-    use super::Radix;
-    use super::RadixHeapMap;
-    use std::cmp::Reverse;
+            let sorted = heap.clone().into_sorted_vec();
+            heap.into_iter_sorted().eq(sorted.iter().copied())
+                && sorted.iter().map(|&(k, _)| k).eq(expected)
+        }
+
+        quickcheck(prop as fn(Vec<(i32, u8)>) -> bool);
+    }
 
     #[test]
-    fn radix_dist() {
-        assert!(4u32.radix_distance(&2) == 3);
-        assert!(3u32.radix_distance(&2) == 1);
-        assert!(2u32.radix_distance(&2) == 0);
-        assert!(1u32.radix_distance(&2) == 2);
-        assert!(0u32.radix_distance(&2) == 2);
+    fn append() {
+        fn prop(xs: Vec<u16>, ys: Vec<u16>, pops: (u8, u8)) -> bool {
+            let mut a: RadixHeapMap<_, _> = xs.into_iter().map(|k| (k, ())).collect();
+            for _ in 0..pops.0 % 4 {
+                a.pop();
+            }
+
+            let top = a.top().unwrap_or(u16::MAX);
+            let mut b: RadixHeapMap<_, _> = ys
+                .into_iter()
+                .filter(|&k| k <= top)
+                .map(|k| (k, ()))
+                .collect();
+            for _ in 0..pops.1 % 4 {
+                b.pop();
+            }
+
+            let mut expected: Vec<_> = a.keys().chain(b.keys()).copied().collect();
+            expected.sort_unstable_by_key(|&k| Reverse(k));
+
+            let before = a.top();
+            a.append(&mut b);
+
+            b.is_empty()
+                && a.len() == expected.len()
+                && a.top() == before
+                && std::iter::from_fn(|| a.pop()).map(|(k, _)| k).eq(expected)
+        }
+
+        quickcheck(prop as fn(Vec<u16>, Vec<u16>, (u8, u8)) -> bool);
     }
 
     #[test]
-    fn clear() {
-        let mut heap = RadixHeapMap::new();
-        heap.push(0u32, 'a');
-        heap.clear();
-        assert!(heap.pop().is_none());
+    #[should_panic]
+    fn append_above_top() {
+        let mut a = RadixHeapMap::new_at(5u8);
+        let mut b = RadixHeapMap::new();
+        b.push(6, ());
+        a.append(&mut b);
     }
 
     #[test]
-    fn push_pop() {
-        let mut heap = RadixHeapMap::new();
-        heap.push(0u32, 'a');
-        heap.push(3, 'b');
-        heap.push(2, 'c');
+    fn capacity() {
+        let heap = RadixHeapMap::<u32, ()>::with_capacity(100);
+        assert!(heap.initial.capacity() >= 100);
 
-        assert!(heap.len() == 3);
-        assert!(!heap.is_empty());
+        let mut heap = RadixHeapMap::<u8, ()>::new_at_with_capacity(255, 100);
+        assert!(heap.buckets[..1]
+            .iter()
+            .all(|bucket| bucket.capacity() >= 12));
+        assert!(heap.buckets.iter().all(|bucket| bucket.capacity() >= 11));
 
-        assert!(heap.pop() == Some((3, 'b')));
-        assert!(heap.pop() == Some((2, 'c')));
-        assert!(heap.pop() == Some((0, 'a')));
-        assert!(heap.pop() == None);
+        heap.extend((0..20).map(|k| (k, ())));
+        heap.reserve(50);
+        assert!(heap
+            .buckets
+            .iter()
+            .all(|bucket| bucket.capacity() >= bucket.len() + 50));
 
-        assert!(heap.len() == 0);
-        assert!(heap.is_empty());
+        heap.shrink_to(4);
+        assert!(heap
+            .buckets
+            .iter()
+            .all(|bucket| bucket.capacity() >= bucket.len() && bucket.capacity() < 50));
+        assert_eq!(heap.len(), 20);
     }
 
     #[test]
-    fn rev_push_pop() {
-        let mut heap = RadixHeapMap::new();
-        heap.push(Reverse(0), 'a');
-        heap.push(Reverse(3), 'b');
-        heap.push(Reverse(2), 'c');
+    fn peek_mut() {
+        fn prop(xs: Vec<(u32, u8)>) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&(k, v)| (k, v as u32)).collect();
+            let mut expected: Vec<_> = xs.iter().map(|&(key, _)| key).collect();
+            expected.sort_unstable();
 
-        assert!(heap.len() == 3);
-        assert!(!heap.is_empty());
+            while let Some(mut top) = heap.peek_mut() {
+                *top += 1000;
+                let key = PeekMut::key(&top);
+                let (popped, value) = PeekMut::pop(top);
 
-        assert!(heap.pop() == Some((Reverse(0), 'a')));
-        assert!(heap.pop() == Some((Reverse(2), 'c')));
-        assert!(heap.pop() == Some((Reverse(3), 'b')));
-        assert!(heap.pop() == None);
+                if expected.pop() != Some(key) || popped != key || value < 1000 {
+                    return false;
+                }
+            }
 
-        assert!(heap.len() == 0);
-        assert!(heap.is_empty());
+            expected.is_empty() && heap.peek_mut().is_none()
+        }
+
+        quickcheck(prop as fn(Vec<(u32, u8)>) -> bool);
     }
 
     #[test]
-    #[should_panic]
-    fn push_pop_panic() {
+    fn retain() {
+        fn prop(xs: Vec<(u16, u8)>, modulus: u8) -> bool {
+            let modulus = modulus.max(1);
+            let mut heap: RadixHeapMap<_, _> = xs.iter().copied().collect();
+            heap.pop();
+
+            let mut expected: Vec<_> = heap
+                .iter()
+                .copied()
+                .filter(|&(_, v)| v % modulus != 0)
+                .collect();
+            expected.sort_unstable();
+
+            heap.retain(|_, value| {
+                let keep = *value % modulus != 0;
+                *value = value.wrapping_add(1);
+                keep
+            });
+
+            let mut remaining: Vec<_> = heap.iter().map(|&(k, v)| (k, v.wrapping_sub(1))).collect();
+            remaining.sort_unstable();
+
+            heap.len() == expected.len() && remaining == expected
+        }
+
+        quickcheck(prop as fn(Vec<(u16, u8)>, u8) -> bool);
+    }
+
+    #[test]
+    fn pop_if() {
         let mut heap = RadixHeapMap::new();
-        heap.push(0u32, 'a');
-        heap.push(3, 'b');
+        heap.extend(vec![(7u8, 'a'), (4, 'b'), (9, 'c')]);
 
-        assert!(heap.pop() == Some((3, 'b')));
-        heap.push(4, 'd');
+        assert_eq!(heap.pop_if(|&key, _| key > 8), Some((9, 'c')));
+        assert_eq!(heap.pop_if(|&key, _| key > 8), None);
+        assert_eq!(heap.pop_if(|_, &value| value == 'b'), None);
+        assert_eq!(heap.len(), 2);
+        assert_eq!(heap.pop_if(|_, &value| value == 'a'), Some((7, 'a')));
+        assert_eq!(heap.pop_if(|_, _| true), Some((4, 'b')));
+        assert_eq!(heap.pop_if(|_, _| true), None);
     }
 
     #[test]
-    fn sort() {
-        fn prop<T: Ord + Radix + Copy>(mut xs: Vec<T>) -> bool {
-            let mut heap: RadixHeapMap<_, _> =
-                xs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+    fn remove_where() {
+        fn prop(xs: Vec<(u16, u8)>, target: u8) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().copied().collect();
+            let mut expected = xs.clone();
+            expected.sort_by_key(|&(key, _)| key);
 
-            xs.sort();
+            heap.pop();
+            expected.pop();
 
-            while xs.pop() == heap.pop().map(|(k, _)| k) {
-                if xs.is_empty() {
-                    return true;
+            let removed = heap.remove_where(|_, &value| value == target);
+            match removed {
+                Some(item) => {
+                    let i = expected.iter().rposition(|&x| x == item).unwrap();
+                    expected.remove(i);
+                }
+                None => {
+                    if expected.iter().any(|&(_, value)| value == target) {
+                        return false;
+                    }
                 }
             }
 
-            return false;
+            heap.len() == expected.len()
+                && std::iter::from_fn(|| heap.pop())
+                    .map(|(key, _)| key)
+                    .eq(expected.into_iter().rev().map(|(key, _)| key))
         }
 
-        quickcheck(prop as fn(Vec<()>) -> bool);
-        quickcheck(prop as fn(Vec<u32>) -> bool);
-        quickcheck(prop as fn(Vec<i32>) -> bool);
-        quickcheck(prop as fn(Vec<(u32, i32)>) -> bool);
-        quickcheck(prop as fn(Vec<u8>) -> bool);
-        quickcheck(prop as fn(Vec<i16>) -> bool);
-        quickcheck(prop as fn(Vec<(i64, usize)>) -> bool);
-        quickcheck(prop as fn(Vec<i128>) -> bool);
-        quickcheck(prop as fn(Vec<u128>) -> bool);
+        quickcheck(prop as fn(Vec<(u16, u8)>, u8) -> bool);
     }
 
-    #[cfg(feature = "ordered-float")]
     #[test]
-    fn sort_float() {
-        fn prop(xs: Vec<f32>) -> TestResult {
-            if xs.iter().any(|x| x.is_nan()) {
-                return TestResult::discard();
+    fn to_sorted_vec() {
+        fn prop(xs: Vec<u32>, pops: u8) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, ())).collect();
+            for _ in 0..pops % 4 {
+                heap.pop();
             }
 
-            let mut xs: Vec<_> = xs
-                .into_iter()
-                .map(|x| ordered_float::NotNan::new(x).unwrap())
-                .collect();
-            xs.sort();
+            let mut unsorted = heap.to_vec();
+            let sorted = heap.to_sorted_vec();
+            unsorted.sort_by(|a, b| b.cmp(a));
 
-            let mut heap: RadixHeapMap<_, _> =
-                xs.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+            let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+            sorted == popped && unsorted == popped
+        }
 
-            while xs.pop() == heap.pop().map(|(k, _)| k) {
-                if xs.is_empty() {
-                    return TestResult::passed();
-                }
+        quickcheck(prop as fn(Vec<u32>, u8) -> bool);
+    }
+
+    #[test]
+    fn extend_sorted() {
+        fn prop(mut xs: Vec<u32>, pops: u8) -> bool {
+            xs.sort_unstable_by(|a, b| b.cmp(a));
+
+            let mut heap = RadixHeapMap::new();
+            let mut expected = RadixHeapMap::new();
+            heap.extend_sorted(xs.iter().map(|&x| (x, ())));
+            expected.extend(xs.iter().map(|&x| (x, ())));
+
+            // Continue from the top after a few pops.
+            for _ in 0..pops % 4 {
+                heap.pop();
+                expected.pop();
             }
 
-            return TestResult::failed();
+            let rest: Vec<_> = xs.iter().map(|&x| x / 2).collect();
+            if let Some(top) = expected.top() {
+                let rest = rest.iter().map(|&x| (x.min(top), ()));
+                heap.extend_sorted(rest.clone());
+                expected.extend(rest);
+            }
+
+            std::iter::from_fn(|| heap.pop()).eq(std::iter::from_fn(|| expected.pop()))
         }
 
-        quickcheck(prop as fn(Vec<f32>) -> TestResult);
+        quickcheck(prop as fn(Vec<u32>, u8) -> bool);
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_sorted_above_top() {
+        let mut heap = RadixHeapMap::new_at(5);
+        heap.extend_sorted(vec![(6, ()), (4, ())]);
+    }
+
+    #[cfg(feature = "extremes")]
+    #[test]
+    fn key_extremes_seen() {
+        fn prop(xs: Vec<i32>) -> bool {
+            let mut heap = RadixHeapMap::new();
+
+            for &x in &xs {
+                heap.push(x, ());
+                heap.pop();
+                heap.clear();
+                heap.push(x, ());
+            }
+
+            heap.extend(xs.iter().map(|&x| (x, ())));
+            heap.pop();
+
+            heap.max_key_seen() == xs.iter().copied().max()
+                && heap.min_key_seen() == xs.iter().copied().min()
+        }
+
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+
+        let mut heap = RadixHeapMap::new();
+        heap.extend(vec![(10u32, ()), (4, ())]);
+        heap.shift_keys(5);
+        assert_eq!(heap.max_key_seen(), Some(15));
+        assert_eq!(heap.min_key_seen(), Some(9));
     }
 
     #[test]
@@ -784,6 +4262,90 @@ mod tests {
         quickcheck(prop as fn(Vec<(i64, usize)>) -> TestResult);
     }
 
+    #[test]
+    fn iter_mut() {
+        fn prop(xs: Vec<u16>, pops: u8) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, 0u32)).collect();
+            for _ in 0..pops % 4 {
+                heap.pop();
+            }
+
+            let len = heap.len();
+            if heap.iter_mut().len() != len || heap.values_mut().count() != len {
+                return false;
+            }
+
+            for (&key, value) in &mut heap {
+                *value += key as u32;
+            }
+            for value in heap.values_mut() {
+                *value *= 2;
+            }
+
+            std::iter::from_fn(|| heap.pop()).all(|(key, value)| value == 2 * key as u32)
+        }
+
+        quickcheck(prop as fn(Vec<u16>, u8) -> bool);
+    }
+
+    #[test]
+    fn from_vec() {
+        fn prop(xs: Vec<i32>) -> bool {
+            let mut sorted = xs.clone();
+            sorted.sort_unstable_by_key(|&x| Reverse(x));
+
+            let mut heap = RadixHeapMap::from(xs.iter().map(|&x| (x, ())).collect::<Vec<_>>());
+            let mut from_sorted = RadixHeapMap::from_sorted_iter(sorted.iter().map(|&x| (x, ())));
+
+            heap.top() == sorted.first().copied()
+                && from_sorted.top() == heap.top()
+                && heap.initial.is_empty()
+                && std::iter::from_fn(|| heap.pop())
+                    .map(|(x, ())| x)
+                    .eq(sorted.iter().copied())
+                && std::iter::from_fn(|| from_sorted.pop())
+                    .map(|(x, ())| x)
+                    .eq(sorted)
+        }
+
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+    }
+
+    #[test]
+    fn lookup() {
+        fn prop(xs: Vec<u8>, pops: u8, key: u8) -> bool {
+            let mut heap: RadixHeapMap<_, _> =
+                xs.iter().enumerate().map(|(i, &x)| (x, i)).collect();
+            for _ in 0..pops % 4 {
+                heap.pop();
+            }
+
+            let mut expected: Vec<_> = heap
+                .iter()
+                .filter(|&&(k, _)| k == key)
+                .map(|&(_, v)| v)
+                .collect();
+            let mut all: Vec<_> = heap.get_all(&key).copied().collect();
+            all.sort_unstable();
+            expected.sort_unstable();
+
+            let mut popped = heap.clone();
+            let first = std::iter::from_fn(|| popped.pop())
+                .find(|&(k, _)| k == key)
+                .map(|(_, v)| v);
+            let get = heap.get(&key).copied();
+            let len = heap.len();
+
+            heap.contains_key(&key) != expected.is_empty()
+                && all == expected
+                && get == first
+                && heap.remove_key(&key).map(|(_, v)| v) == get
+                && heap.len() == len - get.is_some() as usize
+        }
+
+        quickcheck(prop as fn(Vec<u8>, u8, u8) -> bool);
+    }
+
     #[test]
     fn into_iter_inital() {
         let mut heap = RadixHeapMap::new();
@@ -808,4 +4370,238 @@ mod tests {
         vec.sort();
         assert_eq!(vec, vec![(1, 2), (5, 4)]);
     }
+
+    #[test]
+    fn iter_specializations() {
+        fn prop(xs: Vec<u32>, pops: u8, n: u8) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, x)).collect();
+            for _ in 0..pops % 4 {
+                heap.pop();
+            }
+
+            let n = n as usize % (heap.len() + 2);
+            let expected: Vec<_> = std::iter::from_fn({
+                let mut iter = heap.iter();
+                move || iter.next()
+            })
+            .collect();
+
+            let mut iter = heap.iter();
+            let nth = iter.nth(n);
+            let rest = iter.len();
+
+            let mut into_iter = heap.clone().into_iter();
+            let into_nth = into_iter.nth(n);
+
+            nth == expected.get(n).copied()
+                && into_nth == expected.get(n).copied().copied()
+                && rest == expected.len().saturating_sub(n + 1)
+                && into_iter.count() == rest
+                && iter.count() == rest
+                && heap.iter().last() == expected.last().copied()
+                && heap.clone().into_iter().last() == expected.last().copied().copied()
+                && heap.keys().last() == expected.last().map(|(k, _)| k)
+                && heap.values().nth(n) == expected.get(n).map(|(_, v)| v)
+                && heap.iter().fold(0u64, |acc, &(k, _)| {
+                    acc.wrapping_mul(3).wrapping_add(k as u64)
+                }) == expected.iter().fold(0u64, |acc, &&(k, _)| {
+                    acc.wrapping_mul(3).wrapping_add(k as u64)
+                })
+                && heap.clone().into_iter().fold(Vec::new(), |mut acc, pair| {
+                    acc.push(pair);
+                    acc
+                }) == expected.iter().copied().copied().collect::<Vec<_>>()
+        }
+
+        quickcheck(prop as fn(Vec<u32>, u8, u8) -> bool);
+    }
+
+    #[test]
+    fn split_off_at() {
+        fn prop(xs: Vec<i16>, pops: u8, key: i16) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, x)).collect();
+            for _ in 0..pops % 4 {
+                heap.pop();
+            }
+
+            let mut expected: Vec<_> = heap.iter().copied().collect();
+            expected.sort_unstable();
+            let top = heap.top();
+            let mut above = heap.split_off_at(key);
+            let tops = (above.top(), heap.top());
+
+            let popped: Vec<_> = std::iter::from_fn(|| above.pop())
+                .chain(std::iter::from_fn(|| heap.pop()))
+                .collect();
+            let split = popped.iter().position(|&(k, _)| k < key);
+
+            tops == (top, top)
+                && popped.iter().rev().copied().eq(expected)
+                && popped[split.unwrap_or(popped.len())..]
+                    .iter()
+                    .all(|&(k, _)| k < key)
+        }
+
+        quickcheck(prop as fn(Vec<i16>, u8, i16) -> bool);
+    }
+
+    #[test]
+    fn split_off_above_top() {
+        let mut heap = RadixHeapMap::new_at(100u32);
+        heap.extend(vec![(90, 'a'), (40, 'b')]);
+
+        let above = heap.split_off_at(200);
+        assert!(above.is_empty());
+        assert_eq!(heap.len(), 2);
+        assert_eq!(heap.pop(), Some((90, 'a')));
+        assert_eq!(heap.pop(), Some((40, 'b')));
+    }
+
+    #[test]
+    fn bucket_chunks() {
+        fn prop(xs: Vec<i16>, pops: u8) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, ())).collect();
+            for _ in 0..pops % 4 {
+                heap.pop();
+            }
+            heap.constrain();
+
+            let chunks: Vec<_> = heap.bucket_chunks().collect();
+            let mut reversed: Vec<_> = heap.bucket_chunks().rev().collect();
+            reversed.reverse();
+
+            chunks == reversed
+                && chunks.iter().map(|(_, chunk)| chunk.len()).sum::<usize>() == heap.len()
+                && chunks
+                    .iter()
+                    .all(|&(distance, chunk)| !chunk.is_empty() && chunk == heap.bucket(distance))
+                && chunks.windows(2).all(|w| w[0].0 < w[1].0)
+                && heap.bucket_lens().len() == i16::RADIX_BITS as usize + 1
+                && heap
+                    .bucket_lens()
+                    .enumerate()
+                    .all(|(distance, len)| len == heap.bucket(distance as u32).len())
+        }
+
+        quickcheck(prop as fn(Vec<i16>, u8) -> bool);
+    }
+
+    #[test]
+    fn try_push_alloc() {
+        let mut heap = RadixHeapMap::new();
+        heap.try_reserve(2).unwrap();
+        assert!(heap.buckets.iter().all(|bucket| bucket.capacity() >= 2));
+
+        assert_eq!(heap.try_push_alloc(5u8, 'a'), Ok(()));
+        heap.pop();
+        assert_eq!(heap.try_push_alloc(4, 'b'), Ok(()));
+        assert_eq!(heap.try_pop_no_alloc(), Ok(Some((4, 'b'))));
+    }
+
+    #[test]
+    fn from_iter_at() {
+        fn prop(xs: Vec<u16>) -> bool {
+            let top = xs.iter().copied().max().unwrap_or(0);
+            let mut heap = RadixHeapMap::from_iter_at(top, xs.iter().map(|&x| (x, ())));
+            let mut sorted = xs.clone();
+            sorted.sort_unstable();
+
+            heap.initial.is_empty()
+                && heap.top() == Some(top)
+                && std::iter::from_fn(|| heap.pop())
+                    .map(|(k, _)| k)
+                    .eq(sorted.into_iter().rev())
+        }
+
+        quickcheck(prop as fn(Vec<u16>) -> bool);
+    }
+
+    #[test]
+    fn fmt_sorted() {
+        fn prop(xs: Vec<i8>, pops: u8) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, ())).collect();
+            for _ in 0..pops % 4 {
+                heap.pop();
+            }
+
+            let rebuilt: RadixHeapMap<_, _> = heap.to_vec().into_iter().rev().collect();
+            let formatted = format!("{:?}", heap.fmt_sorted());
+
+            formatted == format!("{:?}", rebuilt.fmt_sorted())
+                && formatted == format!("{:?}", heap.to_sorted_vec())
+        }
+
+        quickcheck(prop as fn(Vec<i8>, u8) -> bool);
+    }
+
+    #[test]
+    fn pop_range() {
+        fn prop(xs: Vec<u16>, pops: u8, start: u16, end: u16) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&x| (x, ())).collect();
+            for _ in 0..pops % 4 {
+                heap.pop();
+            }
+
+            let mut remaining = heap.to_sorted_vec();
+            let removed = heap.pop_range(start..=end);
+            let expected: Vec<_> = remaining
+                .iter()
+                .copied()
+                .filter(|&(k, _)| (start..=end).contains(&k))
+                .collect();
+            remaining.retain(|&(k, _)| !(start..=end).contains(&k));
+
+            removed == expected
+                && heap.len() == remaining.len()
+                && std::iter::from_fn(|| heap.pop()).eq(remaining)
+        }
+
+        quickcheck(prop as fn(Vec<u16>, u8, u16, u16) -> bool);
+    }
+
+    #[test]
+    fn try_extend() {
+        fn prop(xs: Vec<u8>, top: u8) -> bool {
+            let mut heap = RadixHeapMap::new_at(top);
+            heap.push(top, ());
+
+            let result = heap.try_extend(xs.iter().map(|&x| (x, ())));
+            let valid = xs.iter().all(|&x| x <= top);
+
+            match result {
+                Ok(()) => valid && heap.len() == xs.len() + 1,
+                Err(error) => !valid && error.key > top && heap.len() == 1,
+            }
+        }
+
+        quickcheck(prop as fn(Vec<u8>, u8) -> bool);
+    }
+
+    #[test]
+    fn try_push() {
+        fn prop(xs: Vec<(i16, bool)>) -> bool {
+            let mut heap = RadixHeapMap::new();
+            let mut len = 0;
+
+            for &(x, pop) in &xs {
+                match heap.try_push(x, ()) {
+                    Ok(()) => len += 1,
+                    Err(error) if Some(error.key) > heap.top() => {}
+                    Err(_) => return false,
+                }
+
+                if pop && heap.pop().is_some() {
+                    len -= 1;
+                }
+
+                if heap.len() != len {
+                    return false;
+                }
+            }
+
+            true
+        }
+
+        quickcheck(prop as fn(Vec<(i16, bool)>) -> bool);
+    }
 }
@@ -0,0 +1,191 @@
+use crate::{Radix, RadixHeapMap};
+use core::cmp::Reverse;
+
+/// Maintains the running median, or any other quantile, of a stream of keys.
+///
+/// Keys are split between a max-heap holding the lower part of the stream
+/// and a min-heap holding the upper part, which are rebalanced after each
+/// insert. Both halves are radix heaps, so a key that has to go above the
+/// top of the lower half (or below the top of the upper half) raises that
+/// top first. This is cheap when the stream stays close to the current
+/// quantile, but can cost a redistribution of a bucket when it jumps around.
+///
+/// ```
+/// let mut median = radix_heap::RunningMedian::new();
+///
+/// median.insert(5u32);
+/// median.insert(1);
+/// median.insert(9);
+/// assert_eq!(median.median(), Some(5));
+///
+/// median.insert(7);
+/// assert_eq!(median.median(), Some(5));
+/// ```
+#[derive(Clone)]
+pub struct RunningMedian<K> {
+    quantile: f64,
+    lower: RadixHeapMap<K, ()>,
+    upper: RadixHeapMap<Reverse<K>, ()>,
+}
+
+impl<K: Radix + Ord + Copy> RunningMedian<K> {
+    /// Create an empty `RunningMedian` tracking the median.
+    pub fn new() -> RunningMedian<K> {
+        RunningMedian::with_quantile(0.5)
+    }
+
+    /// Create an empty `RunningMedian` tracking the given quantile instead of
+    /// the median.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `quantile` is not between 0 and 1.
+    pub fn with_quantile(quantile: f64) -> RunningMedian<K> {
+        assert!(
+            (0.0..=1.0).contains(&quantile),
+            "Quantile must be between 0 and 1"
+        );
+
+        RunningMedian {
+            quantile,
+            lower: RadixHeapMap::new(),
+            upper: RadixHeapMap::new(),
+        }
+    }
+
+    /// Inserts a key into the stream.
+    pub fn insert(&mut self, key: K) {
+        match self.lower.top() {
+            Some(max) if !self.lower.is_empty() && key <= max => push(&mut self.lower, key),
+            _ => push(&mut self.upper, Reverse(key)),
+        }
+
+        let target = self.lower_len();
+
+        while self.lower.len() > target {
+            let (key, ()) = self.lower.pop().expect("Expected non-empty heap");
+            push(&mut self.upper, Reverse(key));
+        }
+
+        while self.lower.len() < target {
+            let (Reverse(key), ()) = self.upper.pop().expect("Expected non-empty heap");
+            push(&mut self.lower, key);
+        }
+
+        self.lower.constrain();
+    }
+
+    /// The lower median of the keys inserted so far, or `None` if empty.
+    ///
+    /// If the `RunningMedian` was created using `with_quantile`, this is the
+    /// key at that quantile using the nearest-rank method.
+    #[inline]
+    pub fn median(&self) -> Option<K> {
+        if self.lower.is_empty() {
+            None
+        } else {
+            self.lower.top()
+        }
+    }
+
+    /// The quantile being tracked.
+    #[inline]
+    pub fn quantile(&self) -> f64 {
+        self.quantile
+    }
+
+    /// Returns the number of keys inserted so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lower.len() + self.upper.len()
+    }
+
+    /// Returns true if no keys have been inserted.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all keys from the `RunningMedian`.
+    pub fn clear(&mut self) {
+        self.lower.clear();
+        self.upper.clear();
+    }
+
+    /// The number of keys that should be in the lower half.
+    fn lower_len(&self) -> usize {
+        let len = self.len();
+        crate::ceil_usize(self.quantile * len as f64).clamp(len.min(1), len)
+    }
+}
+
+impl<K: Radix + Ord + Copy> Default for RunningMedian<K> {
+    fn default() -> RunningMedian<K> {
+        RunningMedian::new()
+    }
+}
+
+/// Pushes a key, raising the top of the heap first if the key is above it.
+fn push<K: Radix + Ord + Copy>(heap: &mut RadixHeapMap<K, ()>, key: K) {
+    heap.raise_top(key);
+    heap.push(key, ());
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::{quickcheck, TestResult};
+    use super::RunningMedian;
+
+    #[test]
+    fn median() {
+        fn prop(xs: Vec<i32>) -> bool {
+            let mut median = RunningMedian::new();
+            let mut sorted = Vec::new();
+
+            xs.into_iter().all(|x| {
+                median.insert(x);
+                sorted.push(x);
+                sorted.sort();
+                median.median() == Some(sorted[(sorted.len() - 1) / 2])
+            })
+        }
+
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+    }
+
+    #[test]
+    fn quantile() {
+        fn prop(xs: Vec<u16>, quantile: u8) -> TestResult {
+            if quantile > 100 {
+                return TestResult::discard();
+            }
+
+            let quantile = f64::from(quantile) / 100.0;
+            let mut running = RunningMedian::with_quantile(quantile);
+            let mut sorted = Vec::new();
+
+            TestResult::from_bool(xs.into_iter().all(|x| {
+                running.insert(x);
+                sorted.push(x);
+                sorted.sort();
+                let rank = (quantile * sorted.len() as f64).ceil() as usize;
+                running.median() == Some(sorted[rank.max(1) - 1])
+            }))
+        }
+
+        quickcheck(prop as fn(Vec<u16>, u8) -> TestResult);
+    }
+
+    #[test]
+    fn empty() {
+        let mut median = RunningMedian::<u32>::new();
+        assert_eq!(median.median(), None);
+
+        median.insert(3);
+        median.clear();
+        assert!(median.is_empty());
+        assert_eq!(median.median(), None);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{Iter, Radix, RadixHeapMap, Values};
+use core::{cmp::Reverse, fmt, iter::FromIterator};
+
+/// A monotone min-heap, implemented as a [`RadixHeapMap`] with `Reverse`
+/// keys.
+///
+/// Keys are popped in increasing order, and every pushed key must be no
+/// smaller than the [`top`](RadixMinHeapMap::top) key, which is the last key
+/// popped. This is the order Dijkstra's algorithm and A* pop costs in, without
+/// wrapping every key in `Reverse`.
+///
+/// ```
+/// let mut heap = radix_heap::RadixMinHeapMap::new();
+/// heap.push(7u32, 'a');
+/// heap.push(2, 'b');
+///
+/// assert_eq!(heap.pop(), Some((2, 'b')));
+/// heap.push(4, 'c');
+/// assert_eq!(heap.pop(), Some((4, 'c')));
+/// assert_eq!(heap.top(), Some(4));
+/// ```
+#[derive(Clone)]
+pub struct RadixMinHeapMap<K, V> {
+    heap: RadixHeapMap<Reverse<K>, V>,
+}
+
+impl<K: Radix + Ord + Copy, V> RadixMinHeapMap<K, V> {
+    /// Create an empty `RadixMinHeapMap`.
+    pub fn new() -> RadixMinHeapMap<K, V> {
+        RadixMinHeapMap {
+            heap: RadixHeapMap::new(),
+        }
+    }
+
+    /// Create an empty `RadixMinHeapMap` with the top key set to a specific
+    /// value, which must be no larger than any key pushed.
+    pub fn new_at(top: K) -> RadixMinHeapMap<K, V> {
+        RadixMinHeapMap {
+            heap: RadixHeapMap::new_at(Reverse(top)),
+        }
+    }
+
+    /// Drops all items from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// Drops all items from the heap and sets the top key to a specific
+    /// value.
+    pub fn clear_to(&mut self, top: K) {
+        self.heap.clear_to(Reverse(top));
+    }
+
+    /// Pushes a new key value pair onto the heap.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is smaller than the current top key.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        self.heap.push(Reverse(key), value);
+    }
+
+    /// Removes the smallest element from the heap and returns it, or `None`
+    /// if empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.heap.pop().map(|(Reverse(key), value)| (key, value))
+    }
+
+    /// Returns the smallest element without removing it, or `None` if empty.
+    ///
+    /// See [`RadixHeapMap::peek`].
+    pub fn peek(&mut self) -> Option<(&K, &V)> {
+        self.heap.peek().map(|(Reverse(key), value)| (key, value))
+    }
+
+    /// Returns the smallest key without removing it, or `None` if empty.
+    #[inline]
+    pub fn peek_key(&mut self) -> Option<K> {
+        self.heap.peek_key().map(|Reverse(key)| key)
+    }
+
+    /// The current top key. All keys pushed onto the heap must be larger than
+    /// this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.heap.top().map(|Reverse(key)| key)
+    }
+
+    /// Returns the number of elements in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if there are no elements in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns an iterator of all key-value pairs in the heap in arbitrary
+    /// order.
+    pub fn iter(&self) -> MinIter<'_, K, V> {
+        self.heap.iter().map(|(Reverse(key), value)| (key, value))
+    }
+
+    /// Returns an iterator of all values in the heap in arbitrary order.
+    pub fn values(&self) -> Values<'_, Reverse<K>, V> {
+        self.heap.values()
+    }
+
+    /// The underlying max-heap with `Reverse` keys.
+    #[inline]
+    pub fn as_map(&self) -> &RadixHeapMap<Reverse<K>, V> {
+        &self.heap
+    }
+
+    /// Returns the underlying max-heap with `Reverse` keys.
+    pub fn into_map(self) -> RadixHeapMap<Reverse<K>, V> {
+        self.heap
+    }
+}
+
+/// An iterator over the key-value pairs of a [`RadixMinHeapMap`].
+pub type MinIter<'a, K, V> =
+    core::iter::Map<Iter<'a, Reverse<K>, V>, fn(&'a (Reverse<K>, V)) -> (&'a K, &'a V)>;
+
+impl<K: Radix + Ord + Copy, V> Default for RadixMinHeapMap<K, V> {
+    fn default() -> RadixMinHeapMap<K, V> {
+        RadixMinHeapMap::new()
+    }
+}
+
+impl<K: Radix + Ord + Copy, V> From<RadixHeapMap<Reverse<K>, V>> for RadixMinHeapMap<K, V> {
+    fn from(heap: RadixHeapMap<Reverse<K>, V>) -> RadixMinHeapMap<K, V> {
+        RadixMinHeapMap { heap }
+    }
+}
+
+impl<K: Radix + Ord + Copy, V> FromIterator<(K, V)> for RadixMinHeapMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> RadixMinHeapMap<K, V> {
+        let mut heap = RadixMinHeapMap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<K: Radix + Ord + Copy, V> Extend<(K, V)> for RadixMinHeapMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.heap
+            .extend(iter.into_iter().map(|(key, value)| (Reverse(key), value)));
+    }
+}
+
+impl<K: Radix + Ord + Copy, V> IntoIterator for RadixMinHeapMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = core::iter::Map<crate::IntoIter<Reverse<K>, V>, fn((Reverse<K>, V)) -> (K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.heap
+            .into_iter()
+            .map(|(Reverse(key), value)| (key, value))
+    }
+}
+
+impl<K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug> fmt::Debug for RadixMinHeapMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::RadixMinHeapMap;
+
+    #[test]
+    fn sort() {
+        fn prop(mut xs: Vec<i16>) -> bool {
+            let mut heap: RadixMinHeapMap<_, _> = xs.iter().map(|&x| (x, x)).collect();
+            xs.sort_unstable();
+
+            heap.peek_key() == xs.first().copied()
+                && heap.iter().all(|(key, value)| key == value)
+                && std::iter::from_fn(|| heap.pop()).map(|(key, _)| key).eq(xs)
+        }
+
+        quickcheck(prop as fn(Vec<i16>) -> bool);
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_below_top() {
+        let mut heap = RadixMinHeapMap::new_at(5u8);
+        heap.push(4, ());
+    }
+}
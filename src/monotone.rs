@@ -0,0 +1,132 @@
+use crate::{
+    BucketKey, BucketQueue, ByteRadixHeapMap, CompactKey, CompactRadixHeapMap, LazyRadixHeapMap,
+    Radix, RadixBytes, RadixHeapMap,
+};
+
+/// A monotone max-priority queue of key-value pairs, where pushed keys must
+/// not be larger than the last key popped.
+///
+/// This is implemented for [`RadixHeapMap`], [`LazyRadixHeapMap`],
+/// [`ByteRadixHeapMap`], [`CompactRadixHeapMap`] and [`BucketQueue`], so
+/// algorithms written against it, such as
+/// [`dijkstra_with`](crate::algorithms::dijkstra_with), can pick the backend
+/// that suits their keys.
+///
+/// ```
+/// use radix_heap::{BucketQueue, MonotonePriorityQueue, RadixHeapMap};
+/// use std::cmp::Reverse;
+///
+/// fn countdown<Q: MonotonePriorityQueue<Reverse<u32>, char>>(mut queue: Q) -> Vec<char> {
+///     queue.push(Reverse(2), 'b');
+///     queue.push(Reverse(1), 'a');
+///     std::iter::from_fn(|| queue.pop()).map(|(_, c)| c).collect()
+/// }
+///
+/// assert_eq!(countdown(RadixHeapMap::new()), vec!['a', 'b']);
+/// assert_eq!(countdown(BucketQueue::new(1)), vec!['a', 'b']);
+/// ```
+pub trait MonotonePriorityQueue<K, V> {
+    /// Pushes a new key value pair onto the queue.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key, or if the queue
+    /// otherwise cannot hold it, such as a key too far below the top key of a
+    /// [`BucketQueue`].
+    fn push(&mut self, key: K, value: V);
+
+    /// Removes the greatest element from the queue and returns it, or `None`
+    /// if empty. This sets the top key to the extracted key.
+    fn pop(&mut self) -> Option<(K, V)>;
+
+    /// The current top key. All keys pushed onto the queue must be smaller
+    /// than this value.
+    fn top(&self) -> Option<K>;
+
+    /// Returns the number of elements in the queue.
+    fn len(&self) -> usize;
+
+    /// Returns true if there are no elements in the queue.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all items from the queue and sets the top key to `None`.
+    fn clear(&mut self);
+}
+
+macro_rules! monotone_impl {
+    ($heap:ident, $($bound:tt)+) => {
+        impl<K: $($bound)+, V> MonotonePriorityQueue<K, V> for $heap<K, V> {
+            #[inline]
+            fn push(&mut self, key: K, value: V) {
+                $heap::push(self, key, value)
+            }
+
+            #[inline]
+            fn pop(&mut self) -> Option<(K, V)> {
+                $heap::pop(self)
+            }
+
+            #[inline]
+            fn top(&self) -> Option<K> {
+                $heap::top(self)
+            }
+
+            #[inline]
+            fn len(&self) -> usize {
+                $heap::len(self)
+            }
+
+            #[inline]
+            fn clear(&mut self) {
+                $heap::clear(self)
+            }
+        }
+    };
+}
+
+monotone_impl!(RadixHeapMap, Radix + Ord + Clone);
+monotone_impl!(LazyRadixHeapMap, Radix + Ord + Clone);
+monotone_impl!(ByteRadixHeapMap, RadixBytes + Ord + Copy);
+monotone_impl!(BucketQueue, BucketKey);
+monotone_impl!(CompactRadixHeapMap, CompactKey);
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::MonotonePriorityQueue;
+    use crate::{
+        BucketQueue, ByteRadixHeapMap, CompactRadixHeapMap, LazyRadixHeapMap, RadixHeapMap,
+    };
+
+    fn sort<Q: MonotonePriorityQueue<u8, ()>>(mut queue: Q, xs: &[u8]) -> Vec<u8> {
+        for &x in xs {
+            queue.push(x, ());
+        }
+
+        let sorted = std::iter::from_fn(|| queue.pop())
+            .map(|(k, ())| k)
+            .collect();
+        queue.clear();
+        assert!(queue.is_empty() && queue.top().is_none());
+        sorted
+    }
+
+    #[test]
+    fn backends_agree() {
+        fn prop(xs: Vec<u8>) -> bool {
+            let expected = sort(RadixHeapMap::new(), &xs);
+
+            expected == sort(LazyRadixHeapMap::new(), &xs)
+                && expected == sort(ByteRadixHeapMap::new(), &xs)
+                && expected == sort(CompactRadixHeapMap::new(), &xs)
+                && expected == sort(BucketQueue::new(255), &xs)
+        }
+
+        quickcheck(prop as fn(Vec<u8>) -> bool);
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(feature = "observer")]
+use alloc::sync::Arc;
+use core::marker::PhantomData;
+
+/// An operation on a [`RadixHeapMap`](crate::RadixHeapMap), passed to its
+/// observer.
+#[cfg(feature = "observer")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Event<K> {
+    /// A key was pushed. `distance` is its radix distance from the top key,
+    /// or `None` if no top key was set yet.
+    Push {
+        /// The pushed key.
+        key: K,
+        /// The radix distance from the top key.
+        distance: Option<u32>,
+    },
+
+    /// A key was popped.
+    Pop {
+        /// The popped key.
+        key: K,
+    },
+
+    /// The top key was set to the largest key, moving items between buckets.
+    Constrain {
+        /// The new top key.
+        top: K,
+        /// The number of items moved between buckets.
+        moved: usize,
+    },
+}
+
+/// The observer of a heap, which is empty unless the `observer` feature is
+/// enabled.
+pub(crate) struct Hooks<K> {
+    #[cfg(feature = "observer")]
+    observer: Option<Arc<dyn Fn(Event<K>) + Send + Sync>>,
+    key: PhantomData<fn(K)>,
+}
+
+impl<K> Hooks<K> {
+    #[cfg(feature = "observer")]
+    pub(crate) fn set(&mut self, observer: Option<Arc<dyn Fn(Event<K>) + Send + Sync>>) {
+        self.observer = observer;
+    }
+
+    #[cfg(feature = "observer")]
+    #[inline]
+    fn emit(&self, event: Event<K>) {
+        if let Some(observer) = &self.observer {
+            observer(event);
+        }
+    }
+}
+
+impl<K: Clone> Hooks<K> {
+    #[inline]
+    pub(crate) fn push(&self, _key: &K, _distance: Option<u32>) {
+        #[cfg(feature = "observer")]
+        self.emit(Event::Push {
+            key: _key.clone(),
+            distance: _distance,
+        });
+    }
+
+    #[inline]
+    pub(crate) fn pop(&self, _key: &K) {
+        #[cfg(feature = "observer")]
+        self.emit(Event::Pop { key: _key.clone() });
+    }
+
+    #[inline]
+    pub(crate) fn constrain(&self, _top: &K, _moved: usize) {
+        #[cfg(feature = "observer")]
+        self.emit(Event::Constrain {
+            top: _top.clone(),
+            moved: _moved,
+        });
+    }
+}
+
+impl<K> Default for Hooks<K> {
+    fn default() -> Hooks<K> {
+        Hooks {
+            #[cfg(feature = "observer")]
+            observer: None,
+            key: PhantomData,
+        }
+    }
+}
+
+impl<K> Clone for Hooks<K> {
+    fn clone(&self) -> Hooks<K> {
+        Hooks {
+            #[cfg(feature = "observer")]
+            observer: self.observer.clone(),
+            key: PhantomData,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "observer"))]
+mod tests {
+    use super::Event;
+    use crate::RadixHeapMap;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn events() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut heap = RadixHeapMap::new();
+
+        let log = events.clone();
+        heap.set_observer(move |event| log.lock().unwrap().push(event));
+
+        heap.push(5u8, ());
+        heap.push(3, ());
+        heap.pop();
+        heap.push(4, ());
+        heap.clear_observer();
+        heap.pop();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                Event::Push {
+                    key: 5,
+                    distance: None
+                },
+                Event::Push {
+                    key: 3,
+                    distance: None
+                },
+                Event::Constrain { top: 5, moved: 2 },
+                Event::Pop { key: 5 },
+                Event::Push {
+                    key: 4,
+                    distance: Some(1)
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Building blocks for implementing [`Radix`](crate::Radix) on custom keys.
+
+pub mod combinators;
+
+/// Implements [`Radix`](crate::Radix) for fieldless enums by comparing their
+/// discriminants as the integer type they are represented by.
+///
+/// The enum must be `Copy`, so it can be cast to that type, and must be
+/// ordered like its discriminants, which a derived `Ord` is if the
+/// discriminants increase in declaration order, as they do by default.
+/// `RADIX_BITS` is the number of bits of the integer type. Several enums can
+/// be listed at once.
+///
+/// ```
+/// use radix_heap::{impl_radix_for_repr_enum, RadixHeapMap};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// #[repr(u8)]
+/// enum Class {
+///     Background,
+///     Normal,
+///     Urgent = 7,
+/// }
+///
+/// impl_radix_for_repr_enum!(Class: u8);
+///
+/// // Layered priorities compose with tuples and options.
+/// let mut heap = RadixHeapMap::new();
+/// heap.push((Class::Normal, Some(3u32)), 'a');
+/// heap.push((Class::Urgent, None), 'b');
+/// heap.push((Class::Normal, None), 'c');
+///
+/// assert_eq!(heap.pop().map(|(_, v)| v), Some('b'));
+/// assert_eq!(heap.pop().map(|(_, v)| v), Some('a'));
+/// assert_eq!(heap.pop().map(|(_, v)| v), Some('c'));
+/// ```
+#[macro_export]
+macro_rules! impl_radix_for_repr_enum {
+    ($($t:ty: $repr:ty),+ $(,)?) => {
+        $(
+            impl $crate::Radix for $t {
+                #[inline]
+                fn radix_similarity(&self, other: &$t) -> u32 {
+                    $crate::Radix::radix_similarity(&(*self as $repr), &(*other as $repr))
+                }
+
+                const RADIX_BITS: u32 = <$repr as $crate::Radix>::RADIX_BITS;
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use crate::Radix;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    #[repr(i8)]
+    enum Signed {
+        Low = -3,
+        Zero = 0,
+        High = 4,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Plain {
+        A,
+        B,
+        C,
+    }
+
+    impl_radix_for_repr_enum!(Signed: i8, Plain: u16);
+
+    #[test]
+    fn repr_enum() {
+        fn prop(a: u8, b: u8) -> bool {
+            let signed = [Signed::Low, Signed::Zero, Signed::High];
+            let plain = [Plain::A, Plain::B, Plain::C];
+            let (i, j) = (a as usize % 3, b as usize % 3);
+
+            signed[i].radix_similarity(&signed[j])
+                == (signed[i] as i8).radix_similarity(&(signed[j] as i8))
+                && plain[i].radix_similarity(&plain[j]) == (i as u16).radix_similarity(&(j as u16))
+        }
+
+        assert_eq!(Signed::RADIX_BITS, 8);
+        assert_eq!(Plain::RADIX_BITS, 16);
+        quickcheck(prop as fn(u8, u8) -> bool);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn consistent() {
+        use crate::testing::check_radix_consistency;
+
+        fn prop(keys: Vec<Option<(bool, Option<u8>)>>) -> bool {
+            check_radix_consistency(&keys).is_ok()
+        }
+
+        quickcheck(prop as fn(Vec<Option<(bool, Option<u8>)>>) -> bool);
+        assert!(check_radix_consistency(&[Signed::Low, Signed::High, Signed::Zero]).is_ok());
+    }
+}
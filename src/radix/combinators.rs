@@ -0,0 +1,189 @@
+//! Functions for computing the radix similarity of custom keys from the
+//! similarity of simpler ones.
+//!
+//! A [`Radix`] impl is consistent with the key's [`Ord`] impl if the
+//! similarity of two keys is `RADIX_BITS` exactly when they are equal, and
+//! keys further apart in the order never have a larger similarity than keys
+//! between them. Each combinator below documents what it needs of its inputs
+//! to produce a consistent similarity, and what `RADIX_BITS` to use with it.
+//!
+//! ```
+//! use radix_heap::{radix::combinators::{invert, lexicographic}, Radix};
+//! use std::cmp::Ordering;
+//!
+//! /// Ordered by deadline, then by descending id.
+//! #[derive(Clone, Copy, PartialEq, Eq)]
+//! struct Job {
+//!     deadline: u32,
+//!     id: u16,
+//! }
+//!
+//! impl Ord for Job {
+//!     fn cmp(&self, other: &Job) -> Ordering {
+//!         self.deadline.cmp(&other.deadline).then(other.id.cmp(&self.id))
+//!     }
+//! }
+//!
+//! impl PartialOrd for Job {
+//!     fn partial_cmp(&self, other: &Job) -> Option<Ordering> {
+//!         Some(self.cmp(other))
+//!     }
+//! }
+//!
+//! impl Radix for Job {
+//!     fn radix_similarity(&self, other: &Job) -> u32 {
+//!         lexicographic((&self.deadline, &other.deadline), || {
+//!             invert(&self.id, &other.id)
+//!         })
+//!     }
+//!
+//!     const RADIX_BITS: u32 = u32::RADIX_BITS + u16::RADIX_BITS;
+//! }
+//!
+//! let mut heap = radix_heap::RadixHeapMap::new();
+//! heap.push(Job { deadline: 5, id: 1 }, 'a');
+//! heap.push(Job { deadline: 5, id: 0 }, 'b');
+//! assert_eq!(heap.pop().map(|(_, v)| v), Some('b'));
+//! ```
+
+use crate::Radix;
+use core::{num::Wrapping, ops::Sub};
+
+/// The similarity of keys ordered first by a field of type `A`, and then by
+/// the rest of the key.
+///
+/// Returns the similarity of the first fields if they differ, and otherwise
+/// `A::RADIX_BITS` plus the similarity of the rest, given by `rest`. Use
+/// `A::RADIX_BITS` plus the `RADIX_BITS` of the rest as the `RADIX_BITS` of
+/// the key.
+///
+/// This is consistent if the similarities of both the first field and the
+/// rest are. `rest` is only called when the first fields are equal.
+#[inline]
+pub fn lexicographic<A: Radix>(first: (&A, &A), rest: impl FnOnce() -> u32) -> u32 {
+    let similarity = first.0.radix_similarity(first.1);
+
+    if similarity < A::RADIX_BITS {
+        similarity
+    } else {
+        similarity + rest()
+    }
+}
+
+/// The similarity of `x` and `y` as given by the similarity of `bits(x)` and
+/// `bits(y)`. Use `B::RADIX_BITS` as the `RADIX_BITS` of the key.
+///
+/// This is consistent if `bits` maps keys to `B` in a way that preserves their
+/// order, that is `x < y` exactly when `bits(x) < bits(y)`, as long as the
+/// similarity of `B` is consistent. Mapping floats to integers with the same
+/// order is a common use.
+#[inline]
+pub fn from_bits<T, B: Radix>(x: &T, y: &T, bits: impl Fn(&T) -> B) -> u32 {
+    bits(x).radix_similarity(&bits(y))
+}
+
+/// The similarity of `x` and `y` measured relative to `origin`, by
+/// subtracting `origin` from both with wrapping arithmetic. Use
+/// `K::RADIX_BITS` as the `RADIX_BITS` of the key.
+///
+/// This is consistent for keys that are all within `2^K::RADIX_BITS` of
+/// `origin`, counting upwards and wrapping around, as long as they are ordered
+/// by their distance from `origin`. This suits sequence numbers and clocks
+/// that wrap around, where `origin` is a point known to be at or before every
+/// key in the heap.
+#[inline]
+pub fn offset<K>(x: &K, y: &K, origin: K) -> u32
+where
+    K: Copy,
+    Wrapping<K>: Sub<Output = Wrapping<K>> + Radix,
+{
+    let x = Wrapping(*x) - Wrapping(origin);
+    let y = Wrapping(*y) - Wrapping(origin);
+    x.radix_similarity(&y)
+}
+
+/// The similarity of keys ordered in reverse. Use `K::RADIX_BITS` as the
+/// `RADIX_BITS` of the key.
+///
+/// Radix similarity counts the leading bits two keys have in common, which
+/// does not depend on the direction of the order, so this is always
+/// consistent if the similarity of `K` is. It equals
+/// `x.radix_similarity(y)`, and exists to make reversed fields explicit, as
+/// with [`Reverse`](std::cmp::Reverse).
+#[inline]
+pub fn invert<K: Radix>(x: &K, y: &K) -> u32 {
+    x.radix_similarity(y)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::{from_bits, invert, lexicographic, offset};
+    use crate::Radix;
+
+    #[test]
+    fn lexicographic_matches_tuples() {
+        fn prop(a: (u8, i16), b: (u8, i16)) -> bool {
+            lexicographic((&a.0, &b.0), || a.1.radix_similarity(&b.1)) == a.radix_similarity(&b)
+        }
+
+        quickcheck(prop as fn((u8, i16), (u8, i16)) -> bool);
+    }
+
+    #[test]
+    fn from_bits_matches_signed() {
+        fn prop(x: i32, y: i32) -> bool {
+            let bits = |&x: &i32| (x as u32) ^ (1 << 31);
+            from_bits(&x, &y, bits) == x.radix_similarity(&y)
+        }
+
+        quickcheck(prop as fn(i32, i32) -> bool);
+    }
+
+    #[test]
+    fn offset_wraps() {
+        assert_eq!(offset(&250u8, &251, 250), 7);
+        assert_eq!(offset(&250u8, &4, 250), 4);
+        assert_eq!(offset(&250u8, &250, 250), 8);
+        assert_eq!(invert(&3u8, &5), 5);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn consistent() {
+        use crate::testing::check_radix_consistency;
+        use std::cmp::Ordering;
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        struct Key(u8, u16);
+
+        impl Ord for Key {
+            fn cmp(&self, other: &Key) -> Ordering {
+                self.0.cmp(&other.0).then(other.1.cmp(&self.1))
+            }
+        }
+
+        impl PartialOrd for Key {
+            fn partial_cmp(&self, other: &Key) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Radix for Key {
+            fn radix_similarity(&self, other: &Key) -> u32 {
+                lexicographic((&self.0, &other.0), || invert(&self.1, &other.1))
+            }
+
+            const RADIX_BITS: u32 = 24;
+        }
+
+        fn prop(keys: Vec<(u8, u16)>) -> bool {
+            let keys: Vec<_> = keys.into_iter().map(|(a, b)| Key(a, b)).collect();
+            check_radix_consistency(&keys).is_ok()
+        }
+
+        quickcheck(prop as fn(Vec<(u8, u16)>) -> bool);
+    }
+}
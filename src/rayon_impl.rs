@@ -0,0 +1,146 @@
+use crate::{Bucket, Radix, RadixHeapMap};
+use rayon::{
+    iter::{
+        plumbing::UnindexedConsumer, Chain, FlatMap, FromParallelIterator, IntoParallelIterator,
+        Map, ParallelIterator,
+    },
+    slice,
+};
+
+type BucketIter<'a, K, V> = slice::Iter<'a, (K, V)>;
+
+type Inner<'a, K, V> = Chain<
+    BucketIter<'a, K, V>,
+    FlatMap<slice::Iter<'a, Bucket<K, V>>, fn(&'a Bucket<K, V>) -> BucketIter<'a, K, V>>,
+>;
+
+type Value<'a, K, V> = fn(&'a (K, V)) -> &'a V;
+
+impl<K: Radix + Ord + Clone + Sync, V: Sync> RadixHeapMap<K, V> {
+    /// Returns a parallel iterator of all key-value pairs in the heap in
+    /// arbitrary order.
+    ///
+    /// Each bucket is a slice, so the work is split across buckets and
+    /// within the larger ones.
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    ///
+    /// let heap: radix_heap::RadixHeapMap<u32, u32> = (0..1000).map(|k| (k, k)).collect();
+    /// assert_eq!(heap.par_iter().map(|&(_, v)| v).sum::<u32>(), 499500);
+    /// ```
+    pub fn par_iter(&self) -> ParIter<'_, K, V> {
+        let bucket: fn(&Bucket<K, V>) -> BucketIter<'_, K, V> =
+            |bucket| bucket.as_slice().into_par_iter();
+
+        ParIter(
+            self.initial
+                .as_slice()
+                .into_par_iter()
+                .chain(self.buckets.as_slice().into_par_iter().flat_map(bucket)),
+        )
+    }
+
+    /// Returns a parallel iterator of all values in the heap in arbitrary
+    /// order.
+    pub fn par_values(&self) -> ParValues<'_, K, V> {
+        ParValues(self.par_iter().map(|(_, value)| value))
+    }
+}
+
+/// A parallel iterator over the key-value pairs of a [`RadixHeapMap`].
+///
+/// This `struct` is created by [`RadixHeapMap::par_iter`].
+pub struct ParIter<'a, K: Sync, V: Sync>(Inner<'a, K, V>);
+
+impl<'a, K: Sync, V: Sync> ParallelIterator for ParIter<'a, K, V> {
+    type Item = &'a (K, V);
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.0.drive_unindexed(consumer)
+    }
+}
+
+/// A parallel iterator over the values of a [`RadixHeapMap`].
+///
+/// This `struct` is created by [`RadixHeapMap::par_values`].
+pub struct ParValues<'a, K: Sync, V: Sync>(Map<ParIter<'a, K, V>, Value<'a, K, V>>);
+
+impl<'a, K: Sync, V: Sync> ParallelIterator for ParValues<'a, K, V> {
+    type Item = &'a V;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.0.drive_unindexed(consumer)
+    }
+}
+
+impl<'a, K: Radix + Ord + Clone + Sync, V: Sync> IntoParallelIterator for &'a RadixHeapMap<K, V> {
+    type Item = &'a (K, V);
+    type Iter = ParIter<'a, K, V>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter()
+    }
+}
+
+/// Collects into a heap per thread, and moves the items of those heaps
+/// together with [`append`](RadixHeapMap::append).
+impl<K: Radix + Ord + Clone + Send, V: Send> FromParallelIterator<(K, V)> for RadixHeapMap<K, V> {
+    fn from_par_iter<I: IntoParallelIterator<Item = (K, V)>>(iter: I) -> RadixHeapMap<K, V> {
+        iter.into_par_iter()
+            .fold(RadixHeapMap::new, |mut heap, (key, value)| {
+                heap.push(key, value);
+                heap
+            })
+            .reduce(RadixHeapMap::new, |mut heap, mut other| {
+                heap.append(&mut other);
+                heap
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use crate::RadixHeapMap;
+    use rayon::prelude::*;
+
+    #[test]
+    fn par_iter() {
+        fn prop(xs: Vec<u16>, pops: usize) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.iter().map(|&k| (k, k as u32)).collect();
+
+            for _ in 0..pops % (xs.len() + 1) {
+                heap.pop();
+            }
+
+            let mut expected: Vec<_> = heap.iter().copied().collect();
+            let mut entries: Vec<_> = heap.par_iter().copied().collect();
+            expected.sort_unstable();
+            entries.sort_unstable();
+
+            entries == expected
+                && heap.par_values().sum::<u32>() == heap.values().sum::<u32>()
+                && (&heap).into_par_iter().count() == heap.len()
+        }
+
+        quickcheck(prop as fn(Vec<u16>, usize) -> bool);
+    }
+
+    #[test]
+    fn from_par_iter() {
+        fn prop(mut xs: Vec<i32>) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.par_iter().map(|&k| (k, ())).collect();
+            xs.sort_unstable();
+
+            heap.len() == xs.len()
+                && std::iter::from_fn(|| heap.pop())
+                    .map(|(k, ())| k)
+                    .eq(xs.into_iter().rev())
+        }
+
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+    }
+}
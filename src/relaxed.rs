@@ -0,0 +1,196 @@
+use crate::{Radix, RadixHeapMap};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A radix heap that accepts keys larger than the top key instead of
+/// panicking, for keys that are only almost monotone, such as A* costs with
+/// a heuristic that is occasionally inconsistent.
+///
+/// Keys larger than the top key go into an overflow bucket. The next pop
+/// raises the top key to the largest of them and moves them into the heap,
+/// which only redistributes the buckets closest to the old top key. Pushes
+/// that respect the top key cost the same as for a [`RadixHeapMap`].
+///
+/// ```
+/// let mut heap = radix_heap::RelaxedRadixHeapMap::new();
+/// heap.push(5u32, 'a');
+/// heap.push(3, 'b');
+/// assert_eq!(heap.pop(), Some((5, 'a')));
+///
+/// heap.push(7, 'c');
+/// assert_eq!(heap.overflow_len(), 1);
+/// assert_eq!(heap.pop(), Some((7, 'c')));
+/// assert_eq!(heap.pop(), Some((3, 'b')));
+/// ```
+#[derive(Clone)]
+pub struct RelaxedRadixHeapMap<K, V> {
+    heap: RadixHeapMap<K, V>,
+    overflow: Vec<(K, V)>,
+    overflow_max: Option<K>,
+}
+
+impl<K: Radix + Ord + Copy, V> RelaxedRadixHeapMap<K, V> {
+    /// Create an empty `RelaxedRadixHeapMap`.
+    pub fn new() -> RelaxedRadixHeapMap<K, V> {
+        RelaxedRadixHeapMap {
+            heap: RadixHeapMap::new(),
+            overflow: Vec::new(),
+            overflow_max: None,
+        }
+    }
+
+    /// Create an empty `RelaxedRadixHeapMap` with the top key set to a
+    /// specific value.
+    pub fn new_at(top: K) -> RelaxedRadixHeapMap<K, V> {
+        RelaxedRadixHeapMap {
+            heap: RadixHeapMap::new_at(top),
+            ..RelaxedRadixHeapMap::new()
+        }
+    }
+
+    /// Pushes a new key value pair onto the heap. Keys larger than the top
+    /// key are kept in the overflow bucket until the next pop.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        match self.heap.top {
+            Some(top) if key > top => {
+                self.overflow.push((key, value));
+                self.overflow_max = self.overflow_max.max(Some(key));
+            }
+            _ => self.heap.push_unchecked(key, value),
+        }
+    }
+
+    /// Removes the greatest element from the heap and returns it, or `None`
+    /// if empty.
+    ///
+    /// If the overflow bucket is not empty, its items are moved into the heap
+    /// first.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.fold();
+        self.heap.pop()
+    }
+
+    /// Returns the greatest key without removing it, or `None` if empty.
+    pub fn peek_key(&mut self) -> Option<K> {
+        self.fold();
+        self.heap.peek_key()
+    }
+
+    /// The top key of the heap, which is the last key popped. Unlike for
+    /// [`RadixHeapMap`], larger keys can still be pushed.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.heap.top()
+    }
+
+    /// Returns the number of elements in the heap, including the overflow
+    /// bucket.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len() + self.overflow.len()
+    }
+
+    /// Returns true if there are no elements in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements pushed with keys larger than the top
+    /// key since the last pop.
+    #[inline]
+    pub fn overflow_len(&self) -> usize {
+        self.overflow.len()
+    }
+
+    /// Drops all items from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.overflow.clear();
+        self.overflow_max = None;
+    }
+
+    /// Returns the underlying heap, with the items of the overflow bucket
+    /// moved into it.
+    pub fn into_map(mut self) -> RadixHeapMap<K, V> {
+        self.fold();
+        self.heap
+    }
+
+    /// Raises the top key to the largest key in the overflow bucket, and moves
+    /// the items of the overflow bucket into the heap.
+    fn fold(&mut self) {
+        if let Some(max) = self.overflow_max.take() {
+            self.heap.raise_top(max);
+
+            for (key, value) in self.overflow.drain(..) {
+                self.heap.push_unchecked(key, value);
+            }
+        }
+    }
+}
+
+impl<K: Radix + Ord + Copy, V> Default for RelaxedRadixHeapMap<K, V> {
+    fn default() -> RelaxedRadixHeapMap<K, V> {
+        RelaxedRadixHeapMap::new()
+    }
+}
+
+impl<K: Radix + Ord + Copy, V> Extend<(K, V)> for RelaxedRadixHeapMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.push(key, value);
+        }
+    }
+}
+
+impl<K: Radix + Ord + Copy + fmt::Debug, V: fmt::Debug> fmt::Debug for RelaxedRadixHeapMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.heap.iter().chain(&self.overflow))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::RelaxedRadixHeapMap;
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn matches_binary_heap() {
+        fn prop(ops: Vec<Option<i16>>) -> bool {
+            let mut heap = RelaxedRadixHeapMap::new();
+            let mut expected = BinaryHeap::new();
+
+            for op in ops {
+                match op {
+                    Some(key) => {
+                        heap.push(key, ());
+                        expected.push(key);
+                    }
+                    None => {
+                        if heap.pop().map(|(key, ())| key) != expected.pop() {
+                            return false;
+                        }
+                    }
+                }
+
+                if heap.len() != expected.len() {
+                    return false;
+                }
+            }
+
+            std::iter::from_fn(|| heap.pop())
+                .map(|(key, ())| key)
+                .eq(expected.into_sorted_vec().into_iter().rev())
+        }
+
+        quickcheck(prop as fn(Vec<Option<i16>>) -> bool);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::RadixHeapMap;
+use core::{cmp::Reverse, fmt, iter::FusedIterator};
+
+/// A buffer that puts events arriving out of order back in order, as long as
+/// they arrive at most a fixed lateness after the latest event.
+///
+/// Events are keyed by integer timestamps. Once an event with timestamp `t`
+/// has arrived, events up to `t - lateness` are assumed complete and can be
+/// emitted in order of timestamp. An event arriving after events later than
+/// it were emitted is late, and is returned in an error instead of being
+/// buffered.
+///
+/// ```
+/// let mut buffer = radix_heap::ReorderBuffer::new(10);
+///
+/// buffer.push(100, 'a').unwrap();
+/// buffer.push(95, 'b').unwrap();
+/// buffer.push(112, 'c').unwrap();
+///
+/// let emitted: Vec<_> = buffer.emit().collect();
+/// assert_eq!(emitted, vec![(95, 'b'), (100, 'a')]);
+///
+/// assert!(buffer.push(101, 'd').is_err());
+/// assert_eq!(buffer.flush().collect::<Vec<_>>(), vec![(112, 'c')]);
+/// ```
+#[derive(Clone)]
+pub struct ReorderBuffer<T> {
+    heap: RadixHeapMap<Reverse<u64>, T>,
+    lateness: u64,
+    max_seen: Option<u64>,
+    watermark: Option<u64>,
+}
+
+impl<T> ReorderBuffer<T> {
+    /// Create an empty `ReorderBuffer` accepting events up to `lateness`
+    /// behind the latest event.
+    pub fn new(lateness: u64) -> ReorderBuffer<T> {
+        ReorderBuffer {
+            heap: RadixHeapMap::new(),
+            lateness,
+            max_seen: None,
+            watermark: None,
+        }
+    }
+
+    /// Buffers an event with timestamp `time`.
+    ///
+    /// Returns an error containing the event if it is late, that is if it is
+    /// before the [`watermark`](ReorderBuffer::watermark).
+    pub fn push(&mut self, time: u64, event: T) -> Result<(), LateEvent<T>> {
+        if matches!(self.watermark, Some(watermark) if time < watermark) {
+            return Err(LateEvent { time, event });
+        }
+
+        if !matches!(self.max_seen, Some(max) if max >= time) {
+            self.max_seen = Some(time);
+            self.advance(time.checked_sub(self.lateness));
+        }
+
+        // Events after the watermark may be earlier than the earliest event
+        // the heap has seen.
+        self.heap.raise_top(Reverse(time));
+        self.heap.push(Reverse(time), event);
+        Ok(())
+    }
+
+    /// Returns an iterator of the events at or before the watermark, in
+    /// order of timestamp.
+    ///
+    /// Events not consumed by the iterator stay in the buffer.
+    pub fn emit(&mut self) -> Emit<'_, T> {
+        Emit {
+            heap: &mut self.heap,
+            until: self.watermark,
+        }
+    }
+
+    /// Advances the watermark to the latest event and returns an iterator of
+    /// all buffered events, in order of timestamp, such as at the end of a
+    /// stream.
+    ///
+    /// Events before the latest event are late afterwards.
+    pub fn flush(&mut self) -> Emit<'_, T> {
+        self.advance(self.max_seen);
+        Emit {
+            heap: &mut self.heap,
+            until: Some(u64::MAX),
+        }
+    }
+
+    /// The earliest timestamp still accepted, or `None` if every timestamp is
+    /// accepted.
+    ///
+    /// Events at or before the watermark are ready to be emitted.
+    #[inline]
+    pub fn watermark(&self) -> Option<u64> {
+        self.watermark
+    }
+
+    /// The latest timestamp pushed, or `None` if nothing was pushed.
+    #[inline]
+    pub fn max_seen(&self) -> Option<u64> {
+        self.max_seen
+    }
+
+    /// The lateness allowed behind the latest event.
+    #[inline]
+    pub fn lateness(&self) -> u64 {
+        self.lateness
+    }
+
+    /// Returns the number of buffered events.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if there are no buffered events.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    fn advance(&mut self, watermark: Option<u64>) {
+        if watermark > self.watermark {
+            self.watermark = watermark;
+        }
+    }
+}
+
+/// An iterator over the events of a [`ReorderBuffer`] that are ready to be
+/// emitted.
+///
+/// This `struct` is created by [`ReorderBuffer::emit`] and
+/// [`ReorderBuffer::flush`].
+pub struct Emit<'a, T> {
+    heap: &'a mut RadixHeapMap<Reverse<u64>, T>,
+    until: Option<u64>,
+}
+
+impl<'a, T> Iterator for Emit<'a, T> {
+    type Item = (u64, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let until = self.until?;
+
+        self.heap
+            .pop_if(|&Reverse(time), _| time <= until)
+            .map(|(Reverse(time), event)| (time, event))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.heap.len()))
+    }
+}
+
+impl<'a, T> FusedIterator for Emit<'a, T> {}
+
+/// The error returned when pushing an event before the watermark of a
+/// [`ReorderBuffer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LateEvent<T> {
+    /// The timestamp of the rejected event.
+    pub time: u64,
+
+    /// The rejected event.
+    pub event: T,
+}
+
+impl<T> fmt::Display for LateEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "event at {} is before the watermark", self.time)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for LateEvent<T> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::ReorderBuffer;
+
+    #[test]
+    fn in_order() {
+        fn prop(times: Vec<u16>, lateness: u8) -> bool {
+            let mut buffer = ReorderBuffer::new(lateness as u64);
+            let mut emitted = Vec::new();
+            let mut late = 0;
+
+            for (i, &time) in times.iter().enumerate() {
+                match buffer.push(time as u64, i) {
+                    Ok(()) => emitted.extend(buffer.emit()),
+                    Err(_) => late += 1,
+                }
+
+                if buffer
+                    .heap
+                    .iter()
+                    .any(|&(key, _)| Some(key.0) < buffer.watermark)
+                {
+                    return false;
+                }
+            }
+            emitted.extend(buffer.flush());
+
+            emitted.len() + late == times.len()
+                && emitted.windows(2).all(|w| w[0].0 <= w[1].0)
+                && buffer.is_empty()
+        }
+
+        quickcheck(prop as fn(Vec<u16>, u8) -> bool);
+    }
+
+    #[test]
+    fn lateness() {
+        let mut buffer = ReorderBuffer::new(5);
+        buffer.push(3, 'a').unwrap();
+        assert_eq!(buffer.watermark(), None);
+        assert_eq!(buffer.emit().next(), None);
+
+        buffer.push(10, 'b').unwrap();
+        assert_eq!(buffer.watermark(), Some(5));
+        buffer.push(5, 'c').unwrap();
+        assert_eq!(buffer.emit().collect::<Vec<_>>(), vec![(3, 'a'), (5, 'c')]);
+
+        let late = buffer.push(4, 'd').unwrap_err();
+        assert_eq!((late.time, late.event), (4, 'd'));
+        assert_eq!(buffer.len(), 1);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{AdvanceTo, DeadlineQueue};
+
+/// A queue of items to retry after an exponential backoff.
+///
+/// Time is measured in integer ticks, such as milliseconds since some epoch,
+/// and is advanced by [`due`](RetryQueue::due). A retry scheduled for attempt
+/// `n` is due `base * factor^n` ticks after the time last advanced to, capped
+/// at `max`, plus an optional random jitter.
+///
+/// ```
+/// let mut queue = radix_heap::RetryQueue::new(100, 10_000);
+///
+/// assert_eq!(queue.schedule_retry("a", 0), 100);
+/// assert_eq!(queue.schedule_retry("b", 3), 800);
+///
+/// let due: Vec<_> = queue.due(500).collect();
+/// assert_eq!(due, vec![(100, "a")]);
+///
+/// // Retries are scheduled relative to the time last advanced to.
+/// assert_eq!(queue.schedule_retry("a", 1), 700);
+/// ```
+#[derive(Clone)]
+pub struct RetryQueue<T> {
+    queue: DeadlineQueue<u64, T>,
+    base: u64,
+    max: u64,
+    factor: u64,
+    jitter: u64,
+    rng: u64,
+}
+
+impl<T> RetryQueue<T> {
+    /// Create an empty `RetryQueue` where the first attempt is retried after
+    /// `base` ticks, and the delay doubles with each attempt up to `max`
+    /// ticks.
+    pub fn new(base: u64, max: u64) -> RetryQueue<T> {
+        RetryQueue {
+            queue: DeadlineQueue::new_at(0),
+            base,
+            max,
+            factor: 2,
+            jitter: 0,
+            rng: 0,
+        }
+    }
+
+    /// Sets the factor the delay is multiplied by with each attempt.
+    pub fn with_factor(mut self, factor: u64) -> RetryQueue<T> {
+        self.factor = factor;
+        self
+    }
+
+    /// Adds a random jitter of up to `percent` percent of the delay to each
+    /// retry, so that items failing together are not all retried together.
+    ///
+    /// The jitter is drawn from a small pseudorandom generator seeded with
+    /// `seed`, so the same seed gives the same schedule.
+    pub fn with_jitter(mut self, percent: u64, seed: u64) -> RetryQueue<T> {
+        self.jitter = percent;
+        self.rng = seed;
+        self
+    }
+
+    /// The delay before retrying attempt `attempt`, without jitter.
+    pub fn delay(&self, attempt: u32) -> u64 {
+        self.base
+            .saturating_mul(self.factor.saturating_pow(attempt))
+            .min(self.max)
+    }
+
+    /// Schedules `item` to be retried after the delay of attempt `attempt`,
+    /// counted from the time last advanced to, and returns its deadline.
+    pub fn schedule_retry(&mut self, item: T, attempt: u32) -> u64 {
+        let delay = self.delay(attempt);
+        let jitter = match delay.saturating_mul(self.jitter) / 100 {
+            0 => 0,
+            range => self.next_random() % (range + 1),
+        };
+
+        let deadline = self.now().saturating_add(delay).saturating_add(jitter);
+        self.queue
+            .insert(deadline, item)
+            .unwrap_or_else(|_| unreachable!("retries are never scheduled in the past"));
+        deadline
+    }
+
+    /// Advances time to `now` and returns an iterator of all items due for a
+    /// retry, in order of increasing deadline.
+    ///
+    /// Items not consumed by the iterator stay in the queue.
+    pub fn due(&mut self, now: u64) -> AdvanceTo<'_, u64, T> {
+        self.queue.advance_to(now)
+    }
+
+    /// The time last advanced to.
+    #[inline]
+    pub fn now(&self) -> u64 {
+        self.queue.watermark().unwrap_or(0)
+    }
+
+    /// The earliest deadline of any item, or `None` if empty.
+    pub fn next_deadline(&mut self) -> Option<u64> {
+        self.queue.next_deadline()
+    }
+
+    /// Returns the number of items waiting for a retry.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns true if there are no items waiting for a retry.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Drops all items from the queue. The current time is kept.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    /// The next output of a SplitMix64 generator.
+    fn next_random(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetryQueue;
+
+    #[test]
+    fn backoff() {
+        let queue = RetryQueue::<()>::new(10, 1000).with_factor(3);
+        let delays: Vec<_> = (0..6).map(|attempt| queue.delay(attempt)).collect();
+        assert_eq!(delays, vec![10, 30, 90, 270, 810, 1000]);
+        assert_eq!(queue.delay(u32::MAX), 1000);
+    }
+
+    #[test]
+    fn jitter() {
+        let mut queue = RetryQueue::new(1000, 1000).with_jitter(10, 7);
+        let deadlines: Vec<_> = (0..100).map(|i| queue.schedule_retry(i, 0)).collect();
+
+        assert!(deadlines.iter().all(|&d| (1000..=1100).contains(&d)));
+        assert!(deadlines.iter().any(|&d| d != deadlines[0]));
+
+        let mut due: Vec<_> = queue.due(1100).map(|(d, _)| d).collect();
+        let mut expected = deadlines.clone();
+        expected.sort_unstable();
+        due.sort_unstable();
+        assert_eq!(due, expected);
+
+        let mut again = RetryQueue::new(1000, 1000).with_jitter(10, 7);
+        assert!((0..100).map(|i| again.schedule_retry(i, 0)).eq(deadlines));
+    }
+
+    #[test]
+    fn due() {
+        let mut queue = RetryQueue::new(5, 100);
+        queue.schedule_retry('a', 3);
+        queue.schedule_retry('b', 0);
+        assert_eq!(queue.next_deadline(), Some(5));
+
+        assert_eq!(queue.due(10).collect::<Vec<_>>(), vec![(5, 'b')]);
+        assert_eq!(queue.now(), 10);
+        assert_eq!(queue.schedule_retry('b', 1), 20);
+        assert_eq!(queue.due(20).collect::<Vec<_>>(), vec![(20, 'b')]);
+        assert_eq!(queue.due(40).collect::<Vec<_>>(), vec![(40, 'a')]);
+        assert!(queue.is_empty());
+    }
+}
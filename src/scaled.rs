@@ -0,0 +1,248 @@
+use crate::Radix;
+use std::{error::Error, fmt, ops::Add};
+
+/// A float cost converted to a fixed-point integer key by a [`Scale`].
+///
+/// This orders float costs without the `ordered-float` dependency, and since
+/// it implements `Add` and `Default`, it can be used as the cost of the
+/// [`algorithms`](crate::algorithms). Costs are exact multiples of
+/// `1 / factor`, so sums of scaled costs can differ slightly from sums of the
+/// original floats.
+///
+/// ```
+/// use radix_heap::{RadixHeapMap, Scale};
+/// use std::cmp::Reverse;
+///
+/// let scale = Scale::new(1000.0);
+/// let mut heap = RadixHeapMap::new();
+///
+/// heap.push(Reverse(scale.key(1.5).unwrap()), 'a');
+/// heap.push(Reverse(scale.key(0.25).unwrap()), 'b');
+///
+/// let (Reverse(key), value) = heap.pop().unwrap();
+/// assert_eq!((scale.cost(key), value), (0.25, 'b'));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScaledKey(pub i64);
+
+/// Adding saturates at the bounds of `i64`, so sums of costs saturated by
+/// [`OnOverflow::Saturate`] stay at the bound instead of wrapping around to
+/// costs of the opposite sign.
+impl Add for ScaledKey {
+    type Output = ScaledKey;
+
+    #[inline]
+    fn add(self, other: ScaledKey) -> ScaledKey {
+        ScaledKey(self.0.saturating_add(other.0))
+    }
+}
+
+impl Radix for ScaledKey {
+    #[inline]
+    fn radix_similarity(&self, other: &ScaledKey) -> u32 {
+        self.0.radix_similarity(&other.0)
+    }
+
+    const RADIX_BITS: u32 = i64::RADIX_BITS;
+}
+
+/// How a scaled cost is rounded to an integer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round to the nearest integer, with ties away from zero.
+    Nearest,
+
+    /// Round towards negative infinity.
+    Down,
+
+    /// Round towards positive infinity, so that scaled edge costs are never
+    /// smaller than the original ones. An A* heuristic rounded down then
+    /// stays admissible.
+    Up,
+}
+
+/// What to do with costs too large or too small for a [`ScaledKey`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OnOverflow {
+    /// Return a [`ScaleError::Overflow`].
+    Fail,
+
+    /// Clamp to the largest or smallest key.
+    Saturate,
+}
+
+/// A conversion of float costs to [`ScaledKey`]s, by multiplying them by a
+/// factor and rounding.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Scale {
+    factor: f64,
+    rounding: Rounding,
+    overflow: OnOverflow,
+}
+
+impl Scale {
+    /// Create a `Scale` multiplying costs by `factor`, which rounds to the
+    /// nearest integer and fails on overflow.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `factor` is not a positive finite number.
+    pub fn new(factor: f64) -> Scale {
+        assert!(
+            factor > 0.0 && factor.is_finite(),
+            "Scale factor must be positive and finite"
+        );
+
+        Scale {
+            factor,
+            rounding: Rounding::Nearest,
+            overflow: OnOverflow::Fail,
+        }
+    }
+
+    /// Sets how scaled costs are rounded.
+    pub fn with_rounding(mut self, rounding: Rounding) -> Scale {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Sets what to do with costs out of range.
+    pub fn with_overflow(mut self, overflow: OnOverflow) -> Scale {
+        self.overflow = overflow;
+        self
+    }
+
+    /// The factor costs are multiplied by.
+    #[inline]
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Converts `cost` to a key.
+    ///
+    /// Returns an error if `cost` is NaN, or if it is out of range and the
+    /// scale fails on overflow.
+    pub fn key<F: Into<f64>>(&self, cost: F) -> Result<ScaledKey, ScaleError> {
+        let scaled = cost.into() * self.factor;
+
+        let rounded = match self.rounding {
+            Rounding::Nearest => scaled.round(),
+            Rounding::Down => scaled.floor(),
+            Rounding::Up => scaled.ceil(),
+        };
+
+        if rounded.is_nan() {
+            return Err(ScaleError::NaN);
+        }
+
+        // i64::MAX is not exactly representable, and rounds up to 2^63.
+        let limit = i64::MAX as f64;
+        if rounded >= limit || rounded < -limit {
+            return match self.overflow {
+                OnOverflow::Fail => Err(ScaleError::Overflow),
+                OnOverflow::Saturate if rounded > 0.0 => Ok(ScaledKey(i64::MAX)),
+                OnOverflow::Saturate => Ok(ScaledKey(i64::MIN)),
+            };
+        }
+
+        Ok(ScaledKey(rounded as i64))
+    }
+
+    /// Converts `key` back to a cost.
+    #[inline]
+    pub fn cost(&self, key: ScaledKey) -> f64 {
+        key.0 as f64 / self.factor
+    }
+}
+
+/// The error returned when a cost cannot be converted to a [`ScaledKey`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScaleError {
+    /// The cost is NaN.
+    NaN,
+
+    /// The scaled cost is out of range.
+    Overflow,
+}
+
+impl fmt::Display for ScaleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaleError::NaN => write!(f, "cost is NaN"),
+            ScaleError::Overflow => write!(f, "scaled cost is out of range"),
+        }
+    }
+}
+
+impl Error for ScaleError {}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::{OnOverflow, Rounding, Scale, ScaleError, ScaledKey};
+
+    #[test]
+    fn monotone() {
+        fn prop(a: f32, b: f32) -> bool {
+            let scale = Scale::new(64.0).with_overflow(OnOverflow::Saturate);
+
+            match (scale.key(a), scale.key(b)) {
+                (Ok(x), Ok(y)) => a >= b || x <= y,
+                _ => a.is_nan() || b.is_nan(),
+            }
+        }
+
+        quickcheck(prop as fn(f32, f32) -> bool);
+    }
+
+    #[test]
+    fn rounding() {
+        let key = |rounding, cost: f64| Scale::new(10.0).with_rounding(rounding).key(cost);
+
+        assert_eq!(key(Rounding::Nearest, 0.25), Ok(ScaledKey(3)));
+        assert_eq!(key(Rounding::Down, 0.29), Ok(ScaledKey(2)));
+        assert_eq!(key(Rounding::Up, 0.21), Ok(ScaledKey(3)));
+        assert_eq!(key(Rounding::Down, -0.21), Ok(ScaledKey(-3)));
+    }
+
+    #[test]
+    fn overflow() {
+        let scale = Scale::new(1e10);
+        assert_eq!(scale.key(1e10), Err(ScaleError::Overflow));
+        assert_eq!(scale.key(f64::NAN), Err(ScaleError::NaN));
+
+        let scale = scale.with_overflow(OnOverflow::Saturate);
+        assert_eq!(scale.key(f64::INFINITY), Ok(ScaledKey(i64::MAX)));
+        assert_eq!(scale.key(-1e10), Ok(ScaledKey(i64::MIN)));
+        assert_eq!(scale.key(f64::NAN), Err(ScaleError::NaN));
+    }
+
+    #[test]
+    fn add_saturated() {
+        let scale = Scale::new(1e10).with_overflow(OnOverflow::Saturate);
+        let (max, min) = (scale.key(1e10).unwrap(), scale.key(-1e10).unwrap());
+
+        assert_eq!(max + max, ScaledKey(i64::MAX));
+        assert_eq!(min + min, ScaledKey(i64::MIN));
+        assert_eq!(max + ScaledKey(-1), ScaledKey(i64::MAX - 1));
+    }
+
+    #[cfg(feature = "algorithms")]
+    #[test]
+    fn dijkstra() {
+        use crate::algorithms::dijkstra;
+
+        let scale = Scale::new(100.0);
+        let edges = [vec![(1, 0.5), (2, 2.25)], vec![(2, 1.5)], vec![]];
+        let costs = dijkstra(0, |n: usize| {
+            edges[n]
+                .iter()
+                .map(|&(m, cost)| (m, scale.key(cost).unwrap()))
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(scale.cost(costs[&2]), 2.0);
+    }
+}
@@ -0,0 +1,71 @@
+use core::marker::PhantomData;
+
+/// The smallest and largest keys pushed since the heap was created or last
+/// cleared. This is empty unless the `extremes` feature is enabled, so that
+/// pushes do not pay for a clone and comparisons of the key otherwise.
+#[derive(Clone, Debug)]
+pub(crate) struct Seen<K> {
+    #[cfg(feature = "extremes")]
+    range: Option<(K, K)>,
+    key: PhantomData<K>,
+}
+
+impl<K> Default for Seen<K> {
+    fn default() -> Seen<K> {
+        Seen {
+            #[cfg(feature = "extremes")]
+            range: None,
+            key: PhantomData,
+        }
+    }
+}
+
+impl<K: Ord + Clone> Seen<K> {
+    /// Records a pushed key.
+    #[inline]
+    pub(crate) fn record(&mut self, _key: &K) {
+        #[cfg(feature = "extremes")]
+        match &mut self.range {
+            Some((min, _)) if _key < min => *min = _key.clone(),
+            Some((_, max)) if _key > max => *max = _key.clone(),
+            Some(_) => {}
+            None => self.range = Some((_key.clone(), _key.clone())),
+        }
+    }
+
+    /// Records the keys seen by another heap merged into this one.
+    #[inline]
+    pub(crate) fn merge(&mut self, _other: Seen<K>) {
+        #[cfg(feature = "extremes")]
+        {
+            self.range = match (self.range.take(), _other.range) {
+                (Some((min, max)), Some((other_min, other_max))) => {
+                    Some((min.min(other_min), max.max(other_max)))
+                }
+                (range, None) | (None, range) => range,
+            };
+        }
+    }
+
+    /// Applies a monotone function to the keys seen.
+    #[inline]
+    pub(crate) fn map<F: FnMut(K) -> K>(self, _f: &mut F) -> Seen<K> {
+        Seen {
+            #[cfg(feature = "extremes")]
+            range: self.range.map(|(min, max)| (_f(min), _f(max))),
+            key: PhantomData,
+        }
+    }
+
+    #[cfg(feature = "extremes")]
+    #[inline]
+    pub(crate) fn min(&self) -> Option<K> {
+        self.range.as_ref().map(|(min, _)| min.clone())
+    }
+
+    #[cfg(feature = "extremes")]
+    #[inline]
+    pub(crate) fn max(&self) -> Option<K> {
+        self.range.as_ref().map(|(_, max)| max.clone())
+    }
+}
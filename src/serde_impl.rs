@@ -0,0 +1,187 @@
+use crate::{Radix, RadixHeapMap};
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+use serde::{
+    de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor},
+    ser::{Serialize, SerializeStruct, Serializer},
+};
+
+const FIELDS: &[&str] = &["top", "entries"];
+
+/// Serializes the top key and the entries in bucket order.
+///
+/// Entries of the same bucket keep their order when deserialized, so ties
+/// are still popped last inserted first. Settings such as
+/// [`set_auto_rebase`](RadixHeapMap::set_auto_rebase) are not serialized.
+impl<K, V> Serialize for RadixHeapMap<K, V>
+where
+    K: Radix + Ord + Clone + Serialize,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("RadixHeapMap", FIELDS.len())?;
+        state.serialize_field("top", &self.top)?;
+        state.serialize_field("entries", &Entries(self))?;
+        state.end()
+    }
+}
+
+struct Entries<'a, K, V>(&'a RadixHeapMap<K, V>);
+
+impl<'a, K: Radix + Ord + Clone + Serialize, V: Serialize> Serialize for Entries<'a, K, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+/// Deserializes a heap serialized by the `Serialize` impl, putting every
+/// entry back into its bucket.
+///
+/// Fails if any key is larger than the top key.
+impl<'de, K, V> Deserialize<'de> for RadixHeapMap<K, V>
+where
+    K: Radix + Ord + Clone + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("RadixHeapMap", FIELDS, HeapVisitor(PhantomData))
+    }
+}
+
+enum Field {
+    Top,
+    Entries,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Field, D::Error> {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("`top` or `entries`")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+                match value {
+                    "top" => Ok(Field::Top),
+                    "entries" => Ok(Field::Entries),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct HeapVisitor<K, V>(PhantomData<(K, V)>);
+
+impl<'de, K, V> Visitor<'de> for HeapVisitor<K, V>
+where
+    K: Radix + Ord + Clone + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    type Value = RadixHeapMap<K, V>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("struct RadixHeapMap")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let top = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let entries = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        build(top, entries)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut top = None;
+        let mut entries = None;
+
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Top if top.is_some() => return Err(de::Error::duplicate_field("top")),
+                Field::Top => top = Some(map.next_value()?),
+                Field::Entries if entries.is_some() => {
+                    return Err(de::Error::duplicate_field("entries"))
+                }
+                Field::Entries => entries = Some(map.next_value()?),
+            }
+        }
+
+        let top = top.ok_or_else(|| de::Error::missing_field("top"))?;
+        let entries = entries.ok_or_else(|| de::Error::missing_field("entries"))?;
+        build(top, entries)
+    }
+}
+
+fn build<K, V, E>(top: Option<K>, entries: Vec<(K, V)>) -> Result<RadixHeapMap<K, V>, E>
+where
+    K: Radix + Ord + Clone,
+    E: de::Error,
+{
+    let reserve = if top.is_none() { entries.len() } else { 0 };
+
+    let mut heap = match top {
+        Some(top) if entries.iter().any(|(key, _)| *key > top) => {
+            return Err(E::custom("key is larger than the top key"));
+        }
+        Some(top) => RadixHeapMap::new_at(top),
+        None => RadixHeapMap::new(),
+    };
+
+    heap.initial.reserve_exact(reserve);
+
+    for (key, value) in entries {
+        heap.push_unchecked(key, value);
+    }
+
+    Ok(heap)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use crate::RadixHeapMap;
+
+    #[test]
+    fn round_trip() {
+        fn prop(xs: Vec<(i16, u8)>, pops: u8) -> bool {
+            let mut heap: RadixHeapMap<_, _> = xs.into_iter().collect();
+            for _ in 0..pops % 4 {
+                heap.pop();
+            }
+
+            let json = serde_json::to_string(&heap).unwrap();
+            let copy: RadixHeapMap<i16, u8> = serde_json::from_str(&json).unwrap();
+
+            copy.top() == heap.top()
+                && copy.len() == heap.len()
+                && copy.initial == heap.initial
+                && copy.buckets == heap.buckets
+        }
+
+        quickcheck(prop as fn(Vec<(i16, u8)>, u8) -> bool);
+    }
+
+    #[test]
+    fn format() {
+        let mut heap = RadixHeapMap::new_at(8u8);
+        heap.push(5, 'a');
+
+        let json = serde_json::to_string(&heap).unwrap();
+        assert_eq!(json, r#"{"top":8,"entries":[[5,"a"]]}"#);
+
+        let invalid = r#"{"top":4,"entries":[[5,"a"]]}"#;
+        assert!(serde_json::from_str::<RadixHeapMap<u8, char>>(invalid).is_err());
+    }
+}
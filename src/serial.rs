@@ -0,0 +1,130 @@
+use core::cmp::Ordering;
+
+/// A sequence number that wraps around, compared using serial number
+/// arithmetic as in [RFC 1982](https://tools.ietf.org/html/rfc1982).
+///
+/// A serial number is greater than another if it is ahead of it by less than
+/// half the range of the integer type, counting modulo `2^N`. So for
+/// `Serial<u8>`, 2 is greater than 250 since it is only 8 steps ahead of it.
+/// Numbers exactly half the range apart, which the RFC leaves undefined, are
+/// compared as plain integers.
+///
+/// This order is only transitive for numbers within half the range of each
+/// other, so every key in a heap must be within that window of the top key.
+/// With this restriction the heap keeps working as keys wrap around, since
+/// keys that have wrapped only ever share the highest radix bucket with
+/// other keys on the same side of the wrap.
+///
+/// ```
+/// use radix_heap::{RadixHeapMap, Serial};
+/// use std::cmp::Reverse;
+///
+/// let mut heap = RadixHeapMap::new();
+/// heap.push(Reverse(Serial(254u8)), 'a');
+/// heap.push(Reverse(Serial(1)), 'c');
+/// heap.push(Reverse(Serial(255)), 'b');
+///
+/// assert_eq!(heap.pop(), Some((Reverse(Serial(254)), 'a')));
+/// heap.push(Reverse(Serial(0)), 'd');
+/// assert_eq!(heap.pop(), Some((Reverse(Serial(255)), 'b')));
+/// assert_eq!(heap.pop(), Some((Reverse(Serial(0)), 'd')));
+/// assert_eq!(heap.pop(), Some((Reverse(Serial(1)), 'c')));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Serial<T>(pub T);
+
+macro_rules! serial_impl {
+    ($t:ty) => {
+        impl Ord for Serial<$t> {
+            #[inline]
+            fn cmp(&self, other: &Serial<$t>) -> Ordering {
+                const HALF: $t = 1 << (<$t>::BITS - 1);
+
+                match other.0.wrapping_sub(self.0) {
+                    0 => Ordering::Equal,
+                    HALF => self.0.cmp(&other.0),
+                    ahead if ahead < HALF => Ordering::Less,
+                    _ => Ordering::Greater,
+                }
+            }
+        }
+
+        impl PartialOrd for Serial<$t> {
+            #[inline]
+            fn partial_cmp(&self, other: &Serial<$t>) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+    };
+}
+
+serial_impl!(u8);
+serial_impl!(u16);
+serial_impl!(u32);
+serial_impl!(u64);
+serial_impl!(u128);
+serial_impl!(usize);
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::{quickcheck, TestResult};
+    use super::Serial;
+    use crate::RadixHeapMap;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn order() {
+        assert!(Serial(0u8) < Serial(1));
+        assert!(Serial(255u8) < Serial(0));
+        assert!(Serial(200u8) < Serial(71));
+        assert!(Serial(200u8) > Serial(72));
+        assert!(Serial(0u8) < Serial(128));
+        assert!(Serial(128u8) > Serial(0));
+        assert!(Serial(u32::MAX) < Serial(5));
+    }
+
+    #[test]
+    fn reassembly() {
+        // Pushes sequence numbers ahead of the next expected one, in any
+        // order, and checks they come out in sequence across the wrap.
+        fn prop(start: u8, batches: Vec<Vec<u8>>) -> TestResult {
+            let mut heap = RadixHeapMap::new_at(Reverse(Serial(start)));
+            let mut next = start;
+
+            for batch in batches {
+                for ahead in batch {
+                    heap.push(Reverse(Serial(next.wrapping_add(ahead % 128))), ahead);
+                }
+
+                if let Some((Reverse(Serial(seq)), _)) = heap.pop() {
+                    let mut remaining: Vec<_> = heap.keys().map(|&Reverse(Serial(s))| s).collect();
+                    remaining.push(seq);
+
+                    if remaining
+                        .iter()
+                        .any(|&s| s.wrapping_sub(next) < seq.wrapping_sub(next))
+                    {
+                        return TestResult::failed();
+                    }
+
+                    next = seq;
+                }
+            }
+
+            let mut last = next;
+            while let Some((Reverse(Serial(seq)), _)) = heap.pop() {
+                if seq.wrapping_sub(next) < last.wrapping_sub(next) {
+                    return TestResult::failed();
+                }
+
+                last = seq;
+            }
+
+            TestResult::passed()
+        }
+
+        quickcheck(prop as fn(u8, Vec<Vec<u8>>) -> TestResult);
+    }
+}
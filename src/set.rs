@@ -0,0 +1,199 @@
+use crate::{IntoIter, Keys, Radix, RadixHeapMap};
+use core::{fmt, iter::FromIterator};
+
+/// A monotone priority queue of keys only, implemented using a radix heap.
+///
+/// This is a [`RadixHeapMap`] without values, for keys that carry all the
+/// information needed, such as timestamps or packed distance and node ids.
+/// It is a max-heap, and every pushed key must be no larger than the
+/// [`top`](RadixHeapSet::top) key.
+///
+/// Despite the name, equal keys can be pushed more than once and are popped
+/// once for each push.
+///
+/// ```
+/// let mut heap = radix_heap::RadixHeapSet::new();
+/// heap.extend(vec![3u32, 8, 5]);
+///
+/// assert_eq!(heap.pop(), Some(8));
+/// heap.push(6);
+/// assert_eq!(heap.pop(), Some(6));
+/// assert_eq!(heap.len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct RadixHeapSet<K> {
+    heap: RadixHeapMap<K, ()>,
+}
+
+impl<K: Radix + Ord + Copy> RadixHeapSet<K> {
+    /// Create an empty `RadixHeapSet`.
+    pub fn new() -> RadixHeapSet<K> {
+        RadixHeapSet {
+            heap: RadixHeapMap::new(),
+        }
+    }
+
+    /// Create an empty `RadixHeapSet` with the top key set to a specific
+    /// value.
+    pub fn new_at(top: K) -> RadixHeapSet<K> {
+        RadixHeapSet {
+            heap: RadixHeapMap::new_at(top),
+        }
+    }
+
+    /// Drops all keys from the heap and sets the top key to `None`.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// Drops all keys from the heap and sets the top key to a specific value.
+    pub fn clear_to(&mut self, top: K) {
+        self.heap.clear_to(top);
+    }
+
+    /// Pushes a key onto the heap.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    #[inline]
+    pub fn push(&mut self, key: K) {
+        self.heap.push(key, ());
+    }
+
+    /// Removes the greatest key from the heap and returns it, or `None` if
+    /// empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<K> {
+        self.heap.pop().map(|(key, ())| key)
+    }
+
+    /// Returns the greatest key without removing it, or `None` if empty.
+    ///
+    /// See [`RadixHeapMap::peek`].
+    #[inline]
+    pub fn peek(&mut self) -> Option<K> {
+        self.heap.peek_key()
+    }
+
+    /// The current top key. All keys pushed onto the heap must be smaller than
+    /// this value.
+    #[inline]
+    pub fn top(&self) -> Option<K> {
+        self.heap.top()
+    }
+
+    /// Returns the number of keys in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if there are no keys in the heap.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns an iterator of all keys in the heap in arbitrary order.
+    pub fn iter(&self) -> Keys<'_, K, ()> {
+        self.heap.keys()
+    }
+
+    /// The underlying heap, with `()` values.
+    #[inline]
+    pub fn as_map(&self) -> &RadixHeapMap<K, ()> {
+        &self.heap
+    }
+
+    /// Returns the underlying heap, with `()` values.
+    pub fn into_map(self) -> RadixHeapMap<K, ()> {
+        self.heap
+    }
+}
+
+impl<K: Radix + Ord + Copy> Default for RadixHeapSet<K> {
+    fn default() -> RadixHeapSet<K> {
+        RadixHeapSet::new()
+    }
+}
+
+impl<K: Radix + Ord + Copy> From<RadixHeapMap<K, ()>> for RadixHeapSet<K> {
+    fn from(heap: RadixHeapMap<K, ()>) -> RadixHeapSet<K> {
+        RadixHeapSet { heap }
+    }
+}
+
+impl<K: Radix + Ord + Copy> FromIterator<K> for RadixHeapSet<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> RadixHeapSet<K> {
+        let mut heap = RadixHeapSet::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<K: Radix + Ord + Copy> Extend<K> for RadixHeapSet<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        self.heap.extend(iter.into_iter().map(|key| (key, ())));
+    }
+}
+
+impl<'a, K: Radix + Ord + Copy + 'a> Extend<&'a K> for RadixHeapSet<K> {
+    fn extend<I: IntoIterator<Item = &'a K>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<K: Radix + Ord + Copy> IntoIterator for RadixHeapSet<K> {
+    type Item = K;
+    type IntoIter = core::iter::Map<IntoIter<K, ()>, fn((K, ())) -> K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.heap.into_iter().map(|(key, ())| key)
+    }
+}
+
+impl<'a, K: Radix + Ord + Copy> IntoIterator for &'a RadixHeapSet<K> {
+    type Item = &'a K;
+    type IntoIter = Keys<'a, K, ()>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Radix + Ord + Copy + fmt::Debug> fmt::Debug for RadixHeapSet<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::RadixHeapSet;
+
+    #[test]
+    fn sort() {
+        fn prop(mut xs: Vec<i32>) -> bool {
+            let mut heap: RadixHeapSet<_> = xs.iter().copied().collect();
+            xs.sort_unstable();
+
+            heap.peek() == xs.last().copied()
+                && heap.iter().count() == xs.len()
+                && std::iter::from_fn(|| heap.pop()).eq(xs.into_iter().rev())
+        }
+
+        quickcheck(prop as fn(Vec<i32>) -> bool);
+    }
+
+    #[test]
+    fn into_iter() {
+        let heap: RadixHeapSet<u8> = vec![4, 1, 4].into_iter().collect();
+        let mut keys: Vec<_> = heap.into_iter().collect();
+        keys.sort_unstable();
+        assert_eq!(keys, vec![1, 4, 4]);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::Radix;
+use core::cmp::Ordering;
+
+/// A key paired with an insertion stamp, so that equal keys are popped in a
+/// deterministic order.
+///
+/// Stamped keys are ordered by key and then by stamp, with smaller stamps
+/// ordered as greater. So among equal keys, the one stamped first is popped
+/// first, and the order of pops only depends on the keys and stamps pushed.
+/// Stamps are usually handed out in increasing order by a [`Stamper`].
+///
+/// ```
+/// use radix_heap::{RadixHeapMap, Stamper};
+///
+/// let mut stamper = Stamper::new();
+/// let mut heap = RadixHeapMap::new();
+///
+/// for (key, value) in vec![(5u32, 'a'), (7, 'b'), (5, 'c'), (5, 'd')] {
+///     heap.push(stamper.stamp(key), value);
+/// }
+///
+/// let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).map(|(_, v)| v).collect();
+/// assert_eq!(popped, vec!['b', 'a', 'c', 'd']);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stamped<K> {
+    /// The key.
+    pub key: K,
+
+    /// The stamp, which orders equal keys.
+    pub stamp: u64,
+}
+
+impl<K> Stamped<K> {
+    /// Create a stamped key with an explicit stamp.
+    #[inline]
+    pub fn new(key: K, stamp: u64) -> Stamped<K> {
+        Stamped { key, stamp }
+    }
+}
+
+impl<K: Ord> Ord for Stamped<K> {
+    #[inline]
+    fn cmp(&self, other: &Stamped<K>) -> Ordering {
+        self.key
+            .cmp(&other.key)
+            .then_with(|| other.stamp.cmp(&self.stamp))
+    }
+}
+
+impl<K: Ord> PartialOrd for Stamped<K> {
+    #[inline]
+    fn partial_cmp(&self, other: &Stamped<K>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Radix> Radix for Stamped<K> {
+    #[inline]
+    fn radix_similarity(&self, other: &Stamped<K>) -> u32 {
+        let similarity = self.key.radix_similarity(&other.key);
+
+        if similarity < K::RADIX_BITS {
+            similarity
+        } else {
+            similarity + self.stamp.radix_similarity(&other.stamp)
+        }
+    }
+
+    const RADIX_BITS: u32 = K::RADIX_BITS + u64::RADIX_BITS;
+}
+
+/// A counter handing out increasing stamps for [`Stamped`] keys.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stamper {
+    next: u64,
+}
+
+impl Stamper {
+    /// Create a `Stamper` starting at stamp 0.
+    pub fn new() -> Stamper {
+        Stamper::default()
+    }
+
+    /// Pairs `key` with the next stamp.
+    #[inline]
+    pub fn stamp<K>(&mut self, key: K) -> Stamped<K> {
+        let stamp = self.next;
+        self.next += 1;
+        Stamped { key, stamp }
+    }
+
+    /// The stamp the next key will get.
+    #[inline]
+    pub fn next_stamp(&self) -> u64 {
+        self.next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::Stamper;
+    use crate::RadixHeapMap;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn stable() {
+        fn prop(keys: Vec<u8>) -> bool {
+            let mut stamper = Stamper::new();
+            let mut heap = RadixHeapMap::new();
+
+            for (i, &key) in keys.iter().enumerate() {
+                heap.push(stamper.stamp(Reverse(key)), i);
+            }
+
+            let mut expected: Vec<_> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+            expected.sort();
+
+            std::iter::from_fn(|| heap.pop())
+                .map(|(stamped, i)| (stamped.key.0, i))
+                .eq(expected)
+        }
+
+        quickcheck(prop as fn(Vec<u8>) -> bool);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn consistent() {
+        use super::Stamped;
+        use crate::testing::check_radix_consistency;
+
+        fn prop(keys: Vec<(i8, u64)>) -> bool {
+            let keys: Vec<_> = keys.into_iter().map(|(k, s)| Stamped::new(k, s)).collect();
+            check_radix_consistency(&keys).is_ok()
+        }
+
+        quickcheck(prop as fn(Vec<(i8, u64)>) -> bool);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::Bucket;
+
+/// Counters of the operations performed on a
+/// [`RadixHeapMap`](crate::RadixHeapMap) since it was created or the counters
+/// were reset.
+///
+/// The counters are only kept when the `stats` feature is enabled, and cost a
+/// few additions per operation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    #[cfg(feature = "stats")]
+    pushes: u64,
+    #[cfg(feature = "stats")]
+    pops: u64,
+    #[cfg(feature = "stats")]
+    constrains: u64,
+    #[cfg(feature = "stats")]
+    redistributed: u64,
+    #[cfg(feature = "stats")]
+    max_bucket_len: usize,
+}
+
+#[cfg(feature = "stats")]
+impl Stats {
+    /// The number of items pushed.
+    #[inline]
+    pub fn pushes(&self) -> u64 {
+        self.pushes
+    }
+
+    /// The number of items popped.
+    #[inline]
+    pub fn pops(&self) -> u64 {
+        self.pops
+    }
+
+    /// The number of times the top key was moved to the largest key, either by
+    /// [`constrain`](crate::RadixHeapMap::constrain) or by a pop finding the
+    /// closest bucket empty.
+    #[inline]
+    pub fn constrains(&self) -> u64 {
+        self.constrains
+    }
+
+    /// The total number of items moved between buckets when the top key
+    /// changed.
+    #[inline]
+    pub fn redistributed(&self) -> u64 {
+        self.redistributed
+    }
+
+    /// The largest number of items seen in a single bucket.
+    #[inline]
+    pub fn max_bucket_len(&self) -> usize {
+        self.max_bucket_len
+    }
+}
+
+impl Stats {
+    #[inline]
+    pub(crate) fn record_push(&mut self, _bucket_len: usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.pushes += 1;
+            self.max_bucket_len = self.max_bucket_len.max(_bucket_len);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn record_pop(&mut self) {
+        #[cfg(feature = "stats")]
+        {
+            self.pops += 1;
+        }
+    }
+
+    #[inline]
+    pub(crate) fn record_constrain(&mut self) {
+        #[cfg(feature = "stats")]
+        {
+            self.constrains += 1;
+        }
+    }
+
+    /// Records `_moved` items being redistributed into `_buckets`.
+    #[inline]
+    pub(crate) fn record_redistribute<K, V>(&mut self, _moved: usize, _buckets: &[Bucket<K, V>]) {
+        #[cfg(feature = "stats")]
+        {
+            self.redistributed += _moved as u64;
+
+            let longest = _buckets
+                .iter()
+                .map(|bucket| bucket.len())
+                .max()
+                .unwrap_or(0);
+            self.max_bucket_len = self.max_bucket_len.max(longest);
+        }
+    }
+}
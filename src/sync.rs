@@ -0,0 +1,227 @@
+use crate::{Radix, RadixHeapMap};
+use std::{
+    error::Error,
+    fmt,
+    sync::{Condvar, Mutex, MutexGuard},
+};
+
+/// A bounded, thread-safe [`RadixHeapMap`] for producers and consumers.
+///
+/// [`push`](SyncRadixQueue::push) blocks while the queue is full and
+/// [`pop`](SyncRadixQueue::pop) blocks while it is empty. The non-blocking
+/// [`try_push`](SyncRadixQueue::try_push) and
+/// [`try_pop`](SyncRadixQueue::try_pop) return immediately instead.
+///
+/// ```
+/// use radix_heap::SyncRadixQueue;
+/// use std::{sync::Arc, thread};
+///
+/// let queue = Arc::new(SyncRadixQueue::new(2));
+///
+/// let producer = {
+///     let queue = queue.clone();
+///     thread::spawn(move || {
+///         for i in (0..10u32).rev() {
+///             queue.push(i, ());
+///         }
+///     })
+/// };
+///
+/// let popped: Vec<_> = (0..10).map(|_| queue.pop().0).collect();
+/// producer.join().unwrap();
+///
+/// assert_eq!(popped, (0..10).rev().collect::<Vec<_>>());
+/// ```
+pub struct SyncRadixQueue<K, V> {
+    heap: Mutex<RadixHeapMap<K, V>>,
+    capacity: usize,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<K: Radix + Ord + Copy, V> SyncRadixQueue<K, V> {
+    /// Create an empty queue holding at most `capacity` items.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> SyncRadixQueue<K, V> {
+        assert!(capacity > 0, "Capacity must be positive");
+
+        SyncRadixQueue {
+            heap: Mutex::new(RadixHeapMap::new()),
+            capacity,
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    /// Pushes a key value pair, blocking while the queue is full.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    pub fn push(&self, key: K, value: V) {
+        let mut heap = self.lock();
+
+        while heap.len() >= self.capacity {
+            heap = self.not_full.wait(heap).unwrap();
+        }
+
+        self.push_locked(heap, key, value);
+    }
+
+    /// Pushes a key value pair, or returns it in an error if the queue is
+    /// full.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the key is larger than the current top key.
+    pub fn try_push(&self, key: K, value: V) -> Result<(), WouldBlock<(K, V)>> {
+        let heap = self.lock();
+
+        if heap.len() >= self.capacity {
+            return Err(WouldBlock((key, value)));
+        }
+
+        self.push_locked(heap, key, value);
+        Ok(())
+    }
+
+    /// Removes the greatest element and returns it, blocking while the queue
+    /// is empty.
+    pub fn pop(&self) -> (K, V) {
+        let mut heap = self.lock();
+
+        loop {
+            if let Some(item) = heap.pop() {
+                drop(heap);
+                self.not_full.notify_one();
+                return item;
+            }
+
+            heap = self.not_empty.wait(heap).unwrap();
+        }
+    }
+
+    /// Removes the greatest element and returns it, or `None` if the queue is
+    /// empty.
+    pub fn try_pop(&self) -> Option<(K, V)> {
+        let item = self.lock().pop();
+
+        if item.is_some() {
+            self.not_full.notify_one();
+        }
+
+        item
+    }
+
+    /// The maximum number of items in the queue.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of items in the queue.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if there are no items in the queue.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns the current top key, or `None` if none is set yet.
+    pub fn top(&self) -> Option<K> {
+        self.lock().top()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RadixHeapMap<K, V>> {
+        self.heap.lock().unwrap()
+    }
+
+    fn push_locked(&self, mut heap: MutexGuard<'_, RadixHeapMap<K, V>>, key: K, value: V) {
+        // Check before pushing so that the mutex is not poisoned.
+        if matches!(heap.top(), Some(top) if key > top) {
+            drop(heap);
+            panic!("Key must not be larger than the top key");
+        }
+
+        heap.push(key, value);
+        drop(heap);
+        self.not_empty.notify_one();
+    }
+}
+
+/// The error returned by [`SyncRadixQueue::try_push`] when the queue is full.
+/// It contains the item that could not be pushed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WouldBlock<T>(pub T);
+
+impl<T> fmt::Display for WouldBlock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("queue is full")
+    }
+}
+
+impl<T: fmt::Debug> Error for WouldBlock<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{SyncRadixQueue, WouldBlock};
+    use std::{sync::Arc, thread};
+
+    #[test]
+    fn try_push_pop() {
+        let queue = SyncRadixQueue::new(2);
+
+        assert_eq!(queue.try_push(3u8, 'a'), Ok(()));
+        assert_eq!(queue.try_push(5, 'b'), Ok(()));
+        assert_eq!(queue.try_push(4, 'c'), Err(WouldBlock((4, 'c'))));
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.try_pop(), Some((5, 'b')));
+        assert_eq!(queue.try_push(4, 'c'), Ok(()));
+        assert_eq!(queue.try_pop(), Some((4, 'c')));
+        assert_eq!(queue.try_pop(), Some((3, 'a')));
+        assert_eq!(queue.try_pop(), None);
+        assert_eq!(queue.top(), Some(3));
+    }
+
+    #[test]
+    fn producers_consumers() {
+        let queue = Arc::new(SyncRadixQueue::new(4));
+
+        let producers: Vec<_> = (0..4u32)
+            .map(|p| {
+                let queue = queue.clone();
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        queue.push(0, p * 100 + i);
+                    }
+                })
+            })
+            .collect();
+
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let queue = queue.clone();
+                thread::spawn(move || (0..200).map(|_| queue.pop().1).collect::<Vec<_>>())
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        let mut values: Vec<_> = consumers
+            .into_iter()
+            .flat_map(|consumer| consumer.join().unwrap())
+            .collect();
+        values.sort_unstable();
+
+        assert_eq!(values, (0..400).collect::<Vec<_>>());
+        assert!(queue.is_empty());
+    }
+}
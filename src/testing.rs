@@ -0,0 +1,344 @@
+//! Differential testing of [`RadixHeapMap`] against a reference model.
+//!
+//! A sequence of [`Op`]s is applied to both a `RadixHeapMap` and a model
+//! built on [`BinaryHeap`], and [`apply_ops`] checks that they agree after
+//! every operation. This makes it easy for property tests and fuzzers to find
+//! invariant bugs:
+//!
+//! ```
+//! use radix_heap::testing::{apply_ops, Op};
+//!
+//! let ops = vec![Op::Push(3u32, 'a'), Op::Push(5, 'b'), Op::Pop, Op::Push(9, 'c')];
+//! assert!(apply_ops(ops).is_ok());
+//! ```
+//!
+//! Custom [`Radix`] implementations can be checked for the properties the
+//! heap relies on with [`check_radix_consistency`].
+
+use crate::{Radix, RadixHeapMap};
+use std::{cmp::Ordering, collections::BinaryHeap, error::Error, fmt};
+
+/// An operation on a heap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Op<K, V> {
+    /// Push a key value pair. Pushes of keys larger than the top key are
+    /// skipped, so any sequence of operations is valid.
+    Push(K, V),
+
+    /// Pop the greatest element.
+    Pop,
+
+    /// Drop all elements and reset the top key.
+    Clear,
+
+    /// Drop all elements and set the top key.
+    ClearTo(K),
+
+    /// Set the top key to the greatest key.
+    Constrain,
+}
+
+/// A difference between the heap and the model, found by [`apply_ops`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The index of the operation after which the heap and model differed.
+    pub step: usize,
+
+    /// A description of the difference.
+    pub description: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "divergence after operation {}: {}",
+            self.step, self.description
+        )
+    }
+}
+
+impl Error for Divergence {}
+
+/// An element of the model. Elements are ordered by key and then by
+/// insertion order, so that the last inserted of equal keys is popped first.
+struct Entry<K, V> {
+    key: K,
+    seq: u64,
+    value: V,
+}
+
+impl<K: Ord, V> PartialEq for Entry<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Ord, V> Eq for Entry<K, V> {}
+
+impl<K: Ord, V> PartialOrd for Entry<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> Ord for Entry<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.key, self.seq).cmp(&(&other.key, other.seq))
+    }
+}
+
+/// Applies `ops` to a new `RadixHeapMap` and to a reference model, and
+/// returns the first point at which their elements, lengths or top keys
+/// differ.
+pub fn apply_ops<K, V, I>(ops: I) -> Result<(), Divergence>
+where
+    K: Radix + Ord + Copy + fmt::Debug,
+    V: Clone + PartialEq + fmt::Debug,
+    I: IntoIterator<Item = Op<K, V>>,
+{
+    let mut heap = RadixHeapMap::new();
+    let mut model = BinaryHeap::new();
+    let mut top = None;
+    let mut seq = 0;
+
+    for (step, op) in ops.into_iter().enumerate() {
+        let diverge = |description: String| Err(Divergence { step, description });
+
+        match op {
+            Op::Push(key, value) => {
+                if matches!(top, Some(top) if key > top) {
+                    continue;
+                }
+
+                heap.push(key, value.clone());
+                model.push(Entry { key, seq, value });
+                seq += 1;
+            }
+            Op::Pop => {
+                let expected = model.pop().map(|entry| (entry.key, entry.value));
+                let actual = heap.pop();
+
+                if actual != expected {
+                    return diverge(format!("popped {:?}, expected {:?}", actual, expected));
+                }
+
+                top = expected.map(|(key, _)| key).or(top);
+            }
+            Op::Clear => {
+                heap.clear();
+                model.clear();
+                top = None;
+            }
+            Op::ClearTo(key) => {
+                heap.clear_to(key);
+                model.clear();
+                top = Some(key);
+            }
+            Op::Constrain => {
+                heap.constrain();
+                top = model.peek().map(|entry| entry.key).or(top);
+            }
+        }
+
+        if heap.len() != model.len() {
+            return diverge(format!("length {}, expected {}", heap.len(), model.len()));
+        }
+
+        if heap.top() != top {
+            return diverge(format!("top {:?}, expected {:?}", heap.top(), top));
+        }
+
+        if heap.iter().count() != model.len() {
+            return diverge(format!(
+                "iterated {} elements, expected {}",
+                heap.iter().count(),
+                model.len()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// A violation of a property required of [`Radix`] implementations, found
+/// by [`check_radix_consistency`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadixInconsistency {
+    /// A description of the violation and the keys involved.
+    pub description: String,
+}
+
+impl fmt::Display for RadixInconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "inconsistent radix implementation: {}", self.description)
+    }
+}
+
+impl Error for RadixInconsistency {}
+
+/// Checks that the [`Radix`] implementation of `K` has the properties the
+/// heap relies on for every pair and triple of the sample `keys`:
+///
+/// * `radix_similarity` is symmetric and at most `RADIX_BITS`.
+/// * Keys have a similarity of `RADIX_BITS` exactly when they are equal.
+/// * For keys `a <= b <= c`, `a` is no more similar to `c` than `b` is, so
+///   that smaller keys never land in a closer bucket than larger ones.
+///
+/// An implementation that violates these makes the heap pop elements in the
+/// wrong order. This takes time quadratic in the number of keys.
+///
+/// ```
+/// use radix_heap::{testing::check_radix_consistency, Radix};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Cost(u16, u16);
+///
+/// impl Radix for Cost {
+///     fn radix_similarity(&self, other: &Cost) -> u32 {
+///         // Wrong: the second field should only count if the first is equal.
+///         self.0.radix_similarity(&other.0) + self.1.radix_similarity(&other.1)
+///     }
+///
+///     const RADIX_BITS: u32 = 32;
+/// }
+///
+/// assert!(check_radix_consistency(&[Cost(1, 0), Cost(1, 9), Cost(2, 0)]).is_err());
+/// ```
+pub fn check_radix_consistency<K>(keys: &[K]) -> Result<(), RadixInconsistency>
+where
+    K: Radix + Ord + fmt::Debug,
+{
+    let mut keys: Vec<&K> = keys.iter().collect();
+    keys.sort();
+
+    let inconsistent = |description: String| Err(RadixInconsistency { description });
+
+    for (i, c) in keys.iter().enumerate() {
+        let mut closest = 0;
+
+        for b in &keys[..=i] {
+            let similarity = b.radix_similarity(c);
+
+            if similarity != c.radix_similarity(b) {
+                return inconsistent(format!(
+                    "similarity of {:?} and {:?} is not symmetric",
+                    b, c
+                ));
+            }
+
+            if similarity > K::RADIX_BITS {
+                return inconsistent(format!(
+                    "similarity of {:?} and {:?} is {}, more than RADIX_BITS {}",
+                    b,
+                    c,
+                    similarity,
+                    K::RADIX_BITS
+                ));
+            }
+
+            if (similarity == K::RADIX_BITS) != (b == c) {
+                return inconsistent(format!(
+                    "similarity of {:?} and {:?} is {} with RADIX_BITS {}",
+                    b,
+                    c,
+                    similarity,
+                    K::RADIX_BITS
+                ));
+            }
+
+            if similarity < closest {
+                return inconsistent(format!(
+                    "{:?} is less similar to {:?} than a smaller key is",
+                    b, c
+                ));
+            }
+
+            closest = similarity;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate quickcheck;
+
+    use self::quickcheck::quickcheck;
+    use super::{apply_ops, check_radix_consistency, Op};
+    use crate::{BoundedBytes, Radix};
+    use std::cmp::Reverse;
+
+    #[test]
+    fn radix_heap_matches_model() {
+        fn prop(ops: Vec<(u8, i16)>) -> bool {
+            let ops = ops
+                .into_iter()
+                .enumerate()
+                .map(|(i, (op, key))| match op % 16 {
+                    0 => Op::Clear,
+                    1 => Op::ClearTo(key),
+                    2 | 3 => Op::Constrain,
+                    4..=9 => Op::Pop,
+                    _ => Op::Push(key, i),
+                });
+
+            apply_ops(ops).is_ok()
+        }
+
+        quickcheck(prop as fn(Vec<(u8, i16)>) -> bool);
+    }
+
+    #[test]
+    fn equal_keys() {
+        let ops = vec![
+            Op::Push(1u8, 'a'),
+            Op::Push(1, 'b'),
+            Op::Constrain,
+            Op::Push(1, 'c'),
+            Op::Pop,
+            Op::Pop,
+            Op::Pop,
+        ];
+        assert_eq!(apply_ops(ops), Ok(()));
+    }
+
+    #[test]
+    fn builtin_radix_consistent() {
+        fn prop(ints: Vec<i32>, bytes: Vec<Vec<u8>>) -> bool {
+            let reversed: Vec<_> = ints.iter().map(|&x| Reverse(x)).collect();
+            let pairs: Vec<_> = ints.iter().map(|&x| (x as u8, x)).collect();
+            let bytes: Vec<_> = bytes
+                .iter()
+                .map(|b| BoundedBytes::<2>::new(&b[..b.len().min(2)]).unwrap())
+                .collect();
+
+            check_radix_consistency(&ints).is_ok()
+                && check_radix_consistency(&reversed).is_ok()
+                && check_radix_consistency(&pairs).is_ok()
+                && check_radix_consistency(&bytes).is_ok()
+        }
+
+        quickcheck(prop as fn(Vec<i32>, Vec<Vec<u8>>) -> bool);
+    }
+
+    #[test]
+    fn inconsistent_radix() {
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct LowBits(u8);
+
+        impl Radix for LowBits {
+            fn radix_similarity(&self, other: &LowBits) -> u32 {
+                (self.0 ^ other.0).trailing_zeros()
+            }
+
+            const RADIX_BITS: u32 = 8;
+        }
+
+        let keys: Vec<_> = (0..=255).map(LowBits).collect();
+        let error = check_radix_consistency(&keys).unwrap_err();
+        assert!(error.description.contains("less similar"));
+    }
+}
@@ -0,0 +1,61 @@
+//! [Kani](https://model-checking.github.io/kani/) proof harnesses.
+//!
+//! These only build under Kani, which sets `cfg(kani)`, and are checked
+//! with `cargo kani`. Each harness pushes a bounded number of arbitrary keys,
+//! so the proofs cover every heap of up to [`ENTRIES`] entries.
+
+use crate::RadixHeapMap;
+
+/// The number of entries the harnesses push. Loops in the heap run at most
+/// once per bucket, so unwinding 10 times is enough for `u8` keys.
+const ENTRIES: usize = 4;
+
+/// Pushing keys no larger than the top key and popping never panics, and
+/// `len` counts the entries pushed and not yet popped.
+#[kani::proof]
+#[kani::unwind(10)]
+fn push_pop_len() {
+    let top: u8 = kani::any();
+    let mut heap = RadixHeapMap::new_at(top);
+    let mut len = 0;
+
+    for _ in 0..ENTRIES {
+        if kani::any() {
+            let key: u8 = kani::any();
+            kani::assume(heap.top().map_or(true, |top| key <= top));
+            heap.push(key, ());
+            len += 1;
+        } else if heap.pop().is_some() {
+            len -= 1;
+        }
+
+        assert_eq!(heap.len(), len);
+        assert_eq!(heap.is_empty(), len == 0);
+    }
+}
+
+/// Every pop returns an entry with the greatest key left in the heap, and
+/// every entry pushed is popped exactly once.
+#[kani::proof]
+#[kani::unwind(10)]
+fn pop_returns_max() {
+    let mut heap = RadixHeapMap::new();
+    let mut keys = [0u8; ENTRIES];
+
+    for (i, key) in keys.iter_mut().enumerate() {
+        *key = kani::any();
+        heap.push(*key, i);
+    }
+
+    let mut popped = [false; ENTRIES];
+
+    while let Some((key, i)) = heap.pop() {
+        assert_eq!(keys[i], key);
+        assert!(!popped[i]);
+        popped[i] = true;
+
+        assert!(keys.iter().zip(&popped).all(|(&k, &p)| p || k <= key));
+    }
+
+    assert!(popped.iter().all(|&popped| popped));
+}
@@ -0,0 +1,94 @@
+//! JavaScript bindings for radix-heap.
+//!
+//! This exposes a max-heap with `u32` keys and arbitrary JavaScript values
+//! through wasm-bindgen.
+
+use js_sys::Array;
+use radix_heap::RadixHeapMap;
+use wasm_bindgen::prelude::*;
+
+/// A monotone max-heap of `u32` keys and JavaScript values.
+///
+/// Pushed keys must not be larger than the last popped key.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct RadixHeapU32 {
+    heap: RadixHeapMap<u32, JsValue>,
+}
+
+#[wasm_bindgen]
+impl RadixHeapU32 {
+    /// Creates an empty heap.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> RadixHeapU32 {
+        RadixHeapU32::default()
+    }
+
+    /// Creates a heap from an array of `[key, value]` pairs. Throws if an
+    /// entry is not such a pair.
+    #[wasm_bindgen(js_name = fromEntries)]
+    pub fn from_entries(entries: &Array) -> Result<RadixHeapU32, JsError> {
+        let mut heap = RadixHeapMap::new();
+
+        for entry in entries.iter() {
+            let entry = Array::from(&entry);
+            let key = entry
+                .get(0)
+                .as_f64()
+                .filter(|&key| key >= 0.0 && key <= u32::MAX as f64 && key.fract() == 0.0)
+                .ok_or_else(|| JsError::new("entry key is not a u32"))?;
+
+            heap.push(key as u32, entry.get(1));
+        }
+
+        Ok(RadixHeapU32 { heap })
+    }
+
+    /// Pushes a key and value. Throws if the key is larger than the top key.
+    pub fn push(&mut self, key: u32, value: JsValue) -> Result<(), JsError> {
+        match self.heap.top() {
+            Some(top) if key > top => Err(JsError::new(&format!(
+                "key {} is larger than the top key {}",
+                key, top
+            ))),
+            _ => {
+                self.heap.push(key, value);
+                Ok(())
+            }
+        }
+    }
+
+    /// Removes the entry with the largest key and returns it as a
+    /// `[key, value]` pair, or `undefined` if empty.
+    pub fn pop(&mut self) -> Option<Array> {
+        self.heap.pop().map(|(key, value)| entry(key, &value))
+    }
+
+    /// Returns the entry with the largest key as a `[key, value]` pair
+    /// without removing it, or `undefined` if empty.
+    pub fn peek(&mut self) -> Option<Array> {
+        self.heap.peek().map(|(key, value)| entry(*key, value))
+    }
+
+    /// The top key, which pushed keys must not be larger than, or
+    /// `undefined` if not set yet.
+    #[wasm_bindgen(getter)]
+    pub fn top(&self) -> Option<u32> {
+        self.heap.top()
+    }
+
+    /// The number of entries in the heap.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Removes all entries and resets the top key.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+fn entry(key: u32, value: &JsValue) -> Array {
+    Array::of2(&key.into(), value)
+}